@@ -8,9 +8,9 @@ enum Method {
     Post,
     Put,
     Delete,
+    Patch,
     // Options,
     // Head,
-    // Patch,
     // Trace,
 }
 
@@ -34,14 +34,29 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Immich endpoints required by immichctl
     let allowed: HashMap<&str, Vec<Method>> = HashMap::from([
         ("/server/version", vec![Method::Get]),
+        ("/server/ping", vec![Method::Get]),
+        ("/users/me", vec![Method::Get]),
         ("/auth/validateToken", vec![Method::Post]),
         ("/search/metadata", vec![Method::Post]),
+        ("/search/smart", vec![Method::Post]),
+        ("/assets/jobs", vec![Method::Post]),
         ("/assets/{id}", vec![Method::Get, Method::Put]),
+        ("/assets/{id}/original", vec![Method::Get]),
         ("/tags", vec![Method::Get, Method::Post]),
-        ("/tags/{id}", vec![Method::Delete]),
+        ("/tags/{id}", vec![Method::Put, Method::Delete]),
         ("/tags/{id}/assets", vec![Method::Put, Method::Delete]),
         ("/albums", vec![Method::Get]),
+        ("/libraries", vec![Method::Get]),
+        ("/api-keys", vec![Method::Get]),
+        ("/api-keys/me", vec![Method::Get]),
+        ("/api-keys/{id}", vec![Method::Delete]),
+        ("/albums/{id}", vec![Method::Get, Method::Patch]),
         ("/albums/{id}/assets", vec![Method::Put, Method::Delete]),
+        ("/shared-links", vec![Method::Post]),
+        ("/shared-links/me", vec![Method::Get]),
+        ("/shared-links/login", vec![Method::Post]),
+        ("/timeline/buckets", vec![Method::Get]),
+        ("/timeline/bucket", vec![Method::Get]),
         ("/download/info", vec![Method::Post]),
         ("/download/archive", vec![Method::Post]),
     ]);
@@ -67,9 +82,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         if pi.delete.is_some() && !methods.contains(&Method::Delete) {
             pi.delete = None;
         }
+        if pi.patch.is_some() && !methods.contains(&Method::Patch) {
+            pi.patch = None;
+        }
         pi.head = None;
         pi.options = None;
-        pi.patch = None;
         pi.trace = None;
         true
     });
@@ -79,6 +96,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Generate Rust client code using progenitor
     let mut settings = progenitor::GenerationSettings::default();
     settings.with_derive("PartialEq");
+    settings.with_derive("schemars::JsonSchema");
     let mut generator = progenitor::Generator::new(&settings);
     let tokens = generator
         .generate_tokens(&spec)
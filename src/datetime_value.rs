@@ -0,0 +1,66 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use std::ops::Deref;
+use std::str::FromStr;
+
+use crate::timedelta::TimeDeltaValue;
+
+/// Absolute date/time (RFC 3339) or a time delta relative to now (e.g. `7d` = 7 days ago).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTimeValue(DateTime<FixedOffset>);
+
+impl Deref for DateTimeValue {
+    type Target = DateTime<FixedOffset>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl FromStr for DateTimeValue {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, Utc::now())
+    }
+}
+
+impl DateTimeValue {
+    fn parse(s: &str, now: DateTime<Utc>) -> anyhow::Result<Self> {
+        if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+            return Ok(DateTimeValue(dt));
+        }
+        let delta = TimeDeltaValue::from_str(s).map_err(|_| {
+            anyhow::anyhow!(
+                "Invalid date/time '{}', expected YYYY-MM-DDTHH:MM:SS±00:00 or a relative value like 7d",
+                s
+            )
+        })?;
+        let dt = now
+            .checked_sub_signed(*delta)
+            .ok_or_else(|| anyhow::anyhow!("Date/time '{}' out of range", s))?;
+        Ok(DateTimeValue(dt.fixed_offset()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeDelta, TimeZone};
+
+    #[test]
+    fn test_parse() {
+        let now = Utc.with_ymd_and_hms(2025, 10, 7, 12, 0, 0).unwrap();
+
+        let dt = DateTimeValue::parse("2025-10-01T18:00:00+02:00", now).unwrap();
+        assert_eq!(dt.to_rfc3339(), "2025-10-01T18:00:00+02:00");
+
+        let dt = DateTimeValue::parse("7d", now).unwrap();
+        assert_eq!(*dt, now - TimeDelta::days(7));
+
+        let dt = DateTimeValue::parse("1d12h", now).unwrap();
+        assert_eq!(*dt, now - TimeDelta::hours(36));
+
+        assert!(DateTimeValue::parse("yesterday", now).is_err());
+        assert!(DateTimeValue::parse("2025-10-01", now).is_err());
+    }
+}
@@ -1,50 +1,304 @@
 mod album_cmd;
+mod api_error;
+mod apikey_cmd;
 mod asset_cmd;
 mod assets;
+pub mod color;
 mod config;
+mod config_cmd;
 mod curl_cmd;
 mod download_cmd;
+mod history;
+mod history_cmd;
+mod ids;
+mod library_cmd;
+mod membership_cmd;
+mod netrc;
+pub mod output;
+mod sample_cmd;
 mod server_cmd;
+mod shared_link_cmd;
+mod stats_cmd;
 mod tag_cmd;
+mod timeline_cmd;
+mod timezone;
 
 include!(concat!(env!("OUT_DIR"), "/codegen.rs"));
 
-pub use asset_cmd::{AssetColumns, AssetSearchArgs};
+pub use album_cmd::{AlbumExportFormat, AlbumShowFormat};
+pub use asset_cmd::{
+    AssetColumns, AssetFilter, AssetSearchArgs, ClearFilterArgs, CsvQuoting, ListExtreme,
+    ListFormat,
+};
+pub use color::ColorMode;
 pub use curl_cmd::CurlMethod;
+pub use membership_cmd::MembershipFormat;
+pub use output::OutputMode;
+pub use sample_cmd::SampleMode;
+pub use stats_cmd::StatsFormat;
+pub use tag_cmd::TagMatch;
+pub use timeline_cmd::{TimelineFormat, TimelineGroup};
+pub use timezone::TimeZoneValue;
 
-use anyhow::{Result, anyhow, bail};
+use anyhow::{Context, Result, anyhow, bail};
+use assets::Assets;
+use chrono::{DateTime, FixedOffset};
 use config::Config;
+use futures::StreamExt;
+use std::io::IsTerminal;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use types::{BulkIdResponseDto, BulkIdsDto};
+
+/// `User-Agent` header of all requests
+const USER_AGENT: &str = concat!("immichctl/", env!("CARGO_PKG_VERSION"));
+
+/// Default number of asset ids per bulk request
+pub const DEFAULT_BATCH_SIZE: NonZeroUsize = NonZeroUsize::new(500).unwrap();
+
+/// Default number of parallel read requests (e.g. asset retrieval of `assets refresh`)
+pub const DEFAULT_READ_CONCURRENCY: NonZeroUsize = NonZeroUsize::new(8).unwrap();
+
+/// Default number of parallel write requests (asset updates, tag/album bulk requests).
+/// Lower than for reads to avoid DB contention on small servers.
+pub const DEFAULT_WRITE_CONCURRENCY: NonZeroUsize = NonZeroUsize::new(4).unwrap();
 
 pub struct ImmichCtl {
     config: Config,
     immich: Result<Client>,
     assets_file: PathBuf,
+    history_file: PathBuf,
+    quiet: bool,
+    verbose: bool,
+    dry_run: bool,
+    count_only: bool,
+    color: bool,
+    pretty_json: bool,
+    output: OutputMode,
+    batch_size: NonZeroUsize,
+    read_concurrency: NonZeroUsize,
+    write_concurrency: NonZeroUsize,
+    assume_tz: Option<TimeZoneValue>,
+    csv_quoting: CsvQuoting,
+    list_header: bool,
+    list_summary: bool,
+    since: Option<DateTime<FixedOffset>>,
+    until: Option<DateTime<FixedOffset>>,
+    request_id: String,
 }
 
 impl ImmichCtl {
-    pub fn new() -> Self {
-        let config_dir =
-            Self::get_default_config_dir().expect("Could not determine config directory");
-        Self::with_config_dir(&config_dir)
+    /// Create an `ImmichCtl` using `config_file`, the file set by the `IMMICHCTL_CONFIG`
+    /// env variable or `config.json` in the default config directory (in this order).
+    /// The asset selection is stored in `selection_file` if given, otherwise in
+    /// `assets.json` next to the config file.
+    pub fn new(config_file: Option<&Path>, selection_file: Option<&Path>) -> Self {
+        let config_file =
+            Self::resolve_config_file(config_file, std::env::var_os("IMMICHCTL_CONFIG"));
+        let mut ctl = match config_file {
+            Some(config_file) => Self::with_config_file(&config_file),
+            None => {
+                let config_dir =
+                    Self::get_default_config_dir().expect("Could not determine config directory");
+                Self::with_config_dir(&config_dir)
+            }
+        };
+        if let Some(selection_file) = selection_file {
+            ctl.assets_file = selection_file.to_path_buf();
+        }
+        ctl
+    }
+
+    fn resolve_config_file(
+        config_file: Option<&Path>,
+        env_config_file: Option<std::ffi::OsString>,
+    ) -> Option<PathBuf> {
+        match config_file {
+            Some(config_file) => Some(config_file.to_path_buf()),
+            None => env_config_file.filter(|f| !f.is_empty()).map(PathBuf::from),
+        }
     }
 
     pub fn with_config_dir(config_dir: &Path) -> Self {
-        let config_file = config_dir.join("config.json");
-        let config = Config::load(&config_file);
-        let assets_file = config_dir.join("assets.json");
+        Self::with_config_file(&config_dir.join("config.json"))
+    }
+
+    /// Create an `ImmichCtl` using `config_file`, the asset selection is stored next to it.
+    pub fn with_config_file(config_file: &Path) -> Self {
+        let config = Config::load(config_file);
+        let assets_file = config_file.with_file_name("assets.json");
+        let history_file = config_file.with_file_name("history.jsonl");
 
         // immich client gets rebuild when config changes, i.e. for login command
-        let immich = Self::build_client(&config);
+        let request_id = uuid::Uuid::new_v4().to_string();
+        let immich = Self::build_client(&config, &request_id);
+        let assume_tz = config.default_timezone;
 
         ImmichCtl {
             config,
             immich,
             assets_file,
+            history_file,
+            quiet: false,
+            verbose: false,
+            dry_run: false,
+            count_only: false,
+            color: false,
+            pretty_json: false,
+            output: OutputMode::Text,
+            batch_size: DEFAULT_BATCH_SIZE,
+            read_concurrency: DEFAULT_READ_CONCURRENCY,
+            write_concurrency: DEFAULT_WRITE_CONCURRENCY,
+            assume_tz,
+            csv_quoting: CsvQuoting::Minimal,
+            list_header: false,
+            list_summary: false,
+            since: None,
+            until: None,
+            request_id,
+        }
+    }
+
+    /// Suppress progress output.
+    pub fn set_quiet(&mut self, quiet: bool) {
+        self.quiet = quiet;
+    }
+
+    /// Print additional warnings.
+    pub fn set_verbose(&mut self, verbose: bool) {
+        self.verbose = verbose;
+    }
+
+    /// Maximal number of asset ids sent in one bulk request.
+    pub fn set_batch_size(&mut self, batch_size: NonZeroUsize) {
+        self.batch_size = batch_size;
+    }
+
+    /// Maximal number of parallel read requests.
+    pub fn set_read_concurrency(&mut self, concurrency: NonZeroUsize) {
+        self.read_concurrency = concurrency;
+    }
+
+    /// Maximal number of parallel write requests.
+    pub fn set_write_concurrency(&mut self, concurrency: NonZeroUsize) {
+        self.write_concurrency = concurrency;
+    }
+
+    /// Timezone for assets without EXIF timezone, used instead of the timezone
+    /// derived from asset metadata. Defaults to the `default_timezone` of the config.
+    pub fn set_assume_tz(&mut self, assume_tz: Option<FixedOffset>) {
+        self.assume_tz = assume_tz
+            .map(TimeZoneValue::from)
+            .or(self.config.default_timezone);
+    }
+
+    /// Quoting of fields in csv asset lists.
+    pub fn set_csv_quoting(&mut self, quoting: CsvQuoting) {
+        self.csv_quoting = quoting;
+    }
+
+    /// Start csv and tsv asset lists with a line of column names.
+    pub fn set_list_header(&mut self, header: bool) {
+        self.list_header = header;
+    }
+
+    /// Print a `# N assets` summary line to stderr after asset lists.
+    pub fn set_list_summary(&mut self, summary: bool) {
+        self.list_summary = summary;
+    }
+
+    /// Restrict commands working on the local selection to assets taken at or after
+    /// `since` and before `until`.
+    pub fn set_date_window(
+        &mut self,
+        since: Option<DateTime<FixedOffset>>,
+        until: Option<DateTime<FixedOffset>>,
+    ) {
+        self.since = since;
+        self.until = until;
+    }
+
+    /// Value of the `x-request-id` header sent with every request, defaults to a random UUID
+    /// per invocation.
+    pub fn set_request_id(&mut self, request_id: &str) -> Result<()> {
+        reqwest::header::HeaderValue::from_str(request_id)
+            .map_err(|_| anyhow!("Invalid request id '{}'", request_id))?;
+        self.request_id = request_id.to_string();
+        self.immich = Self::build_client(&self.config, &self.request_id);
+        Ok(())
+    }
+
+    /// Complete a missing server and/or API key from `netrc_file`, `$NETRC` or `~/.netrc`.
+    /// Values of the config file take precedence, netrc values are never saved.
+    pub fn use_netrc(&mut self, netrc_file: Option<&Path>) -> Result<()> {
+        if self.config.logged_in() {
+            return Ok(());
+        }
+        let Some(netrc_file) = netrc_file
+            .map(Path::to_path_buf)
+            .or_else(netrc::default_netrc_file)
+        else {
+            return Ok(());
+        };
+        let contents = std::fs::read_to_string(&netrc_file)
+            .with_context(|| format!("Could not read netrc file '{}'", netrc_file.display()))?;
+        if self
+            .config
+            .complete_from_netrc(&netrc::parse_netrc(&contents))
+        {
+            self.immich = Self::build_client(&self.config, &self.request_id);
+        } else if self.verbose {
+            self.eprint_warning(&format!(
+                "No matching entry for immichctl in netrc file '{}'.",
+                netrc_file.display()
+            ));
         }
+        Ok(())
+    }
+
+    /// Print only the number of changed assets to stdout instead of human readable
+    /// summaries (tag and album assign).
+    pub fn set_count_only(&mut self, count_only: bool) {
+        self.count_only = count_only;
+    }
+
+    /// Color warnings on stderr according to `mode`.
+    pub fn set_color(&mut self, mode: ColorMode) {
+        self.color = mode.stderr_enabled();
+    }
+
+    /// Indent json written to stdout, e.g. by `assets list --format json` or `curl`.
+    pub fn set_pretty_json(&mut self, pretty: bool) {
+        self.pretty_json = pretty;
+    }
+
+    /// Format of warnings on stderr, json lines with `OutputMode::Json`.
+    pub fn set_output(&mut self, output: OutputMode) {
+        self.output = output;
+    }
+
+    /// Print `value` as json to stdout, indented according to `set_pretty_json`.
+    pub fn print_json<T: serde::Serialize + ?Sized>(&self, value: &T) -> Result<()> {
+        output::print_json(value, self.pretty_json)
+    }
+
+    /// Print the intended effect of mutating commands instead of performing
+    /// server writes or saving the asset selection.
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
     }
 
+    /// The directory set by the `IMMICHCTL_CONFIG_DIR` env variable (e.g. a volume in a
+    /// container), otherwise `$HOME/.immichctl`.
     pub fn get_default_config_dir() -> Result<PathBuf> {
+        Self::resolve_default_config_dir(std::env::var_os("IMMICHCTL_CONFIG_DIR"))
+    }
+
+    fn resolve_default_config_dir(env_config_dir: Option<std::ffi::OsString>) -> Result<PathBuf> {
+        if let Some(config_dir) = env_config_dir.filter(|d| !d.is_empty()) {
+            return Ok(PathBuf::from(config_dir));
+        }
         let Some(mut path) = dirs::home_dir() else {
             bail!("Could not determine home directory")
         };
@@ -52,21 +306,39 @@ impl ImmichCtl {
         Ok(path)
     }
 
-    fn build_client(config: &Config) -> Result<Client> {
+    fn build_client(config: &Config, request_id: &str) -> Result<Client> {
+        Self::build_client_with_headers(config, request_id, reqwest::header::HeaderMap::new())
+    }
+
+    /// Build a client that sends `headers` in addition to the default headers,
+    /// e.g. the cookie of a shared link login.
+    fn build_client_with_headers(
+        config: &Config,
+        request_id: &str,
+        mut headers: reqwest::header::HeaderMap,
+    ) -> Result<Client> {
+        if let Some(err) = config.load_error() {
+            bail!("{}", err)
+        }
         if !config.logged_in() {
             bail!("Not logged in. Use 'immichctl login <URL> --apikey <KEY>' to login.")
         }
 
-        let mut headers = reqwest::header::HeaderMap::new();
         headers.insert(
             "x-api-key",
-            reqwest::header::HeaderValue::from_str(&config.apikey).unwrap(),
+            reqwest::header::HeaderValue::from_str(&config.apikey)
+                .context("Invalid API key in configuration, please login again.")?,
+        );
+        headers.insert(
+            "x-request-id",
+            reqwest::header::HeaderValue::from_str(request_id)?,
         );
         let client_with_custom_defaults = reqwest::ClientBuilder::new()
+            .user_agent(USER_AGENT)
             .default_headers(headers)
             .connection_verbose(true)
             .build()?;
-        let immich_api_url = config.server.clone() + "/api";
+        let immich_api_url = config.server_url()? + "/api";
         Ok(Client::new_with_client(
             &immich_api_url,
             client_with_custom_defaults,
@@ -83,7 +355,7 @@ impl ImmichCtl {
     ///
     /// ```
     /// # within an fn implementation of ImmichCtl
-    /// let version = self.immich()?.get_server_version().await.context("Could not get server version")?;
+    /// let version = self.immich()?.get_server_version().api_context("Could not get server version").await?;
     /// ```
     pub fn immich(&self) -> Result<&Client> {
         match &self.immich {
@@ -93,20 +365,166 @@ impl ImmichCtl {
     }
 
     pub fn assert_logged_in(&self) -> Result<()> {
+        if let Some(err) = self.config.load_error() {
+            bail!("{}", err)
+        }
         if !self.config.logged_in() {
             bail!("Not logged in. Use 'immichctl login <URL> --apikey <KEY>' to login.")
         }
         Ok(())
     }
 
+    /// Whether the user can answer prompts, i.e. stdin and stderr are terminals.
+    pub fn is_interactive() -> bool {
+        std::io::stdin().is_terminal() && std::io::stderr().is_terminal()
+    }
+
+    /// Fail instead of blocking on a prompt if not running interactively.
+    pub fn assert_interactive(&self, command: &str) -> Result<()> {
+        if !Self::is_interactive() {
+            bail!(
+                "'{}' prompts for input and needs an interactive terminal (stdin and stderr).",
+                command
+            )
+        }
+        Ok(())
+    }
+
+    /// Print `msg` to stderr or only `count` to stdout with `--count-only`.
+    fn eprint_or_count(&self, msg: &str, count: usize) {
+        if self.count_only {
+            println!("{}", count);
+        } else {
+            eprintln!("{}", msg);
+        }
+    }
+
+    /// Print a warning to stderr that doesn't abort the command.
+    pub fn eprint_warning(&self, msg: &str) {
+        self.warn(msg, None);
+    }
+
+    /// Load the asset selection. A selection without server gets the configured server
+    /// recorded, a selection built on another server is loaded with a warning.
+    pub fn load_assets(&self) -> Assets {
+        let mut sel = Assets::load(&self.assets_file);
+        if let Some(msg) = self.server_mismatch(&sel) {
+            self.eprint_warning(&msg);
+        }
+        if (sel.server().is_none() || sel.is_empty())
+            && let Some(server) = self.selection_server()
+        {
+            sel.set_server(&server);
+        }
+        sel
+    }
+
+    /// The expanded server URL recorded in the selection, `None` if not logged in or if
+    /// the URL references an unset env variable.
+    fn selection_server(&self) -> Option<String> {
+        if !self.config.logged_in() {
+            return None;
+        }
+        self.config.server_url().ok()
+    }
+
+    /// Warning message if the non-empty selection was built on another server than the
+    /// configured one.
+    fn server_mismatch(&self, sel: &Assets) -> Option<String> {
+        let server = sel.server()?;
+        let configured = self.selection_server()?;
+        if sel.is_empty() || server.trim_end_matches('/') == configured {
+            return None;
+        }
+        Some(format!(
+            "The selection was built on server '{}', but the configured server is '{}'. Its asset ids may not exist or refer to other assets on this server, run 'immichctl assets clear --yes' to start a new selection.",
+            server, configured
+        ))
+    }
+
+    /// Print a warning about a single asset to stderr that doesn't abort the command.
+    pub fn eprint_asset_warning(&self, msg: &str, asset: &uuid::Uuid) {
+        self.warn(msg, Some(asset));
+    }
+
+    /// Warnings are json lines with `--output json`, colored text otherwise.
+    fn warn(&self, msg: &str, asset: Option<&uuid::Uuid>) {
+        match self.output {
+            OutputMode::Json => eprintln!("{}", output::warning_json(msg, asset)),
+            OutputMode::Text => {
+                eprintln!("{} {}", color::yellow("Warning:", self.color), msg)
+            }
+        }
+    }
+
+    /// Print progress of a loop over `total` items, `current` is the 0-based index.
+    ///
+    /// On a terminal a percentage line is repainted every `delta` items. Otherwise
+    /// (e.g. CI logs) only a single summary line is printed after the last item.
     pub fn eprint_progress_indicator(&self, current: usize, total: usize, delta: usize) {
-        if current == 0 || current == total - 1 || current.is_multiple_of(delta) {
-            let percentage = current as f32 / total as f32 * 100.0;
-            eprint!("\r{:.0}%", percentage);
+        if self.quiet {
+            return;
+        }
+        let last = current + 1 == total;
+        if std::io::stderr().is_terminal() {
+            if current == 0 || last || current.is_multiple_of(delta) {
+                let percentage = (current + 1) as f32 / total as f32 * 100.0;
+                eprint!("\r{:.0}%", percentage);
+            }
+            if last {
+                eprintln!();
+            }
+        } else if last {
+            eprintln!("Processed {}/{}", current + 1, total);
+        }
+    }
+
+    /// Print how long a phase of a command took to stderr, only with `--verbose`.
+    pub fn eprint_timing(&self, phase: &str, start: Instant) {
+        if self.verbose {
+            eprintln!("{}", Self::format_timing(phase, start.elapsed()));
         }
-        if current == total - 1 {
-            eprintln!();
+    }
+
+    fn format_timing(phase: &str, elapsed: Duration) -> String {
+        format!("Timing: {} took {:.2?}", phase, elapsed)
+    }
+
+    /// Send `ids` in bulk requests of at most `batch_size` ids, up to `write_concurrency`
+    /// requests in parallel. Returns the per-id results of all requests, fails on the first
+    /// failed request. If several requests are needed, progress is shown per finished request.
+    pub async fn bulk_write<F, Fut>(
+        &self,
+        ids: &[uuid::Uuid],
+        request: F,
+    ) -> Result<Vec<BulkIdResponseDto>>
+    where
+        F: Fn(BulkIdsDto) -> Fut,
+        Fut: std::future::Future<Output = Result<Vec<BulkIdResponseDto>>>,
+    {
+        let total = ids.len();
+        let mut requests = futures::stream::iter(ids.chunks(self.batch_size.get()))
+            .map(|ids| {
+                let len = ids.len();
+                let resp = request(BulkIdsDto { ids: ids.to_vec() });
+                async move {
+                    let start = Instant::now();
+                    resp.await.map(|resp| (len, resp, start))
+                }
+            })
+            .buffer_unordered(self.write_concurrency.get());
+        let mut results = Vec::with_capacity(total);
+        let mut done = 0;
+        while let Some(resp) = requests.next().await {
+            let (len, mut resp, start) = resp?;
+            self.eprint_timing(&format!("bulk request of {} ids", len), start);
+            results.append(&mut resp);
+            done += len;
+            if total > self.batch_size.get() {
+                self.eprint_progress_indicator(done - 1, total, 1);
+            }
         }
+        Ok(results)
     }
 }
 
@@ -127,12 +545,123 @@ mod tests {
         (ctl, server)
     }
 
+    #[tokio::test]
+    async fn test_request_headers() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+        ctl.set_request_id("my-request")?;
+        assert!(ctl.set_request_id("invalid\nid").is_err());
+
+        let mock = server
+            .mock("GET", "/api/server/ping")
+            .match_header("user-agent", USER_AGENT)
+            .match_header("x-request-id", "my-request")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"res":"pong"}"#)
+            .create_async()
+            .await;
+        ctl.immich()?.ping_server().await?;
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_timing() {
+        assert_eq!(
+            ImmichCtl::format_timing("search, 3 pages", Duration::from_millis(1240)),
+            "Timing: search, 3 pages took 1.24s"
+        );
+        assert_eq!(
+            ImmichCtl::format_timing("save", Duration::from_micros(12_500)),
+            "Timing: save took 12.50ms"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_apikey_fails_lazily() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let mut config = Config::load(&config_dir.path().join("config.json"));
+        config.server = "http://localhost:2283".to_string();
+        config.apikey = "invalid\nkey".to_string();
+        config.save().unwrap();
+
+        // construction and local commands succeed
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+        assert!(ctl.assets_count(OutputMode::Text).is_ok());
+
+        // the first server call reports the invalid key
+        let err = ctl.version().await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid API key in configuration, please login again."
+        );
+    }
+
     #[test]
     fn test_get_default_config_dir() {
-        let path = ImmichCtl::get_default_config_dir().expect("no home path");
+        let path = ImmichCtl::resolve_default_config_dir(None).expect("no home path");
+        assert!(path.ends_with(".immichctl"));
+        let path = ImmichCtl::resolve_default_config_dir(Some(std::ffi::OsString::new()))
+            .expect("no home path");
         assert!(path.ends_with(".immichctl"));
     }
 
+    #[test]
+    fn test_config_dir_from_env() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_dir =
+            ImmichCtl::resolve_default_config_dir(Some(dir.path().as_os_str().to_owned())).unwrap();
+        assert_eq!(config_dir, dir.path());
+        let ctl = ImmichCtl::with_config_dir(&config_dir);
+        assert_eq!(ctl.config.config_file(), dir.path().join("config.json"));
+        assert_eq!(ctl.assets_file, dir.path().join("assets.json"));
+        assert_eq!(ctl.history_file, dir.path().join("history.jsonl"));
+    }
+
+    #[test]
+    fn test_new_with_selection_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let selection_file = dir.path().join("project").join("selection.json");
+        let ctl = ImmichCtl::new(None, Some(&selection_file));
+        assert_eq!(ctl.assets_file, selection_file);
+
+        let ctl = ImmichCtl::new(None, None);
+        assert!(ctl.assets_file.ends_with(".immichctl/assets.json"));
+    }
+
+    #[test]
+    fn test_new_with_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_file = dir.path().join("prod").join("immich.json");
+        let ctl = ImmichCtl::new(Some(&config_file), None);
+        ctl.config.save().unwrap();
+        assert!(config_file.exists());
+        assert_eq!(ctl.assets_file, dir.path().join("prod").join("assets.json"));
+
+        let selection_file = dir.path().join("selection.json");
+        let ctl = ImmichCtl::new(Some(&config_file), Some(&selection_file));
+        assert_eq!(ctl.assets_file, selection_file);
+    }
+
+    #[test]
+    fn test_resolve_config_file() {
+        let cli_file = Path::new("/cli/config.json");
+        let env_file = Some(std::ffi::OsString::from("/env/config.json"));
+        assert_eq!(
+            ImmichCtl::resolve_config_file(Some(cli_file), env_file.clone()),
+            Some(cli_file.to_path_buf())
+        );
+        assert_eq!(
+            ImmichCtl::resolve_config_file(None, env_file),
+            Some(PathBuf::from("/env/config.json"))
+        );
+        assert_eq!(
+            ImmichCtl::resolve_config_file(None, Some(std::ffi::OsString::new())),
+            None
+        );
+        assert_eq!(ImmichCtl::resolve_config_file(None, None), None);
+    }
+
     #[test]
     fn test_with_config_dir() {
         let config_dir = tempfile::tempdir().unwrap();
@@ -145,4 +674,113 @@ mod tests {
             "Not logged in. Use 'immichctl login <URL> --apikey <KEY>' to login."
         );
     }
+
+    #[test]
+    fn test_use_netrc() -> Result<()> {
+        let config_dir = tempfile::tempdir()?;
+        let netrc_file = config_dir.path().join("netrc");
+        std::fs::write(
+            &netrc_file,
+            "machine immich.example.com login immichctl password key1\n",
+        )?;
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
+        ctl.use_netrc(Some(&netrc_file))?;
+        assert!(ctl.assert_logged_in().is_ok());
+        assert!(ctl.immich().is_ok());
+        assert_eq!(ctl.config.server, "https://immich.example.com");
+
+        let missing = config_dir.path().join("missing");
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
+        assert!(ctl.use_netrc(Some(&missing)).is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_server_mismatch() -> Result<()> {
+        let (mut ctl, server) = create_immichctl_with_server().await;
+        let asset = crate::immichctl::asset_cmd::tests::create_asset_for_download(
+            uuid::Uuid::new_v4(),
+            "A.jpg",
+            "/upload/A.jpg",
+        );
+
+        // the configured server is recorded for a new selection
+        let mut sel = ctl.load_assets();
+        assert_eq!(sel.server(), Some(server.url().as_str()));
+        sel.add_asset(asset);
+        sel.save()?;
+        let sel = ctl.load_assets();
+        assert_eq!(ctl.server_mismatch(&sel), None);
+
+        // selection of another server
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.set_server("https://other.example.com");
+        sel.save()?;
+        let sel = ctl.load_assets();
+        assert_eq!(sel.server(), Some("https://other.example.com"));
+        let msg = ctl.server_mismatch(&sel).unwrap();
+        assert!(msg.starts_with(&format!(
+            "The selection was built on server 'https://other.example.com', but the configured server is '{}'.",
+            server.url()
+        )));
+
+        // a cleared selection belongs to the configured server again
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.clear();
+        sel.save()?;
+        let sel = ctl.load_assets();
+        assert_eq!(sel.server(), Some(server.url().as_str()));
+
+        // an unresolved server URL template is neither recorded nor compared
+        ctl.config
+            .set_login("${IMMICHCTL_TEST_UNDEFINED_VAR}", "key");
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.clear();
+        sel.save()?;
+        let mut sel = ctl.load_assets();
+        assert_eq!(sel.server(), None);
+        sel.set_server("https://other.example.com");
+        sel.add_asset(
+            crate::immichctl::asset_cmd::tests::create_asset_for_download(
+                uuid::Uuid::new_v4(),
+                "B.jpg",
+                "/upload/B.jpg",
+            ),
+        );
+        assert_eq!(ctl.server_mismatch(&sel), None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bulk_write_batches() -> Result<()> {
+        let (mut ctl, _server) = create_immichctl_with_server().await;
+        ctl.set_batch_size(NonZeroUsize::new(2).unwrap());
+        ctl.set_write_concurrency(NonZeroUsize::new(2).unwrap());
+        let ids: Vec<uuid::Uuid> = (0..5).map(|_| uuid::Uuid::new_v4()).collect();
+        let requests = std::sync::atomic::AtomicUsize::new(0);
+
+        let results = ctl
+            .bulk_write(&ids, |dto| {
+                let requests = &requests;
+                async move {
+                    requests.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    assert!(dto.ids.len() <= 2);
+                    Ok(dto
+                        .ids
+                        .into_iter()
+                        .map(|id| BulkIdResponseDto {
+                            id,
+                            success: true,
+                            error: None,
+                            error_message: None,
+                        })
+                        .collect())
+                }
+            })
+            .await?;
+        assert_eq!(requests.into_inner(), 3);
+        let written: std::collections::HashSet<_> = results.into_iter().map(|r| r.id).collect();
+        assert_eq!(written, ids.into_iter().collect());
+        Ok(())
+    }
 }
@@ -1,49 +1,465 @@
 use super::ImmichCtl;
-use super::assets::Assets;
-use super::types::{AlbumResponseDto, BulkIdsDto};
-use anyhow::{Context, Result, bail};
+use super::api_error::ApiContext;
+use super::output;
+use super::types::{
+    AlbumResponseDto, AssetResponseDto, BulkIdErrorReason, BulkIdsDto, MetadataSearchDto,
+    SharedLinkCreateDto, SharedLinkType, UpdateAlbumDto,
+};
+use anyhow::{Result, bail};
+use chrono::{TimeDelta, Utc};
+use futures::StreamExt;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
 use uuid::Uuid;
 
+/// Output format of `album show`
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum AlbumShowFormat {
+    /// Human readable text
+    Text,
+    /// Json format, pretty printed
+    Json,
+}
+
+/// Output format of `album export`
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum AlbumExportFormat {
+    /// Asset ids, one per line
+    Ids,
+    /// CSV with asset id and original file name
+    Csv,
+    /// Json format, pretty printed
+    Json,
+}
+
+/// Album details as printed by `album show --format json`.
+#[derive(Serialize)]
+struct AlbumInfo<'a> {
+    #[serde(flatten)]
+    album: &'a AlbumResponseDto,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    assets: Option<Vec<AssetResponseDto>>,
+}
+
 impl ImmichCtl {
-    pub async fn album_assign(&mut self, name: &str) -> Result<()> {
-        let sel = Assets::load(&self.assets_file);
+    /// Assign the selected assets to one or more albums given by name or to the album
+    /// `id`. All album names are resolved before any album is changed.
+    pub async fn album_assign(&mut self, names: &[String], id: Option<Uuid>) -> Result<()> {
+        let sel = self.load_assets();
         if sel.is_empty() {
-            eprintln!("Selection is empty, nothing to assign to album.");
+            self.eprint_or_count("Selection is empty, nothing to assign to album.", 0);
             return Ok(());
         }
 
-        let album_id = self.find_album_by_name(name).await?;
-        let dto = BulkIdsDto {
-            ids: sel.asset_uuids(),
+        let albums = self.resolve_albums(names, id).await?;
+        let asset_ids = sel.asset_uuids();
+        let mut total = 0;
+        for (name, album_id) in albums {
+            if self.dry_run {
+                eprintln!(
+                    "Dry run: would assign {} assets to album '{}'.",
+                    sel.len(),
+                    name
+                );
+                continue;
+            }
+            let name = &name;
+            let client = self.immich()?;
+            let resp = self
+                .bulk_write(&asset_ids, |dto| async move {
+                    let resp = client
+                        .add_assets_to_album(&album_id, &dto)
+                        .with_api_context(|| format!("Could not assign assets to album '{}'", name))
+                        .await?;
+                    Ok(resp.into_inner())
+                })
+                .await?;
+            let cnt = resp.iter().filter(|r| r.success).count();
+            if !self.count_only {
+                eprintln!("Assigned {} assets to album '{}'.", cnt, name);
+            }
+            self.record_history(&format!("album assign '{}'", name), cnt);
+            total += cnt;
+        }
+        if self.count_only {
+            println!("{}", total);
+        }
+        Ok(())
+    }
+
+    /// Unassign the selected assets from the album `name` or the album `id`.
+    pub async fn album_unassign(&mut self, name: Option<&str>, id: Option<Uuid>) -> Result<()> {
+        let sel = self.load_assets();
+        if sel.is_empty() {
+            eprintln!("Selection is empty, nothing to unassign.");
+            return Ok(());
+        }
+
+        let names: Vec<String> = name.iter().map(|n| n.to_string()).collect();
+        let Some((name, album_id)) = self.resolve_albums(&names, id).await?.pop() else {
+            bail!("Album name or --id required");
         };
+        if self.dry_run {
+            eprintln!(
+                "Dry run: would unassign {} assets from album '{}'.",
+                sel.len(),
+                name
+            );
+            return Ok(());
+        }
+        let client = self.immich()?;
         let resp = self
-            .immich()?
-            .add_assets_to_album(&album_id, &dto)
-            .await
-            .context("Could not assign assets to album")?;
+            .bulk_write(&sel.asset_uuids(), |dto| async move {
+                let resp = client
+                    .remove_asset_from_album(&album_id, &dto)
+                    .api_context("Could not unassign assets from album")
+                    .await?;
+                Ok(resp.into_inner())
+            })
+            .await?;
         let cnt = resp.iter().filter(|r| r.success).count();
-        eprintln!("Assigned {} assets to album '{}'.", cnt, name);
+        eprintln!("Unassigned {} assets from album '{}'.", cnt, name);
+        self.record_history(&format!("album unassign '{}'", name), cnt);
         Ok(())
     }
 
-    pub async fn album_unassign(&mut self, name: &str) -> Result<()> {
-        let sel = Assets::load(&self.assets_file);
+    /// Album names with their ids, or only album `id` which is used as is to
+    /// disambiguate duplicate album names.
+    async fn resolve_albums(
+        &self,
+        names: &[String],
+        id: Option<Uuid>,
+    ) -> Result<Vec<(String, Uuid)>> {
+        if let Some(id) = id {
+            return Ok(vec![(id.to_string(), id)]);
+        }
+        let albums_resp = self
+            .immich()?
+            .get_all_albums(None, None, None, None, None)
+            .api_context("Could not retrieve albums")
+            .await?;
+        names
+            .iter()
+            .map(|name| Ok((name.clone(), Self::album_id_by_name(&albums_resp, name)?)))
+            .collect()
+    }
+
+    /// Move assets of album `from` to album `to`: the selected assets that are in `from`,
+    /// or all assets of `from` if `all` is set. Assets are only removed from `from` after
+    /// they were added to `to` (or were already in `to`).
+    pub async fn album_move(&mut self, from: &str, to: &str, all: bool) -> Result<()> {
+        let from_id = self.find_album_by_name(from).await?;
+        let to_id = self.find_album_by_name(to).await?;
+        if from_id == to_id {
+            // adding would report all assets as duplicates and removing then empties the album
+            bail!(
+                "Source and target album are the same ('{}'), nothing to move.",
+                from
+            );
+        }
+        let search_dto = MetadataSearchDto {
+            album_ids: vec![from_id],
+            ..Default::default()
+        };
+        let mut asset_ids: Vec<Uuid> = self
+            .search_pages(search_dto)
+            .await?
+            .into_iter()
+            .map(|a| a.id)
+            .collect();
+        if !all {
+            let sel = self.load_assets();
+            asset_ids.retain(|id| sel.contains(id));
+        }
+        if asset_ids.is_empty() {
+            eprintln!("No assets to move from album '{}'.", from);
+            return Ok(());
+        }
+        if self.dry_run {
+            eprintln!(
+                "Dry run: would move {} assets from album '{}' to '{}'.",
+                asset_ids.len(),
+                from,
+                to
+            );
+            return Ok(());
+        }
+
+        let mut moved = 0;
+        for ids in asset_ids.chunks(self.batch_size.get()) {
+            let dto = BulkIdsDto { ids: ids.to_vec() };
+            let added: Vec<_> = self
+                .immich()?
+                .add_assets_to_album(&to_id, &dto)
+                .with_api_context(|| format!("Could not assign assets to album '{}'", to))
+                .await?
+                .iter()
+                .filter(|r| r.success || r.error == Some(BulkIdErrorReason::Duplicate))
+                .map(|r| r.id)
+                .collect();
+            if added.is_empty() {
+                continue;
+            }
+            let dto = BulkIdsDto { ids: added };
+            let resp = self
+                .immich()?
+                .remove_asset_from_album(&from_id, &dto)
+                .with_api_context(|| format!("Could not unassign assets from album '{}'", from))
+                .await?;
+            moved += resp.iter().filter(|r| r.success).count();
+        }
+        let failed = asset_ids.len() - moved;
+        eprintln!(
+            "Moved {} assets from album '{}' to '{}', {} failed.",
+            moved, from, to, failed
+        );
+        self.record_history(&format!("album move '{}' to '{}'", from, to), moved);
+        if failed > 0 {
+            bail!("Could not move {} assets.", failed);
+        }
+        Ok(())
+    }
+
+    /// Remove the selected assets from every album they belong to. The albums are looked up
+    /// per asset, then the assets are removed album by album.
+    pub async fn assets_remove_from_albums(&mut self) -> Result<()> {
+        let sel = self.load_assets();
         if sel.is_empty() {
-            eprintln!("Selection is empty, nothing to unassign.");
+            eprintln!("Selection is empty, nothing to remove from albums.");
+            return Ok(());
+        }
+        let albums = self.albums_of_assets(&sel.asset_uuids()).await?;
+        if albums.is_empty() {
+            eprintln!("The selected assets are not in any album.");
+            return Ok(());
+        }
+        if self.dry_run {
+            for (name, asset_ids) in albums.values() {
+                eprintln!(
+                    "Dry run: would remove {} assets from album '{}'.",
+                    asset_ids.len(),
+                    name
+                );
+            }
+            return Ok(());
+        }
+        let mut total = 0;
+        for (album_id, (name, asset_ids)) in &albums {
+            let client = self.immich()?;
+            let resp = self
+                .bulk_write(asset_ids, |dto| async move {
+                    let resp = client
+                        .remove_asset_from_album(album_id, &dto)
+                        .with_api_context(|| {
+                            format!("Could not unassign assets from album '{}'", name)
+                        })
+                        .await?;
+                    Ok(resp.into_inner())
+                })
+                .await?;
+            let cnt = resp.iter().filter(|r| r.success).count();
+            eprintln!("Removed {} assets from album '{}'.", cnt, name);
+            total += cnt;
+        }
+        eprintln!(
+            "Removed {} album memberships from {} albums.",
+            total,
+            albums.len()
+        );
+        self.record_history("assets remove-from-albums", total);
+        Ok(())
+    }
+
+    /// Albums (id -> name and sorted asset ids) containing any of `asset_ids`.
+    async fn albums_of_assets(
+        &self,
+        asset_ids: &[Uuid],
+    ) -> Result<BTreeMap<Uuid, (String, Vec<Uuid>)>> {
+        let mut lookups = futures::stream::iter(asset_ids)
+            .map(|id| async move { (id, self.albums_of_asset(id).await) })
+            .buffered(self.read_concurrency.get());
+        let mut albums: BTreeMap<Uuid, (String, Vec<Uuid>)> = BTreeMap::new();
+        let mut i = 0;
+        while let Some((id, asset_albums)) = lookups.next().await {
+            self.eprint_progress_indicator(i, asset_ids.len(), 50);
+            i += 1;
+            for album in asset_albums? {
+                albums
+                    .entry(album.id)
+                    .or_insert_with(|| (album.album_name, vec![]))
+                    .1
+                    .push(*id);
+            }
+        }
+        for (_, ids) in albums.values_mut() {
+            ids.sort();
+        }
+        Ok(albums)
+    }
+
+    pub async fn album_rename(
+        &mut self,
+        name: &str,
+        new_name: &str,
+        description: &Option<String>,
+    ) -> Result<()> {
+        let album_id = self.find_album_by_name(name).await?;
+        if self.dry_run {
+            eprintln!("Dry run: would rename album '{}' to '{}'.", name, new_name);
             return Ok(());
         }
+        let dto = UpdateAlbumDto {
+            album_name: Some(new_name.to_string()),
+            description: description.clone(),
+            ..Default::default()
+        };
+        self.immich()?
+            .update_album_info(&album_id, &dto)
+            .api_context("Could not rename album")
+            .await?;
+        eprintln!("Renamed album '{}' to '{}'.", name, new_name);
+        self.record_history(&format!("album rename '{}' to '{}'", name, new_name), 1);
+        Ok(())
+    }
 
+    /// Create a public shared link for an album and print its URL.
+    /// The link never expires unless `expires` is given.
+    pub async fn album_share(
+        &mut self,
+        name: &str,
+        expires: &Option<TimeDelta>,
+        password: &Option<String>,
+    ) -> Result<()> {
         let album_id = self.find_album_by_name(name).await?;
-        let dto = BulkIdsDto {
-            ids: sel.asset_uuids(),
+        if self.dry_run {
+            eprintln!("Dry run: would create shared link for album '{}'.", name);
+            return Ok(());
+        }
+        let dto = SharedLinkCreateDto {
+            album_id: Some(album_id),
+            allow_download: true,
+            allow_upload: None,
+            asset_ids: vec![],
+            description: None,
+            expires_at: expires.map(|e| Utc::now() + e),
+            password: password.clone(),
+            show_metadata: true,
+            slug: None,
+            type_: SharedLinkType::Album,
         };
-        let resp = self
+        let link = self
             .immich()?
-            .remove_asset_from_album(&album_id, &dto)
-            .await
-            .context("Could not unassign assets from album")?;
-        let cnt = resp.iter().filter(|r| r.success).count();
-        eprintln!("Unassigned {} assets from album '{}'.", cnt, name);
+            .create_shared_link(&dto)
+            .api_context("Could not create shared link")
+            .await?
+            .into_inner();
+        let server = self.config.server_url()?;
+        let url = match &link.slug {
+            Some(slug) => format!("{}/s/{}", server, slug),
+            None => format!("{}/share/{}", server, link.key),
+        };
+        eprintln!("Created shared link for album '{}'.", name);
+        self.record_history(&format!("album share '{}'", name), 1);
+        println!("{}", url);
+        Ok(())
+    }
+
+    /// Print album details. With `show_assets`, the album's assets are
+    /// listed as well; they are fetched via metadata search since the
+    /// album detail endpoint doesn't include them.
+    pub async fn album_show(
+        &mut self,
+        name: &str,
+        show_assets: bool,
+        format: AlbumShowFormat,
+    ) -> Result<()> {
+        let album_id = self.find_album_by_name(name).await?;
+        let album = self
+            .immich()?
+            .get_album_info(&album_id, None, None)
+            .api_context("Could not retrieve album")
+            .await?
+            .into_inner();
+        let assets = if show_assets {
+            let search_dto = MetadataSearchDto {
+                album_ids: vec![album_id],
+                ..Default::default()
+            };
+            Some(self.search_pages(search_dto).await?)
+        } else {
+            None
+        };
+
+        match format {
+            AlbumShowFormat::Json => {
+                let info = AlbumInfo {
+                    album: &album,
+                    assets,
+                };
+                self.print_json(&info)?;
+            }
+            AlbumShowFormat::Text => {
+                println!("Name: {}", album.album_name);
+                println!("Description: {}", album.description);
+                if let Some(owner) = album.album_users.first() {
+                    println!("Owner: {} ({})", owner.user.name, owner.user.email);
+                }
+                println!("Assets: {}", album.asset_count);
+                if let Some(assets) = assets {
+                    for asset in assets {
+                        println!("  {} {}", asset.id, asset.original_file_name);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Print the assets of an album without changing the asset selection.
+    pub async fn album_export(&mut self, name: &str, format: AlbumExportFormat) -> Result<()> {
+        let album_id = self.find_album_by_name(name).await?;
+        let search_dto = MetadataSearchDto {
+            album_ids: vec![album_id],
+            ..Default::default()
+        };
+        let assets = self.search_pages(search_dto).await?;
+        let mut stdout = std::io::stdout().lock();
+        self.write_album_export(&mut stdout, &assets, format)?;
+        stdout.flush()?;
+        eprintln!("Exported {} assets of album '{}'.", assets.len(), name);
+        Ok(())
+    }
+
+    /// Write the exported album `assets` in `format`, csv file names quoted according to
+    /// `set_csv_quoting`.
+    fn write_album_export(
+        &self,
+        writer: &mut dyn Write,
+        assets: &[AssetResponseDto],
+        format: AlbumExportFormat,
+    ) -> Result<()> {
+        match format {
+            AlbumExportFormat::Ids => {
+                for asset in assets {
+                    writeln!(writer, "{}", asset.id)?;
+                }
+            }
+            AlbumExportFormat::Csv => {
+                for asset in assets {
+                    writeln!(
+                        writer,
+                        "{},{}",
+                        asset.id,
+                        self.csv_quoting.field(&asset.original_file_name)
+                    )?;
+                }
+            }
+            AlbumExportFormat::Json => {
+                output::write_json(writer, assets, self.pretty_json)?;
+                writeln!(writer)?;
+            }
+        }
         Ok(())
     }
 
@@ -51,10 +467,13 @@ impl ImmichCtl {
         let albums_resp = self
             .immich()?
             .get_all_albums(None, None, None, None, None)
-            .await
-            .context("Could not retrieve albums")?;
+            .api_context("Could not retrieve albums")
+            .await?;
+        Self::album_id_by_name(&albums_resp, name)
+    }
 
-        let mut it = albums_resp.iter().filter(|a| a.album_name == name);
+    fn album_id_by_name(albums: &[AlbumResponseDto], name: &str) -> Result<Uuid> {
+        let mut it = albums.iter().filter(|a| a.album_name == name);
         match (it.next(), it.next()) {
             (None, _) => bail!("Album not found: '{}'", name),
             (Some(a), None) => Ok(a.id),
@@ -66,8 +485,8 @@ impl ImmichCtl {
         let albums_resp = self
             .immich()?
             .get_all_albums(None, None, None, None, None)
-            .await
-            .context("Could not retrieve albums")?;
+            .api_context("Could not retrieve albums")
+            .await?;
         let mut albums: Vec<&AlbumResponseDto> = albums_resp.iter().collect();
         albums.sort_by(|a, b| a.album_name.cmp(&b.album_name));
         for album in albums {
@@ -79,10 +498,14 @@ impl ImmichCtl {
 
 #[cfg(test)]
 pub mod tests {
+    use super::{AlbumExportFormat, AlbumShowFormat};
+    use crate::immichctl::asset_cmd::tests::create_asset_for_download;
+    use crate::immichctl::assets::Assets;
     use crate::immichctl::tests::create_immichctl_with_server;
     use crate::immichctl::types::AlbumResponseDto;
+    use crate::immichctl::{CsvQuoting, ImmichCtl};
     use anyhow::Result;
-    use chrono::DateTime;
+    use chrono::{DateTime, TimeDelta};
     use uuid::Uuid;
 
     pub fn create_album(id: &str, name: &str) -> AlbumResponseDto {
@@ -156,4 +579,539 @@ pub mod tests {
         mock.assert_async().await;
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_album_assign_multiple_albums() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let asset_id = Uuid::new_v4();
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(create_asset_for_download(asset_id, "a.jpg", "a.jpg"));
+        sel.save()?;
+        let albums = vec![
+            create_album("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1", "Family"),
+            create_album("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab2", "2024"),
+        ];
+        // all names are resolved with one album list request
+        let albums_mock = server
+            .mock("GET", "/api/albums")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&albums)?)
+            .expect(1)
+            .create_async()
+            .await;
+        let mut assign_mocks = vec![];
+        for album in &albums {
+            let m = server
+                .mock("PUT", format!("/api/albums/{}/assets", album.id).as_str())
+                .match_body(mockito::Matcher::Json(
+                    serde_json::json!({"ids": [asset_id]}),
+                ))
+                .with_status(200)
+                .with_header("content-type", "application/json")
+                .with_body(serde_json::json!([{"id": asset_id, "success": true}]).to_string())
+                .create_async()
+                .await;
+            assign_mocks.push(m);
+        }
+
+        ctl.album_assign(&["Family".to_string(), "2024".to_string()], None)
+            .await?;
+
+        albums_mock.assert_async().await;
+        for m in assign_mocks {
+            m.assert_async().await;
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_album_assign_and_unassign_by_id() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let asset_id = Uuid::new_v4();
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(create_asset_for_download(asset_id, "a.jpg", "a.jpg"));
+        sel.save()?;
+        let album_id = Uuid::parse_str("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab2")?;
+        // albums are not looked up by name
+        let albums_mock = server
+            .mock("GET", "/api/albums")
+            .expect(0)
+            .create_async()
+            .await;
+        let assign_mock = server
+            .mock("PUT", format!("/api/albums/{}/assets", album_id).as_str())
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({"ids": [asset_id]}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::json!([{"id": asset_id, "success": true}]).to_string())
+            .create_async()
+            .await;
+        let unassign_mock = server
+            .mock(
+                "DELETE",
+                format!("/api/albums/{}/assets", album_id).as_str(),
+            )
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({"ids": [asset_id]}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::json!([{"id": asset_id, "success": true}]).to_string())
+            .create_async()
+            .await;
+
+        ctl.album_assign(&[], Some(album_id)).await?;
+        ctl.album_unassign(None, Some(album_id)).await?;
+
+        albums_mock.assert_async().await;
+        assign_mock.assert_async().await;
+        unassign_mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_remove_from_albums() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let in_both = Uuid::new_v4();
+        let in_one = Uuid::new_v4();
+        let in_none = Uuid::new_v4();
+        let mut sel = Assets::load(&ctl.assets_file);
+        for id in [in_both, in_one, in_none] {
+            sel.add_asset(create_asset_for_download(id, "a.jpg", "a.jpg"));
+        }
+        sel.save()?;
+        let rome = create_album("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1", "Rome");
+        let best = create_album("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab2", "Best of");
+        let mut lookup_mocks = vec![];
+        for (id, albums) in [
+            (in_both, vec![&rome, &best]),
+            (in_one, vec![&rome]),
+            (in_none, vec![]),
+        ] {
+            let mock = server
+                .mock("GET", "/api/albums")
+                .match_query(mockito::Matcher::UrlEncoded(
+                    "assetId".into(),
+                    id.to_string(),
+                ))
+                .with_status(200)
+                .with_header("content-type", "application/json")
+                .with_body(serde_json::to_string(&albums)?)
+                .expect(2)
+                .create_async()
+                .await;
+            lookup_mocks.push(mock);
+        }
+        let mut remove_mocks = vec![];
+        for (album, mut ids) in [(&rome, vec![in_both, in_one]), (&best, vec![in_both])] {
+            ids.sort();
+            let results: Vec<_> = ids
+                .iter()
+                .map(|id| serde_json::json!({"id": id, "success": true}))
+                .collect();
+            let mock = server
+                .mock(
+                    "DELETE",
+                    format!("/api/albums/{}/assets", album.id).as_str(),
+                )
+                .match_body(mockito::Matcher::Json(serde_json::json!({ "ids": ids })))
+                .with_status(200)
+                .with_header("content-type", "application/json")
+                .with_body(serde_json::Value::from(results).to_string())
+                .expect(1)
+                .create_async()
+                .await;
+            remove_mocks.push(mock);
+        }
+
+        // dry run looks up the albums only
+        ctl.set_dry_run(true);
+        ctl.assets_remove_from_albums().await?;
+        ctl.set_dry_run(false);
+        ctl.assets_remove_from_albums().await?;
+
+        for mock in lookup_mocks.iter().chain(&remove_mocks) {
+            mock.assert_async().await;
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_album_move() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        // a1 + a2 are in album A and selected, a3 is in album A but not selected
+        let ids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        let mut sel = Assets::load(&ctl.assets_file);
+        for id in &ids[..2] {
+            sel.add_asset(create_asset_for_download(*id, "a.jpg", "a.jpg"));
+        }
+        sel.add_asset(create_asset_for_download(Uuid::new_v4(), "b.jpg", "b.jpg"));
+        sel.save()?;
+        let albums = vec![
+            create_album("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1", "A"),
+            create_album("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab2", "B"),
+        ];
+        let _albums_mock = server
+            .mock("GET", "/api/albums")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&albums)?)
+            .create_async()
+            .await;
+        let album_assets: Vec<_> = ids
+            .iter()
+            .map(|id| create_asset_for_download(*id, "a.jpg", "a.jpg"))
+            .collect();
+        let _search_mock = server
+            .mock("POST", "/api/search/metadata")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                    "assets": {"count": 3, "facets": [], "items": album_assets, "nextPage": null, "total": 3}
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        // a2 is already in B
+        let add_mock = server
+            .mock(
+                "PUT",
+                format!("/api/albums/{}/assets", albums[1].id).as_str(),
+            )
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({"ids": [ids[0], ids[1]]}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!([
+                    {"id": ids[0], "success": true},
+                    {"id": ids[1], "success": false, "error": "duplicate"}
+                ])
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let remove_mock = server
+            .mock(
+                "DELETE",
+                format!("/api/albums/{}/assets", albums[0].id).as_str(),
+            )
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({"ids": [ids[0], ids[1]]}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!([
+                    {"id": ids[0], "success": true},
+                    {"id": ids[1], "success": true}
+                ])
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        ctl.album_move("A", "B", false).await?;
+
+        add_mock.assert_async().await;
+        remove_mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_album_move_same_album() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let albums = vec![create_album("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1", "A")];
+        let _albums_mock = server
+            .mock("GET", "/api/albums")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&albums)?)
+            .create_async()
+            .await;
+        let search_mock = server
+            .mock("POST", "/api/search/metadata")
+            .expect(0)
+            .create_async()
+            .await;
+        let add_mock = server
+            .mock("PUT", mockito::Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+        let remove_mock = server
+            .mock("DELETE", mockito::Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+
+        let err = ctl.album_move("A", "A", true).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Source and target album are the same ('A'), nothing to move."
+        );
+        search_mock.assert_async().await;
+        add_mock.assert_async().await;
+        remove_mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_album_assign_unknown_album_changes_nothing() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(create_asset_for_download(Uuid::new_v4(), "a.jpg", "a.jpg"));
+        sel.save()?;
+        let albums = vec![create_album(
+            "a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1",
+            "Family",
+        )];
+        let _albums_mock = server
+            .mock("GET", "/api/albums")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&albums)?)
+            .create_async()
+            .await;
+        let assign_mock = server
+            .mock(
+                "PUT",
+                "/api/albums/a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1/assets",
+            )
+            .expect(0)
+            .create_async()
+            .await;
+
+        let result = ctl
+            .album_assign(&["Family".to_string(), "Unknown".to_string()], None)
+            .await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Album not found: 'Unknown'"
+        );
+        assign_mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_album_rename() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let albums = vec![create_album(
+            "a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1",
+            "Album 1",
+        )];
+        let albums_mock = server
+            .mock("GET", "/api/albums")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&albums)?)
+            .create_async()
+            .await;
+        let update_mock = server
+            .mock("PATCH", "/api/albums/a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "albumName": "Renamed",
+                "description": "New description"
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&create_album(
+                "a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1",
+                "Renamed",
+            ))?)
+            .create_async()
+            .await;
+
+        ctl.album_rename("Album 1", "Renamed", &Some("New description".to_string()))
+            .await?;
+
+        albums_mock.assert_async().await;
+        update_mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_album_share() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let albums = vec![create_album(
+            "a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1",
+            "Album 1",
+        )];
+        let albums_mock = server
+            .mock("GET", "/api/albums")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&albums)?)
+            .create_async()
+            .await;
+        let link_mock = server
+            .mock("POST", "/api/shared-links")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "albumId": "a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1",
+                "password": "secret",
+                "type": "ALBUM"
+            })))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "id": "b1a7f1a9-7394-49f7-a5a3-e876a7e16ab1",
+                    "key": "sharedkey",
+                    "allowDownload": true,
+                    "allowUpload": false,
+                    "assets": [],
+                    "createdAt": "2024-01-01T00:00:00Z",
+                    "description": null,
+                    "expiresAt": null,
+                    "password": "secret",
+                    "showMetadata": true,
+                    "slug": null,
+                    "type": "ALBUM",
+                    "userId": "c1a7f1a9-7394-49f7-a5a3-e876a7e16ab1"
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        ctl.album_share(
+            "Album 1",
+            &Some(TimeDelta::days(7)),
+            &Some("secret".to_string()),
+        )
+        .await?;
+
+        albums_mock.assert_async().await;
+        link_mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_album_show_with_assets() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let album = create_album("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1", "Album 1");
+        let albums_mock = server
+            .mock("GET", "/api/albums")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&vec![album.clone()])?)
+            .create_async()
+            .await;
+        let album_mock = server
+            .mock("GET", "/api/albums/a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&album)?)
+            .create_async()
+            .await;
+        let search_mock = server
+            .mock("POST", "/api/search/metadata")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "albumIds": ["a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1"]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                    "assets": {"count": 0, "facets": [], "items": [], "nextPage": null, "total": 0}
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        ctl.album_show("Album 1", true, AlbumShowFormat::Json)
+            .await?;
+
+        albums_mock.assert_async().await;
+        album_mock.assert_async().await;
+        search_mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_album_export_keeps_selection() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let sel = Assets::load(&ctl.assets_file);
+        sel.save()?;
+        let albums = vec![create_album(
+            "a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1",
+            "Album 1",
+        )];
+        let _albums_mock = server
+            .mock("GET", "/api/albums")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&albums)?)
+            .create_async()
+            .await;
+        let asset = create_asset_for_download(Uuid::new_v4(), "a.jpg", "a.jpg");
+        let search_mock = server
+            .mock("POST", "/api/search/metadata")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "albumIds": ["a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1"]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                    "assets": {"count": 1, "facets": [], "items": [asset], "nextPage": null, "total": 1}
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        ctl.album_export("Album 1", AlbumExportFormat::Csv).await?;
+
+        search_mock.assert_async().await;
+        assert!(Assets::load(&ctl.assets_file).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_album_export() -> Result<()> {
+        let config_dir = tempfile::tempdir()?;
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
+        let id = Uuid::parse_str("b1a7f1a9-7394-49f7-a5a3-e876a7e16ab1")?;
+        let assets = vec![create_asset_for_download(id, "a,\"b\".jpg", "a.jpg")];
+        let export = |ctl: &ImmichCtl, format| -> Result<String> {
+            let mut out = Vec::new();
+            ctl.write_album_export(&mut out, &assets, format)?;
+            Ok(String::from_utf8(out)?)
+        };
+
+        assert_eq!(export(&ctl, AlbumExportFormat::Ids)?, format!("{}\n", id));
+        assert_eq!(
+            export(&ctl, AlbumExportFormat::Csv)?,
+            format!("{},\"a,\"\"b\"\".jpg\"\n", id)
+        );
+        ctl.set_csv_quoting(CsvQuoting::Never);
+        assert_eq!(
+            export(&ctl, AlbumExportFormat::Csv)?,
+            format!("{},a,\"b\".jpg\n", id)
+        );
+        assert!(export(&ctl, AlbumExportFormat::Json)?.starts_with("[{"));
+        Ok(())
+    }
 }
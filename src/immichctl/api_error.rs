@@ -0,0 +1,192 @@
+use anyhow::Result;
+use reqwest::StatusCode;
+use std::fmt::Display;
+use std::future::Future;
+
+/// Unexpected response of the Immich server: the HTTP status and the full response body.
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub body: String,
+}
+
+impl ApiError {
+    /// The `message` (string or list of validation messages) or `error` field of the
+    /// json error body.
+    pub fn message(&self) -> Option<String> {
+        let json: serde_json::Value = serde_json::from_str(&self.body).ok()?;
+        match &json["message"] {
+            serde_json::Value::String(msg) if !msg.is_empty() => return Some(msg.clone()),
+            serde_json::Value::Array(msgs) if !msgs.is_empty() => {
+                let msgs: Vec<String> = msgs
+                    .iter()
+                    .map(|m| m.as_str().map_or_else(|| m.to_string(), str::to_string))
+                    .collect();
+                return Some(msgs.join(", "));
+            }
+            _ => {}
+        }
+        json["error"]
+            .as_str()
+            .filter(|e| !e.is_empty())
+            .map(str::to_string)
+    }
+}
+
+impl Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.body.is_empty() {
+            write!(f, "HTTP {}", self.status)
+        } else {
+            write!(f, "HTTP {}: {}", self.status, self.body)
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// Like anyhow's `context`, but for Immich API calls: an unexpected response becomes
+/// `<context> (<status>): <server message>` with the full response body as `ApiError`
+/// source, shown with `--verbose`.
+pub trait ApiContext<T> {
+    fn api_context<C>(self, context: C) -> impl Future<Output = Result<T>> + Send
+    where
+        C: Display + Send + Sync + 'static;
+
+    fn with_api_context<C, F>(self, f: F) -> impl Future<Output = Result<T>> + Send
+    where
+        C: Display + Send + Sync + 'static,
+        F: FnOnce() -> C + Send;
+}
+
+impl<T, Fut> ApiContext<T> for Fut
+where
+    T: Send,
+    Fut: Future<Output = Result<T, progenitor_client::Error>> + Send,
+{
+    async fn api_context<C>(self, context: C) -> Result<T>
+    where
+        C: Display + Send + Sync + 'static,
+    {
+        match self.await {
+            Ok(value) => Ok(value),
+            Err(err) => Err(api_error(err, context).await),
+        }
+    }
+
+    async fn with_api_context<C, F>(self, f: F) -> Result<T>
+    where
+        C: Display + Send + Sync + 'static,
+        F: FnOnce() -> C + Send,
+    {
+        match self.await {
+            Ok(value) => Ok(value),
+            Err(err) => Err(api_error(err, f()).await),
+        }
+    }
+}
+
+async fn api_error<C>(err: progenitor_client::Error, context: C) -> anyhow::Error
+where
+    C: Display + Send + Sync + 'static,
+{
+    let progenitor_client::Error::UnexpectedResponse(resp) = err else {
+        return anyhow::Error::new(err).context(context);
+    };
+    let status = resp.status();
+    let body = resp.text().await.unwrap_or_default();
+    with_context(ApiError { status, body }, context)
+}
+
+/// Like reqwest's `error_for_status`, but for raw requests to the Immich API: an error
+/// status becomes an `ApiError` with the response body, see `ApiContext`.
+pub async fn error_for_status<C: Display>(
+    resp: reqwest::Response,
+    context: C,
+) -> Result<reqwest::Response> {
+    let status = resp.status();
+    if !status.is_client_error() && !status.is_server_error() {
+        return Ok(resp);
+    }
+    let body = resp.text().await.unwrap_or_default();
+    Err(with_context(ApiError { status, body }, context))
+}
+
+fn with_context<C: Display>(err: ApiError, context: C) -> anyhow::Error {
+    let msg = match err.message() {
+        Some(message) => format!("{} ({}): {}", context, err.status.as_u16(), message),
+        None => format!("{} ({})", context, err.status.as_u16()),
+    };
+    anyhow::Error::new(err).context(msg)
+}
+
+/// Replace the context of a 401 or 403 response by `context`, e.g. to report a rejected
+/// API key instead of a connection problem. Other errors are returned unchanged.
+pub fn auth_context<C: Display>(err: anyhow::Error, context: C) -> anyhow::Error {
+    let is_auth = err
+        .downcast_ref::<ApiError>()
+        .is_some_and(|e| matches!(e.status, StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN));
+    if !is_auth {
+        return err;
+    }
+    match err.downcast::<ApiError>() {
+        Ok(api_err) => with_context(api_err, context),
+        Err(err) => err,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_error(body: &str) -> ApiError {
+        ApiError {
+            status: StatusCode::BAD_REQUEST,
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_message() {
+        assert_eq!(
+            api_error(r#"{"message":"invalid tagId","error":"Bad Request","statusCode":400}"#)
+                .message()
+                .as_deref(),
+            Some("invalid tagId")
+        );
+        assert_eq!(
+            api_error(r#"{"message":["size must not be less than 1","page must be an integer"]}"#)
+                .message()
+                .as_deref(),
+            Some("size must not be less than 1, page must be an integer")
+        );
+        assert_eq!(
+            api_error(r#"{"error":"Bad Request"}"#).message().as_deref(),
+            Some("Bad Request")
+        );
+        assert_eq!(api_error("<html>Bad Gateway</html>").message(), None);
+        assert_eq!(api_error("").message(), None);
+    }
+
+    #[test]
+    fn test_auth_context() {
+        let err = |status| {
+            let err = ApiError {
+                status,
+                body: r#"{"message":"Invalid API key"}"#.to_string(),
+            };
+            with_context(err, "Could not connect")
+        };
+        let forbidden = auth_context(err(StatusCode::FORBIDDEN), "Rejected");
+        assert_eq!(forbidden.to_string(), "Rejected (403): Invalid API key");
+        assert!(forbidden.downcast_ref::<ApiError>().is_some());
+        assert_eq!(
+            auth_context(err(StatusCode::BAD_GATEWAY), "Rejected").to_string(),
+            "Could not connect (502): Invalid API key"
+        );
+        assert_eq!(
+            auth_context(anyhow::anyhow!("other"), "Rejected").to_string(),
+            "other"
+        );
+    }
+}
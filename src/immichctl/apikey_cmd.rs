@@ -0,0 +1,255 @@
+use super::ImmichCtl;
+use super::api_error::{ApiContext, ApiError};
+use super::output::OutputMode;
+use super::types::ApiKeyResponseDto;
+use anyhow::{Result, bail};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// API key as printed by `apikey list --output json`.
+#[derive(Serialize)]
+struct ApiKeyInfo<'a> {
+    #[serde(flatten)]
+    key: &'a ApiKeyResponseDto,
+    /// Whether immichctl is logged in with this key
+    current: bool,
+}
+
+impl ImmichCtl {
+    /// List the API keys of the user, the key used by immichctl is marked.
+    pub async fn apikey_list(&self, format: OutputMode) -> Result<()> {
+        let keys_resp = self
+            .immich()?
+            .get_api_keys()
+            .api_context("Could not retrieve API keys")
+            .await?;
+        let current = self.current_apikey_id().await.unwrap_or_else(|err| {
+            self.eprint_warning(&err.to_string());
+            None
+        });
+        let mut keys: Vec<&ApiKeyResponseDto> = keys_resp.iter().collect();
+        keys.sort_by_key(|k| k.created_at);
+        match format {
+            OutputMode::Json => {
+                let infos: Vec<_> = keys
+                    .into_iter()
+                    .map(|key| ApiKeyInfo {
+                        key,
+                        current: Some(key.id) == current,
+                    })
+                    .collect();
+                self.print_json(&infos)?;
+            }
+            OutputMode::Text => {
+                for line in Self::apikey_lines(&keys, current) {
+                    println!("{}", line);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// One line per key: id, creation date and name, the `current` key is marked.
+    fn apikey_lines(keys: &[&ApiKeyResponseDto], current: Option<Uuid>) -> Vec<String> {
+        keys.iter()
+            .map(|key| {
+                format!(
+                    "{}  {}  {}{}",
+                    key.id,
+                    key.created_at.format("%Y-%m-%d"),
+                    key.name,
+                    if Some(key.id) == current {
+                        "  (current)"
+                    } else {
+                        ""
+                    }
+                )
+            })
+            .collect()
+    }
+
+    /// Revoke (delete) the API key `id`. Revoking the key used by immichctl, or any key if
+    /// the key in use can't be determined, requires `yes`.
+    pub async fn apikey_revoke(&self, id: &Uuid, yes: bool) -> Result<()> {
+        let msg = match self.current_apikey_id().await {
+            Ok(Some(current)) if current == *id => Some(format!(
+                "API key '{}' is the key immichctl is logged in with, revoking it ends the login.",
+                id
+            )),
+            Ok(Some(_)) => None,
+            Ok(None) => Some(format!(
+                "Could not determine the API key immichctl is logged in with, revoking '{}' may end the login.",
+                id
+            )),
+            Err(err) => Some(format!(
+                "Could not determine the API key immichctl is logged in with ({}), revoking '{}' may end the login.",
+                err, id
+            )),
+        };
+        if let Some(msg) = msg {
+            if !yes {
+                bail!("{} Use --yes to revoke it anyway.", msg);
+            }
+            self.eprint_warning(&msg);
+        }
+        if self.dry_run {
+            eprintln!("Dry run: would revoke API key '{}'.", id);
+            return Ok(());
+        }
+        self.immich()?
+            .delete_api_key(id)
+            .with_api_context(|| format!("Could not revoke API key '{}'", id))
+            .await?;
+        eprintln!("Revoked API key '{}'.", id);
+        self.record_history(&format!("apikey revoke '{}'", id), 1);
+        Ok(())
+    }
+
+    /// Id of the API key immichctl is logged in with, `None` if the server denies the
+    /// lookup (e.g. the key lacks the permission to read itself).
+    async fn current_apikey_id(&self) -> Result<Option<Uuid>> {
+        match self
+            .immich()?
+            .get_my_api_key()
+            .api_context("Could not retrieve the current API key")
+            .await
+        {
+            Ok(key) => Ok(Some(key.id)),
+            Err(err)
+                if err.downcast_ref::<ApiError>().is_some_and(|e| {
+                    matches!(
+                        e.status,
+                        reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN
+                    )
+                }) =>
+            {
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::immichctl::ImmichCtl;
+    use crate::immichctl::tests::create_immichctl_with_server;
+    use crate::immichctl::types::ApiKeyResponseDto;
+    use anyhow::Result;
+    use uuid::Uuid;
+
+    fn create_apikey(id: &str, name: &str) -> serde_json::Value {
+        serde_json::json!({
+            "createdAt": "2024-01-01T00:00:00Z",
+            "id": id,
+            "name": name,
+            "permissions": ["all"],
+            "updatedAt": "2024-01-01T00:00:00Z",
+        })
+    }
+
+    #[tokio::test]
+    async fn test_apikey_revoke() -> Result<()> {
+        let (ctl, mut server) = create_immichctl_with_server().await;
+        let current = "c1a7f1a9-7394-49f7-a5a3-e876a7e16ab1";
+        let other = "c1a7f1a9-7394-49f7-a5a3-e876a7e16ab2";
+        let me_mock = server
+            .mock("GET", "/api/api-keys/me")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(create_apikey(current, "immichctl").to_string())
+            .create_async()
+            .await;
+        let delete_other_mock = server
+            .mock("DELETE", format!("/api/api-keys/{}", other).as_str())
+            .with_status(204)
+            .create_async()
+            .await;
+        let delete_current_mock = server
+            .mock("DELETE", format!("/api/api-keys/{}", current).as_str())
+            .with_status(204)
+            .expect(1)
+            .create_async()
+            .await;
+
+        ctl.apikey_revoke(&Uuid::parse_str(other)?, false).await?;
+        delete_other_mock.assert_async().await;
+
+        // the key in use is only revoked with --yes
+        let err = ctl
+            .apikey_revoke(&Uuid::parse_str(current)?, false)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "API key '{}' is the key immichctl is logged in with, revoking it ends the login. Use --yes to revoke it anyway.",
+                current
+            )
+        );
+        ctl.apikey_revoke(&Uuid::parse_str(current)?, true).await?;
+        delete_current_mock.assert_async().await;
+
+        // any key needs --yes if the key in use can't be determined
+        me_mock.remove_async().await;
+        let _me_failed_mock = server
+            .mock("GET", "/api/api-keys/me")
+            .with_status(500)
+            .create_async()
+            .await;
+        let err = ctl
+            .apikey_revoke(&Uuid::parse_str(other)?, false)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Could not determine the API key immichctl is logged in with (Could not retrieve the current API key (500)), revoking '{}' may end the login. Use --yes to revoke it anyway.",
+                other
+            )
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_apikey_list() -> Result<()> {
+        let (ctl, mut server) = create_immichctl_with_server().await;
+        let keys = vec![
+            create_apikey("c1a7f1a9-7394-49f7-a5a3-e876a7e16ab1", "immichctl"),
+            create_apikey("c1a7f1a9-7394-49f7-a5a3-e876a7e16ab2", "old"),
+        ];
+        let list_mock = server
+            .mock("GET", "/api/api-keys")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&keys)?)
+            .create_async()
+            .await;
+        // the current key can't be determined, e.g. missing permission
+        let _me_mock = server
+            .mock("GET", "/api/api-keys/me")
+            .with_status(403)
+            .create_async()
+            .await;
+
+        ctl.apikey_list(crate::immichctl::OutputMode::Json).await?;
+        list_mock.assert_async().await;
+        assert_eq!(ctl.current_apikey_id().await?, None);
+
+        let keys: Vec<ApiKeyResponseDto> = serde_json::from_value(serde_json::json!(keys))?;
+        let keys: Vec<&ApiKeyResponseDto> = keys.iter().collect();
+        assert_eq!(
+            ImmichCtl::apikey_lines(&keys, Some(keys[0].id)),
+            vec![
+                "c1a7f1a9-7394-49f7-a5a3-e876a7e16ab1  2024-01-01  immichctl  (current)",
+                "c1a7f1a9-7394-49f7-a5a3-e876a7e16ab2  2024-01-01  old",
+            ]
+        );
+        assert!(
+            ImmichCtl::apikey_lines(&keys, None)
+                .iter()
+                .all(|line| !line.contains("(current)"))
+        );
+        Ok(())
+    }
+}
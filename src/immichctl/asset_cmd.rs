@@ -1,25 +1,67 @@
 use std::borrow::Cow;
+use std::fs::File;
+use std::io::{BufRead, BufWriter, Write};
+use std::num::{NonZeroU64, NonZeroUsize};
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::time::Instant;
 
 use super::ImmichCtl;
+use super::api_error::{self, ApiContext};
 use super::assets::Assets;
-use super::types::{AssetResponseDto, AssetVisibility, MetadataSearchDto, UpdateAssetDto};
+use super::ids::parse_ids;
+use super::output::{self, OutputMode};
+use super::tag_cmd::TagMatch;
+use super::timezone::TimeZoneValue;
+use super::types::{
+    AssetJobName, AssetJobsDto, AssetOrder, AssetResponseDto, AssetTypeEnum, AssetVisibility,
+    ExifResponseDto, MetadataSearchDto, SmartSearchDto, UpdateAssetDto,
+};
 use anyhow::{Context, Result, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use chrono::{DateTime, FixedOffset, TimeDelta, Utc};
+use futures::StreamExt;
+use progenitor_client::ClientInfo;
+use uuid::Uuid;
+
+/// Number of refreshed assets after which the selection is saved
+const REFRESH_CHECKPOINT_INTERVAL: usize = 100;
+/// Default of `--max-selection`
+const DEFAULT_MAX_SELECTION: usize = 50000;
+
+/// Default number of assets added by `assets search --similar-to`
+const DEFAULT_SIMILAR_LIMIT: NonZeroU64 = NonZeroU64::new(100).unwrap();
 
 #[derive(clap::Args, Debug, Default)]
 pub struct AssetSearchArgs {
     /// Remove assets from selection instead of adding
     #[arg(long)]
     pub remove: bool,
-    /// Asset id to add (UUID)
+    /// Replace the selection with the search result instead of adding to it. The selection is kept if the search fails.
+    #[arg(long, conflicts_with_all = ["remove", "print_raw"])]
+    pub replace: bool,
+    /// Add all assets of the library, requires --yes
+    #[arg(long, requires = "yes", conflicts_with_all = ["remove", "similar_to", "shared_link"])]
+    pub all: bool,
+    /// Asset id to add (UUID), several ids can be separated by commas, spaces or newlines
     #[arg(long, value_name = "asset id")]
     pub id: Option<String>,
     /// Tag name to search and add by tag id
     #[arg(long, value_name = "tag name")]
     pub tag: Option<String>,
+    /// Treat --tag as glob pattern on the full tag name, all matching tags are used
+    #[arg(long, requires = "tag", conflicts_with = "tag_regex")]
+    pub tag_glob: bool,
+    /// Treat --tag as regular expression on the full tag name, all matching tags are used
+    #[arg(long, requires = "tag")]
+    pub tag_regex: bool,
     /// Album name to search
     #[arg(long, value_name = "album name")]
     pub album: Option<String>,
+    /// External library name or id
+    #[arg(long, value_name = "library name|id")]
+    pub library: Option<String>,
     /// Assets (not) marked as favorite. If used without a value, it's equivalent to `--favorite=true`.
     #[arg(long, value_name = "true|false", num_args = 0..=1, default_missing_value = "true", action = clap::ArgAction::Set)]
     pub favorite: Option<bool>,
@@ -29,13 +71,215 @@ pub struct AssetSearchArgs {
     /// Assets taken before this date/time
     #[arg(long, value_name = "YYYY-MM-DDTHH:MM:SS±00:00")]
     pub taken_before: Option<DateTime<FixedOffset>>,
+    /// Asset type, search results are also filtered locally
+    #[arg(long = "type", value_enum)]
+    pub asset_type: Option<AssetType>,
+    /// City from EXIF metadata
+    #[arg(long)]
+    pub city: Option<String>,
+    /// Country from EXIF metadata
+    #[arg(long)]
+    pub country: Option<String>,
+    /// Camera make from EXIF metadata
+    #[arg(long)]
+    pub make: Option<String>,
+    /// Camera model from EXIF metadata
+    #[arg(long)]
+    pub model: Option<String>,
+    /// Part of the original file name
+    #[arg(long, value_name = "file name")]
+    pub filename: Option<String>,
+    /// SHA-1 checksum of the original file, base64 (as shown by Immich) or hex (as printed by sha1sum)
+    #[arg(long, value_name = "checksum", value_parser = parse_checksum)]
+    pub checksum: Option<String>,
+    /// Also find assets in the trash, they are excluded by default
+    #[arg(long)]
+    pub include_trashed: bool,
     /// Timezone (remove only)
     #[arg(long)]
     pub timezone: Option<FixedOffset>,
+    /// Orientation by image dimensions, evaluated locally. Assets without dimensions are skipped.
+    #[arg(long, value_enum)]
+    pub orientation: Option<Orientation>,
+    /// File extension of the original file name (repeatable, case-insensitive), evaluated locally
+    #[arg(long, value_name = "ext")]
+    pub extension: Vec<String>,
+    /// Only live photos (photos with a paired video), evaluated locally
+    #[arg(long, conflicts_with = "no_live")]
+    pub live_only: bool,
+    /// Exclude live photos, evaluated locally
+    #[arg(long)]
+    pub no_live: bool,
+    /// Soft limit for the selection size when adding assets, checked while paging (0 = unlimited)
+    #[arg(long, value_name = "count", default_value_t = DEFAULT_MAX_SELECTION)]
+    pub max_selection: usize,
+    /// Exceed --max-selection
+    #[arg(short, long)]
+    pub yes: bool,
+    /// Print the raw search response json of each page instead of adding assets to the selection
+    #[arg(long, conflicts_with = "remove")]
+    pub print_raw: bool,
+    /// Add assets visually similar to this asset (UUID), requires smart search on the server
+    #[arg(long, value_name = "asset id", conflicts_with_all = ["remove", "id", "filename", "print_raw", "tag_glob", "tag_regex"])]
+    pub similar_to: Option<String>,
+    /// Add the assets of a shared link (url or key), also if they are owned by other users
+    #[arg(long, value_name = "url|key", conflicts_with_all = [
+        "remove", "id", "tag", "album", "library", "favorite", "taken_after", "taken_before",
+        "city", "country", "make", "model", "filename", "checksum", "print_raw", "similar_to",
+        "tag_glob", "tag_regex", "include_trashed", "order", "start_page", "page_size",
+    ])]
+    pub shared_link: Option<String>,
+    /// Password of a password protected shared link, `-` reads it from stdin [default: IMMICHCTL_LINK_PASSWORD]
+    #[arg(long, value_name = "password", requires = "shared_link")]
+    pub link_password: Option<String>,
+    /// Order of the server search results by date taken, server default if not given
+    #[arg(long, value_enum, conflicts_with_all = ["similar_to", "shared_link"])]
+    pub order: Option<SearchOrder>,
+    /// Maximal number of similar assets [default: 100]
+    #[arg(long, value_name = "count", requires = "similar_to")]
+    pub limit: Option<NonZeroU64>,
+    /// Add at most this many new assets (already selected ones don't count), stops paging once they are found
+    #[arg(long, value_name = "count", conflicts_with_all = ["remove", "similar_to", "shared_link", "print_raw"])]
+    pub max_new: Option<NonZeroUsize>,
+    /// First search result page to fetch (for debugging server pagination)
+    #[arg(long, hide = true)]
+    pub start_page: Option<NonZeroU64>,
+    /// Number of assets per search result page (for debugging server pagination)
+    #[arg(long, hide = true)]
+    pub page_size: Option<NonZeroU64>,
+}
+
+/// A tab separated value, tabs and line breaks would break the format and are replaced by spaces.
+fn tsv_field(value: &str) -> Cow<'_, str> {
+    if value.contains(['\t', '\n', '\r']) {
+        Cow::Owned(value.replace(['\t', '\n', '\r'], " "))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Normalize a SHA-1 checksum given as base64 or hex to base64, the format of `asset.checksum`.
+fn parse_checksum(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    // 40 hex digits are valid base64 as well, but would decode to 30 bytes
+    let bytes = if value.len() == 40 && value.chars().all(|c| c.is_ascii_hexdigit()) {
+        (0..value.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&value[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|e| e.to_string())?
+    } else {
+        BASE64_STANDARD
+            .decode(value)
+            .map_err(|e| format!("expected a base64 or hex SHA-1 checksum ({})", e))?
+    };
+    if bytes.len() != 20 {
+        return Err(format!(
+            "expected a SHA-1 checksum of 20 bytes, got {} bytes",
+            bytes.len()
+        ));
+    }
+    Ok(BASE64_STANDARD.encode(bytes))
+}
+
+/// Filters of `assets clear`, only the selected assets matching all of them are removed.
+#[derive(clap::Args, Debug, Default, Clone)]
+pub struct ClearFilterArgs {
+    /// Tag name
+    #[arg(long, value_name = "tag name")]
+    pub tag: Option<String>,
+    /// Album name
+    #[arg(long, value_name = "album name")]
+    pub album: Option<String>,
+    /// Assets (not) marked as favorite. If used without a value, it's equivalent to `--favorite=true`.
+    #[arg(long, value_name = "true|false", num_args = 0..=1, default_missing_value = "true", action = clap::ArgAction::Set)]
+    pub favorite: Option<bool>,
+    /// Assets taken after this date/time
+    #[arg(long, value_name = "YYYY-MM-DDTHH:MM:SS±00:00")]
+    pub taken_after: Option<DateTime<FixedOffset>>,
+    /// Assets taken before this date/time
+    #[arg(long, value_name = "YYYY-MM-DDTHH:MM:SS±00:00")]
+    pub taken_before: Option<DateTime<FixedOffset>>,
+}
+
+impl ClearFilterArgs {
+    fn is_empty(&self) -> bool {
+        self.tag.is_none()
+            && self.album.is_none()
+            && self.favorite.is_none()
+            && self.taken_after.is_none()
+            && self.taken_before.is_none()
+    }
+
+    /// The equivalent `assets search --remove` arguments.
+    fn search_remove_args(&self) -> AssetSearchArgs {
+        AssetSearchArgs {
+            remove: true,
+            tag: self.tag.clone(),
+            album: self.album.clone(),
+            favorite: self.favorite,
+            taken_after: self.taken_after,
+            taken_before: self.taken_before,
+            ..Default::default()
+        }
+    }
+}
+
+/// Orientation of an asset by its width and height
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    /// Higher than wide
+    Portrait,
+    /// Wider than high
+    Landscape,
+    /// Same width and height
+    Square,
+}
+
+/// Type of an asset
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AssetType {
+    /// Photos
+    Image,
+    /// Videos
+    Video,
+    /// Audio files
+    Audio,
+    /// Any other file
+    Other,
+}
+
+impl From<AssetType> for AssetTypeEnum {
+    fn from(asset_type: AssetType) -> Self {
+        match asset_type {
+            AssetType::Image => AssetTypeEnum::Image,
+            AssetType::Video => AssetTypeEnum::Video,
+            AssetType::Audio => AssetTypeEnum::Audio,
+            AssetType::Other => AssetTypeEnum::Other,
+        }
+    }
+}
+
+/// Order of search results by date taken
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchOrder {
+    /// Most recent first
+    Newest,
+    /// Oldest first
+    Oldest,
+}
+
+impl From<SearchOrder> for AssetOrder {
+    fn from(order: SearchOrder) -> Self {
+        match order {
+            SearchOrder::Newest => AssetOrder::Desc,
+            SearchOrder::Oldest => AssetOrder::Asc,
+        }
+    }
 }
 
 /// Columns for CSV listing of selected assets
-#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AssetColumns {
     /// Asset UUID
     Id,
@@ -56,78 +300,720 @@ pub enum AssetColumns {
     /// DateTimeOriginal from EXIF metadata with timezone (alias: exif-datetime)
     #[value(alias("exif-datetime"))]
     ExifDateTimeOriginal,
+
+    /// Asset is marked as favorite (true/false)
+    IsFavorite,
+    /// Asset is archived (true/false)
+    IsArchived,
+    /// Asset is in trash (true/false)
+    IsTrashed,
+    /// Asset visibility: timeline, archive, hidden or locked
+    Visibility,
+
+    /// UUID of the asset owner
+    OwnerId,
+    /// Name of the asset owner (empty if not provided by the server)
+    OwnerName,
+    /// UUID of the external library (empty for uploaded assets)
+    LibraryId,
+    /// UUID of the paired video of a live photo (empty for other assets)
+    LivePhotoVideoId,
+    /// SHA-1 checksum of the original file (base64)
+    Checksum,
+
+    /// Camera make from EXIF metadata
+    Make,
+    /// Camera model from EXIF metadata
+    Model,
+    /// City from EXIF metadata
+    City,
+    /// Country from EXIF metadata
+    Country,
+}
+
+/// Output format for listing assets
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ListFormat {
+    /// CSV format
+    Csv,
+    /// Tab separated values, tabs and line breaks in values are replaced by spaces
+    Tsv,
+    /// Json format
+    Json,
+    /// Json format, pretty printed
+    JsonPretty,
+}
+
+/// Restrict a listing to the N newest or oldest assets by dateTimeOriginal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ListExtreme {
+    /// Newest first
+    Newest(usize),
+    /// Oldest first
+    Oldest(usize),
+}
+
+/// Quoting of csv fields
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CsvQuoting {
+    /// Quote only fields containing a comma, quote or line break
+    #[default]
+    Minimal,
+    /// Quote all fields
+    Always,
+    /// Never quote, fields may break the csv structure
+    Never,
+}
+
+impl CsvQuoting {
+    /// `value` as csv field, embedded quotes of quoted fields are doubled.
+    pub fn field<'a>(self, value: &'a str) -> Cow<'a, str> {
+        let quote = match self {
+            CsvQuoting::Minimal => value.contains([',', '"', '\n', '\r']),
+            CsvQuoting::Always => true,
+            CsvQuoting::Never => false,
+        };
+        if quote {
+            Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+        } else {
+            Cow::Borrowed(value)
+        }
+    }
+}
+
+/// Comparison operator of an `AssetFilter`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FilterOp {
+    Eq,
+    Ne,
+    Contains,
+}
+
+/// Client side filter `<column><op><value>` for listing the selection, e.g. `make=SONY`.
+/// Supported operators: `=`, `!=` and `~` (contains), all case-insensitive.
+#[derive(Clone, Debug)]
+pub struct AssetFilter {
+    column: AssetColumns,
+    op: FilterOp,
+    value: String,
+}
+
+impl std::str::FromStr for AssetFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let Some(pos) = s.find(['=', '!', '~']) else {
+            bail!("Invalid filter '{}', expected <column>=|!=|~<value>", s);
+        };
+        let (column, rest) = s.split_at(pos);
+        let (op, value) = if let Some(value) = rest.strip_prefix("!=") {
+            (FilterOp::Ne, value)
+        } else if let Some(value) = rest.strip_prefix('=') {
+            (FilterOp::Eq, value)
+        } else if let Some(value) = rest.strip_prefix('~') {
+            (FilterOp::Contains, value)
+        } else {
+            bail!("Invalid filter '{}', expected <column>=|!=|~<value>", s);
+        };
+        let column = <AssetColumns as clap::ValueEnum>::from_str(column.trim(), true)
+            .map_err(|_| anyhow::anyhow!("Unknown column '{}' in filter '{}'", column, s))?;
+        Ok(AssetFilter {
+            column,
+            op,
+            value: value.to_lowercase(),
+        })
+    }
+}
+
+impl AssetFilter {
+    fn matches(&self, asset: &AssetResponseDto, assume_tz: Option<TimeZoneValue>) -> bool {
+        let value = ImmichCtl::asset_column(asset, self.column, assume_tz).to_lowercase();
+        match self.op {
+            FilterOp::Eq => value == self.value,
+            FilterOp::Ne => value != self.value,
+            FilterOp::Contains => value.contains(&self.value),
+        }
+    }
 }
 
 impl ImmichCtl {
-    pub fn assets_clear(&mut self) -> Result<()> {
+    /// Remove the selected assets matching `filter` like `assets search --remove`. Without
+    /// filter the whole selection is cleared, which requires `yes` unless it's empty.
+    pub async fn assets_clear(&mut self, filter: &ClearFilterArgs, yes: bool) -> Result<()> {
+        if !filter.is_empty() {
+            return self
+                .assets_search_remove(&filter.search_remove_args())
+                .await;
+        }
         let mut sel = Assets::load(&self.assets_file);
+        if !yes && !self.dry_run && !sel.is_empty() {
+            bail!(
+                "Clearing all {} selected assets requires --yes. Use filters like --tag or --favorite to clear only matching assets.",
+                sel.len()
+            );
+        }
+        if self.dry_run {
+            eprintln!("Dry run: would clear {} assets from selection.", sel.len());
+            return Ok(());
+        }
         sel.clear();
         sel.save().context("Could not save asset selection")?;
         eprintln!("Asset selection cleared.");
         Ok(())
     }
 
-    pub fn assets_count(&self) {
-        let sel = Assets::load(&self.assets_file);
-        println!("{}", sel.len());
+    /// Interactively page through the selection and drop assets from it.
+    pub fn assets_review(&mut self) -> Result<()> {
+        self.assert_interactive("immichctl assets review")?;
+        let mut sel = self.load_assets();
+        if sel.is_empty() {
+            eprintln!("Selection is empty, nothing to review.");
+            return Ok(());
+        }
+        let removed = self.review_assets(
+            &mut sel,
+            &mut std::io::stdin().lock(),
+            &mut std::io::stderr(),
+        )?;
+        if self.dry_run {
+            eprintln!("Dry run: would remove {} asset(s) from selection.", removed);
+            return Ok(());
+        }
+        sel.save()?;
+        eprintln!("Removed {} asset(s) from selection.", removed);
+        Ok(())
     }
 
-    pub async fn assets_refresh(&mut self) -> Result<()> {
-        let mut sel = Assets::load(&self.assets_file);
-        let total = sel.len();
-        if total == 0 {
-            eprintln!("No assets to refresh.");
+    /// Show each asset (ordered by date/time) on `output` and read a decision per asset
+    /// from `input`: `d` drops the asset, `q` (or end of input) keeps all remaining
+    /// assets, anything else keeps it. Returns the number of dropped assets.
+    fn review_assets(
+        &self,
+        sel: &mut Assets,
+        input: &mut dyn BufRead,
+        output: &mut dyn Write,
+    ) -> Result<usize> {
+        let mut assets: Vec<_> = sel.iter_assets().collect();
+        assets.sort_by_key(|a| Self::get_date_time_original(a, self.assume_tz));
+        let total = assets.len();
+        let mut drop = Vec::new();
+        for (i, asset) in assets.iter().enumerate() {
+            let tags: Vec<_> = asset.tags.iter().map(|t| t.value.as_str()).collect();
+            writeln!(
+                output,
+                "[{}/{}] {}  {}  favorite: {}  tags: {}",
+                i + 1,
+                total,
+                asset.original_file_name,
+                Self::get_date_time_original(asset, self.assume_tz).to_rfc3339(),
+                asset.is_favorite,
+                tags.join(", ")
+            )?;
+            write!(output, "[k]eep, [d]rop, [q]uit? ")?;
+            output.flush()?;
+            let mut answer = String::new();
+            if input.read_line(&mut answer)? == 0 {
+                writeln!(output)?;
+                break;
+            }
+            match answer.trim().to_ascii_lowercase().as_str() {
+                "d" | "drop" => drop.push(asset.id),
+                "q" | "quit" => break,
+                _ => {}
+            }
+        }
+        for id in &drop {
+            sel.remove_asset(id);
+        }
+        Ok(drop.len())
+    }
+
+    /// Print the JSON Schema of the asset selection file.
+    pub fn assets_schema(&self) -> Result<()> {
+        let schema = schemars::schema_for!(Assets);
+        self.print_json(&schema)
+    }
+
+    /// Print the number of selected assets, as `{"count": N}` in json `format`.
+    pub fn assets_count(&self, format: OutputMode) -> Result<()> {
+        let sel = self.load_assets();
+        match format {
+            OutputMode::Json => self.print_json(&serde_json::json!({ "count": sel.len() }))?,
+            OutputMode::Text => println!("{}", sel.len()),
+        }
+        Ok(())
+    }
+
+    pub async fn assets_refresh(&mut self, continue_on_error: bool) -> Result<()> {
+        self.assets_refresh_with_checkpoint(continue_on_error, REFRESH_CHECKPOINT_INTERVAL)
+            .await
+    }
+
+    /// Refreshes pending assets and saves the selection every `checkpoint_interval` assets,
+    /// so that an interrupted refresh can be resumed.
+    async fn assets_refresh_with_checkpoint(
+        &mut self,
+        continue_on_error: bool,
+        checkpoint_interval: usize,
+    ) -> Result<()> {
+        let mut sel = self.load_assets();
+        if sel.is_empty() {
+            eprintln!("Selection is empty, nothing to refresh.");
             return Ok(());
         }
-        for (i, asset) in sel.iter_mut_assets().enumerate() {
-            let asset_res = self
-                .immich()?
-                .get_asset_info(&asset.id, None, None)
-                .await
-                .with_context(|| format!("Could not retrieve asset '{}'", asset.id))?;
-            *asset = asset_res.into_inner();
+        // a refresh of a date window doesn't start or resume a checkpointed refresh of all assets
+        let windowed = self.since.is_some() || self.until.is_some();
+        let pending: Vec<_> = if windowed {
+            sel.iter_assets()
+                .filter(|asset| self.in_date_window(asset))
+                .map(|asset| asset.id)
+                .collect()
+        } else {
+            sel.refresh_pending()
+        };
+        let total = pending.len();
+        if self.dry_run {
+            eprintln!("Dry run: would refresh metadata for {} assets.", total);
+            return Ok(());
+        }
+        if !windowed && total < sel.len() {
+            eprintln!(
+                "Resuming interrupted refresh, {} of {} assets left.",
+                total,
+                sel.len()
+            );
+        }
+        let mut refreshed = 0;
+        let mut failed = 0;
+        let start = Instant::now();
+        let client = self.immich()?;
+        // retrieved in parallel, the results are handled in selection order for checkpointing
+        let mut responses = futures::stream::iter(&pending)
+            .map(|asset_id| async move {
+                let res = client
+                    .get_asset_info(asset_id, None, None)
+                    .with_api_context(|| format!("Could not retrieve asset '{}'", asset_id))
+                    .await;
+                (asset_id, res)
+            })
+            .buffered(self.read_concurrency.get());
+        let mut i = 0;
+        while let Some((asset_id, asset_res)) = responses.next().await {
+            match asset_res {
+                Ok(asset) => {
+                    sel.add_asset(asset.into_inner());
+                    sel.mark_refreshed(asset_id);
+                    refreshed += 1;
+                }
+                Err(err) if continue_on_error => {
+                    self.eprint_asset_warning(&format!("{:#}", err), asset_id);
+                    failed += 1;
+                }
+                Err(err) => {
+                    sel.save()?;
+                    return Err(err);
+                }
+            }
+            if (i + 1) % checkpoint_interval == 0 {
+                sel.save()?;
+            }
             self.eprint_progress_indicator(i, total, 50);
+            i += 1;
         }
+        self.eprint_timing(&format!("retrieve of {} assets", total), start);
+        let start = Instant::now();
         sel.save()?;
-        eprintln!("Refreshed metadata for {} assets.", sel.len());
+        self.eprint_timing("save", start);
+        eprintln!("Refreshed metadata for {} assets.", refreshed);
+        if failed > 0 {
+            bail!(
+                "Could not refresh {} assets, run 'immichctl assets refresh' again to retry.",
+                failed
+            );
+        }
         Ok(())
     }
 
-    pub fn assets_list_json(&self, pretty: bool) -> Result<()> {
-        let sel = Assets::load(&self.assets_file);
-        let assets: Vec<_> = sel.iter_assets().collect();
-        let stdout = std::io::stdout();
-        let writer = stdout.lock();
-        if pretty {
-            serde_json::to_writer_pretty(writer, &assets)?;
-        } else {
-            serde_json::to_writer(writer, &assets)?;
+    /// Queue a server-side metadata (EXIF) re-extraction job for the selected assets,
+    /// returns the number of queued assets. The job runs asynchronously on the server.
+    pub async fn assets_refresh_server(&self) -> Result<usize> {
+        let sel = self.load_assets();
+        let asset_ids: Vec<_> = sel
+            .iter_assets()
+            .filter(|asset| self.in_date_window(asset))
+            .map(|asset| asset.id)
+            .collect();
+        if asset_ids.is_empty() {
+            eprintln!("Selection is empty, nothing to refresh.");
+            return Ok(0);
+        }
+        if self.dry_run {
+            eprintln!(
+                "Dry run: would queue metadata extraction for {} assets on the server.",
+                asset_ids.len()
+            );
+            return Ok(0);
+        }
+        let mut queued = 0;
+        for ids in asset_ids.chunks(self.batch_size.get()) {
+            let dto = AssetJobsDto {
+                asset_ids: ids.to_vec(),
+                name: AssetJobName::RefreshMetadata,
+            };
+            self.immich()?
+                .run_asset_jobs(&dto)
+                .api_context("Could not queue metadata extraction")
+                .await?;
+            queued += ids.len();
         }
+        eprintln!(
+            "Queued metadata extraction for {} assets on the server.",
+            queued
+        );
+        self.record_history("assets refresh --server", queued);
+        Ok(queued)
+    }
+
+    /// List the selected assets matching all `filters` as json to `output_file` or stdout.
+    /// Without `columns` the full asset data is written, otherwise one object per asset
+    /// with the given columns.
+    pub fn assets_list_json(
+        &self,
+        pretty: bool,
+        columns: &[AssetColumns],
+        filters: &[AssetFilter],
+        extreme: Option<ListExtreme>,
+        output_file: Option<&Path>,
+    ) -> Result<()> {
+        let sel = self.load_assets();
+        let assets = self.list_assets(&sel, filters, extreme);
+        self.warn_implausible_timezones(assets.iter().copied());
+        let mut writer = Self::output_writer(output_file)?;
+        self.write_assets_json(&mut writer, &assets, pretty, columns)?;
+        writer.flush()?;
+        self.eprint_output_file_written(output_file, assets.len());
+        self.eprint_list_summary(assets.len());
+        Ok(())
+    }
+
+    /// List the selected assets matching all `filters` as csv to `output_file` or stdout.
+    pub fn assets_list_csv(
+        &self,
+        columns: &[AssetColumns],
+        filters: &[AssetFilter],
+        extreme: Option<ListExtreme>,
+        output_file: Option<&Path>,
+    ) -> Result<()> {
+        let sel = self.load_assets();
+        let assets = self.list_assets(&sel, filters, extreme);
+        self.warn_implausible_timezones(assets.iter().copied());
+        let mut writer = Self::output_writer(output_file)?;
+        let count = self.write_assets_csv(&mut writer, assets.into_iter(), columns)?;
+        writer.flush()?;
+        self.eprint_output_file_written(output_file, count);
+        self.eprint_list_summary(count);
+        Ok(())
+    }
+
+    pub fn assets_list_tsv(
+        &self,
+        columns: &[AssetColumns],
+        filters: &[AssetFilter],
+        extreme: Option<ListExtreme>,
+        output_file: Option<&Path>,
+    ) -> Result<()> {
+        let sel = self.load_assets();
+        let assets = self.list_assets(&sel, filters, extreme);
+        self.warn_implausible_timezones(assets.iter().copied());
+        let mut writer = Self::output_writer(output_file)?;
+        let count = self.write_assets_tsv(&mut writer, assets.into_iter(), columns)?;
+        writer.flush()?;
+        self.eprint_output_file_written(output_file, count);
+        self.eprint_list_summary(count);
         Ok(())
     }
 
-    pub fn assets_list_csv(&self, columns: &[AssetColumns]) {
-        let sel = Assets::load(&self.assets_file);
-        for asset in sel.iter_assets() {
+    /// The selected assets matching all `filters`, only the newest/oldest ones if `extreme`
+    /// is given. The extreme assets are found by partial sort.
+    fn list_assets<'a>(
+        &self,
+        sel: &'a Assets,
+        filters: &'a [AssetFilter],
+        extreme: Option<ListExtreme>,
+    ) -> Vec<&'a AssetResponseDto> {
+        let mut assets: Vec<_> = self.filter_assets(sel, filters).collect();
+        let Some(extreme) = extreme else {
+            return assets;
+        };
+        let taken = |a: &AssetResponseDto| Self::get_date_time_original(a, self.assume_tz);
+        let (n, newest) = match extreme {
+            ListExtreme::Newest(n) => (n, true),
+            ListExtreme::Oldest(n) => (n, false),
+        };
+        let cmp = |a: &&AssetResponseDto, b: &&AssetResponseDto| {
+            let ord = taken(a).cmp(&taken(b));
+            if newest { ord.reverse() } else { ord }
+        };
+        if n < assets.len() {
+            if n > 0 {
+                assets.select_nth_unstable_by(n - 1, &cmp);
+            }
+            assets.truncate(n);
+        }
+        assets.sort_by(cmp);
+        assets
+    }
+
+    fn write_assets_json(
+        &self,
+        writer: &mut dyn Write,
+        assets: &[&AssetResponseDto],
+        pretty: bool,
+        columns: &[AssetColumns],
+    ) -> Result<()> {
+        let value = if columns.is_empty() {
+            serde_json::to_value(assets)?
+        } else {
+            assets
+                .iter()
+                .map(|asset| {
+                    columns
+                        .iter()
+                        .map(|col| {
+                            let name = clap::ValueEnum::to_possible_value(col)
+                                .unwrap()
+                                .get_name()
+                                .to_string();
+                            (name, Self::asset_column_json(asset, *col, self.assume_tz))
+                        })
+                        .collect::<serde_json::Map<_, _>>()
+                })
+                .collect()
+        };
+        output::write_json(writer, &value, pretty)
+    }
+
+    /// Write one csv line per asset, returns the number of written assets.
+    fn write_assets_csv<'a>(
+        &self,
+        writer: &mut dyn Write,
+        assets: impl Iterator<Item = &'a AssetResponseDto>,
+        columns: &[AssetColumns],
+    ) -> Result<usize> {
+        self.write_assets_rows(writer, assets, columns, ",", |value| {
+            self.csv_quoting.field(value)
+        })
+    }
+
+    fn write_assets_tsv<'a>(
+        &self,
+        writer: &mut dyn Write,
+        assets: impl Iterator<Item = &'a AssetResponseDto>,
+        columns: &[AssetColumns],
+    ) -> Result<usize> {
+        self.write_assets_rows(writer, assets, columns, "\t", tsv_field)
+    }
+
+    /// One line per asset with the `columns` (default: original file name) separated by
+    /// `separator`, each value escaped by `field`. Returns the number of assets written.
+    fn write_assets_rows<'a>(
+        &self,
+        writer: &mut dyn Write,
+        assets: impl Iterator<Item = &'a AssetResponseDto>,
+        columns: &[AssetColumns],
+        separator: &str,
+        field: impl for<'v> Fn(&'v str) -> Cow<'v, str>,
+    ) -> Result<usize> {
+        let columns = if columns.is_empty() {
+            &[AssetColumns::OriginalFileName]
+        } else {
+            columns
+        };
+        if self.list_header {
+            let names: Vec<_> = Self::column_names(columns)
+                .iter()
+                .map(|name| field(name).into_owned())
+                .collect();
+            writeln!(writer, "{}", names.join(separator))?;
+        }
+        let mut count = 0;
+        for asset in assets {
+            count += 1;
             for (i, col) in columns.iter().enumerate() {
                 if i > 0 {
-                    print!(",");
+                    write!(writer, "{}", separator)?;
                 }
-                print!("{}", Self::asset_column(asset, *col));
+                let value = Self::asset_column(asset, *col, self.assume_tz);
+                write!(writer, "{}", field(&value))?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(count)
+    }
+
+    /// Column names as accepted by `--column`, so that a header can be used to build the
+    /// next `assets list` command.
+    fn column_names(columns: &[AssetColumns]) -> Vec<String> {
+        columns
+            .iter()
+            .filter_map(clap::ValueEnum::to_possible_value)
+            .map(|v| v.get_name().to_string())
+            .collect()
+    }
+
+    /// Print assets uploaded within the last `days`, newest first, without changing the
+    /// asset selection. With `limit` at most `limit` assets are printed.
+    pub async fn assets_recent(
+        &mut self,
+        days: u32,
+        limit: Option<NonZeroU64>,
+        format: ListFormat,
+        columns: &[AssetColumns],
+    ) -> Result<()> {
+        let assets = self.recent_assets(days, limit).await?;
+        let mut writer = Self::output_writer(None)?;
+        match format {
+            ListFormat::Csv => {
+                self.write_assets_csv(&mut writer, assets.iter(), columns)?;
+            }
+            ListFormat::Tsv => {
+                self.write_assets_tsv(&mut writer, assets.iter(), columns)?;
+            }
+            ListFormat::Json | ListFormat::JsonPretty => {
+                let assets: Vec<_> = assets.iter().collect();
+                self.write_assets_json(
+                    &mut writer,
+                    &assets,
+                    format == ListFormat::JsonPretty || self.pretty_json,
+                    columns,
+                )?;
+            }
+        }
+        writer.flush()?;
+        eprintln!(
+            "Found {} assets added in the last {} days.",
+            assets.len(),
+            days
+        );
+        Ok(())
+    }
+
+    /// Assets uploaded within the last `days`, most recently uploaded first. The server
+    /// orders by date taken, so all pages are fetched before the upload order is applied.
+    async fn recent_assets(
+        &self,
+        days: u32,
+        limit: Option<NonZeroU64>,
+    ) -> Result<Vec<AssetResponseDto>> {
+        let search_dto = MetadataSearchDto {
+            created_after: Some(Utc::now() - TimeDelta::days(days.into())),
+            order: Some(AssetOrder::Desc),
+            visibility: Some(AssetVisibility::Timeline),
+            with_exif: Some(true),
+            ..Default::default()
+        };
+        let mut assets = self.search_pages(search_dto).await?;
+        assets.sort_by_key(|a| std::cmp::Reverse(a.created_at));
+        if let Some(limit) = limit {
+            assets.truncate(usize::try_from(limit.get()).unwrap_or(usize::MAX));
+        }
+        Ok(assets)
+    }
+
+    fn filter_assets<'a>(
+        &self,
+        sel: &'a Assets,
+        filters: &'a [AssetFilter],
+    ) -> impl Iterator<Item = &'a AssetResponseDto> {
+        let assume_tz = self.assume_tz;
+        sel.iter_assets()
+            .filter(|asset| self.in_date_window(asset))
+            .filter(move |asset| filters.iter().all(|f| f.matches(asset, assume_tz)))
+    }
+
+    /// Whether the asset was taken within `--since` and `--until`.
+    fn in_date_window(&self, asset: &AssetResponseDto) -> bool {
+        if self.since.is_none() && self.until.is_none() {
+            return true;
+        }
+        let taken = Self::get_date_time_original(asset, self.assume_tz);
+        self.since.is_none_or(|since| taken >= since)
+            && self.until.is_none_or(|until| taken < until)
+    }
+
+    fn output_writer(output_file: Option<&Path>) -> Result<Box<dyn Write>> {
+        match output_file {
+            Some(path) => {
+                let file = File::create(path)
+                    .with_context(|| format!("Could not create file '{}'", path.display()))?;
+                Ok(Box::new(BufWriter::new(file)))
             }
-            println!();
+            None => Ok(Box::new(std::io::stdout().lock())),
+        }
+    }
+
+    fn eprint_output_file_written(&self, output_file: Option<&Path>, count: usize) {
+        if let Some(path) = output_file
+            && !self.quiet
+        {
+            eprintln!("Wrote {} assets to '{}'.", count, path.display());
+        }
+    }
+
+    /// The `--summary` line, on stderr so that it doesn't end up in csv or json output.
+    fn eprint_list_summary(&self, count: usize) {
+        if self.list_summary && !self.quiet {
+            eprintln!("{}", Self::list_summary(count));
+        }
+    }
+
+    fn list_summary(count: usize) -> String {
+        if count == 1 {
+            "# 1 asset".to_string()
+        } else {
+            format!("# {} assets", count)
         }
     }
 
-    fn asset_column(asset: &AssetResponseDto, col: AssetColumns) -> Cow<'_, str> {
+    fn asset_column(
+        asset: &AssetResponseDto,
+        col: AssetColumns,
+        assume_tz: Option<TimeZoneValue>,
+    ) -> Cow<'_, str> {
         match col {
             AssetColumns::Id => Cow::Owned(asset.id.to_string()),
             AssetColumns::OriginalFileName => Cow::Borrowed(&asset.original_file_name),
             AssetColumns::FileCreatedAt => Cow::Owned(asset.file_created_at.to_rfc3339()),
-            AssetColumns::Timezone => Cow::Owned(Self::asset_timezone_offset(asset).to_string()),
+            AssetColumns::Timezone => Cow::Owned(
+                Self::get_assert_date_time_original(asset, assume_tz)
+                    .timezone()
+                    .to_string(),
+            ),
             AssetColumns::DateTimeOriginal => {
-                Cow::Owned(Self::get_assert_date_time_original(asset).to_rfc3339())
+                Cow::Owned(Self::get_assert_date_time_original(asset, assume_tz).to_rfc3339())
             }
+            AssetColumns::IsFavorite => Cow::Owned(asset.is_favorite.to_string()),
+            AssetColumns::IsArchived => Cow::Owned(asset.is_archived.to_string()),
+            AssetColumns::IsTrashed => Cow::Owned(asset.is_trashed.to_string()),
+            AssetColumns::Visibility => Cow::Owned(asset.visibility.to_string()),
+            AssetColumns::OwnerId => Cow::Owned(asset.owner_id.to_string()),
+            AssetColumns::OwnerName => match &asset.owner {
+                Some(owner) => Cow::Borrowed(&owner.name),
+                None => Cow::Borrowed(""),
+            },
+            AssetColumns::LibraryId => match &asset.library_id {
+                Some(library_id) => Cow::Owned(library_id.to_string()),
+                None => Cow::Borrowed(""),
+            },
+            AssetColumns::LivePhotoVideoId => match &asset.live_photo_video_id {
+                Some(video_id) => Cow::Borrowed(video_id),
+                None => Cow::Borrowed(""),
+            },
+            AssetColumns::Checksum => Cow::Borrowed(&asset.checksum),
+            AssetColumns::Make => Self::exif_column(asset, |exif| &exif.make),
+            AssetColumns::Model => Self::exif_column(asset, |exif| &exif.model),
+            AssetColumns::City => Self::exif_column(asset, |exif| &exif.city),
+            AssetColumns::Country => Self::exif_column(asset, |exif| &exif.country),
             AssetColumns::ExifTimezone => {
                 if let Some(exif_info) = &asset.exif_info {
                     if let Some(tz_str) = &exif_info.time_zone {
@@ -149,46 +1035,259 @@ impl ImmichCtl {
         }
     }
 
+    /// Column value for json output: booleans as json booleans, empty values as null.
+    fn asset_column_json(
+        asset: &AssetResponseDto,
+        col: AssetColumns,
+        assume_tz: Option<TimeZoneValue>,
+    ) -> serde_json::Value {
+        match col {
+            AssetColumns::IsFavorite => asset.is_favorite.into(),
+            AssetColumns::IsArchived => asset.is_archived.into(),
+            AssetColumns::IsTrashed => asset.is_trashed.into(),
+            _ => match Self::asset_column(asset, col, assume_tz) {
+                value if value.is_empty() => serde_json::Value::Null,
+                value => value.into_owned().into(),
+            },
+        }
+    }
+
+    fn exif_column(
+        asset: &AssetResponseDto,
+        field: impl Fn(&ExifResponseDto) -> &Option<String>,
+    ) -> Cow<'_, str> {
+        match asset
+            .exif_info
+            .as_ref()
+            .and_then(|exif| field(exif).as_deref())
+        {
+            Some(value) => Cow::Borrowed(value),
+            None => Cow::Borrowed(""),
+        }
+    }
+
     pub async fn assets_search_add(&mut self, args: &AssetSearchArgs) -> Result<()> {
-        let mut search_dto = self.build_search_dto(args).await?;
-        search_dto.with_exif = Some(true);
+        let mut sel = self.load_assets();
+        let assets = match (&args.similar_to, &args.shared_link) {
+            (Some(reference), _) => self.search_similar(reference, args).await?,
+            (None, Some(link)) => {
+                let password = Self::link_password(args.link_password.as_deref())?;
+                self.shared_link_assets(link, password.as_deref()).await?
+            }
+            (None, None) => {
+                let mut search_dto = self.build_search_dto(args).await?;
+                search_dto.with_exif = Some(true);
+                let ids = Self::search_ids(args)?;
+                if args.print_raw {
+                    for search_dto in Self::split_search(search_dto, &ids) {
+                        self.search_print_raw(search_dto).await?;
+                    }
+                    return Ok(());
+                }
+                // stop paging once --max-new new assets are found or as soon as the
+                // selection would exceed --max-selection
+                let old_len = if args.replace { 0 } else { sel.len() };
+                let mut found = Vec::new();
+                let mut new = 0;
+                let mut exceeded = false;
+                let _ = self
+                    .search_pages_any_tag_with(search_dto, &ids, &mut |asset| {
+                        if !self.matches_local_filters(&asset, args) {
+                            return ControlFlow::Continue(());
+                        }
+                        if args.replace || !sel.contains(&asset.id) {
+                            new += 1;
+                        }
+                        found.push(asset);
+                        if Self::exceeds_max_selection(old_len + new, args) && !args.yes {
+                            exceeded = true;
+                            return ControlFlow::Break(());
+                        }
+                        if args.max_new.is_some_and(|max| new == max.get()) {
+                            ControlFlow::Break(())
+                        } else {
+                            ControlFlow::Continue(())
+                        }
+                    })
+                    .await?;
+                if exceeded {
+                    bail!(
+                        "Selection would grow from {} to more than {} assets, exceeding --max-selection {}. Use --yes to continue anyway.",
+                        old_len,
+                        args.max_selection,
+                        args.max_selection
+                    );
+                }
+                found
+            }
+        };
 
-        let mut sel = Assets::load(&self.assets_file);
+        let replaced = sel.len();
+        if args.replace {
+            // the search succeeded, the old selection can be dropped
+            sel.clear();
+            if let Some(server) = self.selection_server() {
+                sel.set_server(&server);
+            }
+        }
         let old_len = sel.len();
-        for asset in self.search_pages(search_dto).await? {
-            sel.add_asset(asset);
+        let (added, refreshed) = Self::add_assets(
+            &mut sel,
+            assets
+                .into_iter()
+                .filter(|asset| self.matches_local_filters(asset, args)),
+        );
+        if Self::exceeds_max_selection(sel.len(), args) {
+            let msg = format!(
+                "Selection would grow from {} to {} assets, exceeding --max-selection {}.",
+                old_len,
+                sel.len(),
+                args.max_selection
+            );
+            if !args.yes {
+                bail!("{} Use --yes to continue anyway.", msg);
+            }
+            self.eprint_warning(&msg);
+        }
+        if self.dry_run {
+            if args.replace {
+                eprintln!(
+                    "Dry run: would replace {} selected asset(s) with {} asset(s).",
+                    replaced, added
+                );
+            } else {
+                eprintln!(
+                    "Dry run: would add {} asset(s) to selection{}.",
+                    added,
+                    Self::refreshed_suffix(refreshed)
+                );
+            }
+            return Ok(());
         }
+        let start = Instant::now();
         sel.save()?;
-        let new_len = sel.len();
-        eprintln!(
-            "Added {} asset(s) to selection.",
-            new_len.saturating_sub(old_len)
-        );
+        self.eprint_timing("save", start);
+        if args.replace {
+            eprintln!(
+                "Replaced {} selected asset(s) with {} asset(s).",
+                replaced, added
+            );
+        } else {
+            eprintln!(
+                "Added {} asset(s) to selection{}.",
+                added,
+                Self::refreshed_suffix(refreshed)
+            );
+        }
         Ok(())
     }
 
-    pub async fn assets_search_remove(&mut self, args: &AssetSearchArgs) -> Result<()> {
-        let mut assets = Assets::load(&self.assets_file);
-        let old_len = assets.len();
+    /// Whether a selection of `len` assets exceeds `--max-selection` (0 = unlimited).
+    fn exceeds_max_selection(len: usize, args: &AssetSearchArgs) -> bool {
+        args.max_selection > 0 && len > args.max_selection
+    }
 
-        if args.tag.is_some() || args.album.is_some() {
-            // remote search needed if tag or album is specified
-            if args.timezone.is_some() {
-                bail!(
-                    "The --timezone option cannot be used together with other search options when multiple filters are applied."
-                );
+    /// Add `assets` to the selection, returns the number of new and of refreshed
+    /// (already selected) assets.
+    fn add_assets(
+        sel: &mut Assets,
+        assets: impl Iterator<Item = AssetResponseDto>,
+    ) -> (usize, usize) {
+        let mut added = 0;
+        let mut refreshed = 0;
+        for asset in assets {
+            if sel.contains(&asset.id) {
+                refreshed += 1;
+            } else {
+                added += 1;
             }
-            let search_dto = self.build_search_dto(args).await?;
-            self.assets_search_remove_by_immich_query(search_dto, &mut assets)
-                .await?;
+            sel.add_asset(asset);
+        }
+        (added, refreshed)
+    }
+
+    /// Suffix of the add summary for assets that were already selected and got refreshed.
+    fn refreshed_suffix(refreshed: usize) -> String {
+        if refreshed > 0 {
+            format!(", refreshed {} existing", refreshed)
         } else {
-            // other args can be handled locally
-            assets.retain(|asset| {
-                let mut retain = false;
-                if let Some(id) = &args.id
-                    && asset.id.to_string() != *id
-                {
-                    retain = true;
+            String::new()
+        }
+    }
+
+    /// Align the selection with a fresh search by `tag` and/or `album`: new matches are
+    /// added, matches are refreshed and selected assets that don't match anymore are removed.
+    pub async fn assets_sync(&mut self, tag: Option<&str>, album: Option<&str>) -> Result<()> {
+        let args = AssetSearchArgs {
+            tag: tag.map(str::to_string),
+            album: album.map(str::to_string),
+            ..Default::default()
+        };
+        let mut search_dto = self.build_search_dto(&args).await?;
+        search_dto.with_exif = Some(true);
+        let fresh = self.search_pages(search_dto).await?;
+        let fresh_ids: std::collections::HashSet<_> = fresh.iter().map(|a| a.id).collect();
+
+        let mut sel = self.load_assets();
+        let old_len = sel.len();
+        sel.retain(|asset| fresh_ids.contains(&asset.id));
+        let removed = old_len - sel.len();
+        let kept = sel.len();
+        for asset in fresh {
+            sel.add_asset(asset);
+        }
+        let added = sel.len() - kept;
+        if self.dry_run {
+            eprintln!(
+                "Dry run: would add {} and remove {} asset(s), {} unchanged.",
+                added, removed, kept
+            );
+            return Ok(());
+        }
+        sel.save()?;
+        eprintln!(
+            "Synced selection: added {}, removed {} asset(s), refreshed {}.",
+            added, removed, kept
+        );
+        Ok(())
+    }
+
+    pub async fn assets_search_remove(&mut self, args: &AssetSearchArgs) -> Result<()> {
+        let mut assets = self.load_assets();
+        let old_len = assets.len();
+
+        if args.tag.is_some()
+            || args.album.is_some()
+            || args.city.is_some()
+            || args.country.is_some()
+        {
+            // remote search needed if tag or album is specified, also for city or country
+            // as the reverse geocoding of the selected assets may be missing or outdated
+            if args.timezone.is_some() {
+                bail!(
+                    "The --timezone option cannot be used together with other search options when multiple filters are applied."
+                );
+            }
+            let search_dto = self.build_search_dto(args).await?;
+            let ids = Self::search_ids(args)?;
+            self.assets_search_remove_by_immich_query(search_dto, &ids, args, &mut assets)
+                .await?;
+        } else {
+            // other args can be handled locally
+            let library_id = match &args.library {
+                Some(library) => Some(self.find_library(library).await?),
+                None => None,
+            };
+            let ids = Self::search_ids(args)?;
+            assets.retain(|asset| {
+                let mut retain = false;
+                if let Some(library_id) = &library_id
+                    && asset.library_id.as_ref() != Some(library_id)
+                {
+                    retain = true;
+                }
+                if !ids.is_empty() && !ids.contains(&asset.id) {
+                    retain = true;
                 }
                 if let Some(favorite) = &args.favorite
                     && asset.is_favorite != *favorite
@@ -196,12 +1295,35 @@ impl ImmichCtl {
                     retain = true;
                 }
                 if let Some(taken_after) = &args.taken_after
-                    && ImmichCtl::get_date_time_original(asset) <= *taken_after
+                    && ImmichCtl::get_date_time_original(asset, None) <= *taken_after
                 {
                     retain = true;
                 }
                 if let Some(taken_before) = &args.taken_before
-                    && ImmichCtl::get_date_time_original(asset) >= *taken_before
+                    && ImmichCtl::get_date_time_original(asset, None) >= *taken_before
+                {
+                    retain = true;
+                }
+                if let Some(make) = &args.make
+                    && !Self::exif_field_matches(asset, |exif| &exif.make, make)
+                {
+                    retain = true;
+                }
+                if let Some(model) = &args.model
+                    && !Self::exif_field_matches(asset, |exif| &exif.model, model)
+                {
+                    retain = true;
+                }
+                if let Some(filename) = &args.filename
+                    && !asset
+                        .original_file_name
+                        .to_lowercase()
+                        .contains(&filename.to_lowercase())
+                {
+                    retain = true;
+                }
+                if let Some(checksum) = &args.checksum
+                    && asset.checksum != *checksum
                 {
                     retain = true;
                 }
@@ -214,70 +1336,396 @@ impl ImmichCtl {
                         retain = true;
                     }
                 }
+                if !self.matches_local_filters(asset, args) {
+                    retain = true;
+                }
 
                 retain
             });
         }
 
+        let removed = old_len.saturating_sub(assets.len());
+        if self.dry_run {
+            eprintln!("Dry run: would remove {} asset(s) from selection.", removed);
+            return Ok(());
+        }
         assets.save()?;
-        let new_len = assets.len();
-        eprintln!(
-            "Removed {} asset(s) from selection.",
-            old_len.saturating_sub(new_len)
-        );
+        eprintln!("Removed {} asset(s) from selection.", removed);
         Ok(())
     }
 
+    /// Whether the asset matches the filters that the server search doesn't support
+    /// (`--orientation`, `--extension`, `--live-only`, `--no-live`) and `--type`, which the
+    /// server search supports but shared links and the local removal don't.
+    fn matches_local_filters(&self, asset: &AssetResponseDto, args: &AssetSearchArgs) -> bool {
+        args.asset_type
+            .is_none_or(|t| asset.type_ == AssetTypeEnum::from(t))
+            && Self::matches_extension(asset, &args.extension)
+            && Self::matches_live(asset, args.live_only, args.no_live)
+            && self.matches_orientation(asset, args.orientation)
+    }
+
+    /// Whether the original file name ends with one of the `extensions` (with or without
+    /// leading dot, case-insensitive), always true without extensions.
+    fn matches_extension(asset: &AssetResponseDto, extensions: &[String]) -> bool {
+        if extensions.is_empty() {
+            return true;
+        }
+        let file_name = asset.original_file_name.to_lowercase();
+        extensions.iter().any(|ext| {
+            let ext = ext.trim().trim_start_matches('.').to_lowercase();
+            !ext.is_empty() && file_name.ends_with(&format!(".{}", ext))
+        })
+    }
+
+    /// Whether the asset is a live photo (has a paired video) as requested by `live_only`
+    /// or not as requested by `no_live`, always true without both.
+    fn matches_live(asset: &AssetResponseDto, live_only: bool, no_live: bool) -> bool {
+        let live = asset.live_photo_video_id.is_some();
+        (!live_only || live) && (!no_live || !live)
+    }
+
+    /// Whether the asset has the given `orientation`, always true without `orientation`.
+    /// Assets without dimensions never match, `--verbose` prints a note for them.
+    fn matches_orientation(
+        &self,
+        asset: &AssetResponseDto,
+        orientation: Option<Orientation>,
+    ) -> bool {
+        let Some(orientation) = orientation else {
+            return true;
+        };
+        match Self::asset_orientation(asset) {
+            Some(o) => o == orientation,
+            None => {
+                if self.verbose {
+                    self.eprint_asset_warning(
+                        &format!(
+                            "Skipped asset '{}' ({}) without dimensions for --orientation.",
+                            asset.id, asset.original_file_name
+                        ),
+                        &asset.id,
+                    );
+                }
+                false
+            }
+        }
+    }
+
+    /// Orientation by asset width and height, falls back to the EXIF image dimensions
+    /// (swapped if the EXIF orientation rotates the image by 90°).
+    fn asset_orientation(asset: &AssetResponseDto) -> Option<Orientation> {
+        let (width, height) = match (asset.width, asset.height) {
+            (Some(width), Some(height)) => (width, height),
+            _ => {
+                let exif = asset.exif_info.as_ref()?;
+                let (width, height) = (exif.exif_image_width?, exif.exif_image_height?);
+                match exif.orientation.as_deref() {
+                    Some("5" | "6" | "7" | "8") => (height, width),
+                    _ => (width, height),
+                }
+            }
+        };
+        if width <= 0 || height <= 0 {
+            return None;
+        }
+        Some(match width.cmp(&height) {
+            std::cmp::Ordering::Greater => Orientation::Landscape,
+            std::cmp::Ordering::Less => Orientation::Portrait,
+            std::cmp::Ordering::Equal => Orientation::Square,
+        })
+    }
+
+    /// Case insensitive comparison of an EXIF field, assets without EXIF data never match.
+    fn exif_field_matches(
+        asset: &AssetResponseDto,
+        field: impl Fn(&ExifResponseDto) -> &Option<String>,
+        expected: &str,
+    ) -> bool {
+        asset
+            .exif_info
+            .as_ref()
+            .and_then(|exif| field(exif).as_deref())
+            .is_some_and(|value| value.eq_ignore_ascii_case(expected))
+    }
+
     async fn assets_search_remove_by_immich_query(
         &mut self,
         search_dto: MetadataSearchDto,
+        ids: &[Uuid],
+        args: &AssetSearchArgs,
         assets: &mut Assets,
     ) -> Result<()> {
-        for asset in self.search_pages(search_dto).await? {
-            assets.remove_asset(&asset.id);
+        for asset in self.search_pages_any_tag(search_dto, ids).await? {
+            if self.matches_local_filters(&asset, args) {
+                assets.remove_asset(&asset.id);
+            }
         }
         Ok(())
     }
 
-    async fn search_pages(
-        &mut self,
-        mut search_dto: MetadataSearchDto,
+    pub async fn search_pages(
+        &self,
+        search_dto: MetadataSearchDto,
     ) -> Result<Vec<super::types::AssetResponseDto>> {
         let mut results = Vec::new();
-        let mut page = std::num::NonZeroU64::new(1).unwrap();
-        loop {
+        // collects all pages, never stops early
+        let _ = self
+            .search_pages_with(search_dto, &mut |asset| {
+                results.push(asset);
+                ControlFlow::Continue(())
+            })
+            .await?;
+        Ok(results)
+    }
+
+    /// Fetches the search result page by page and passes each asset to `visit`, which can
+    /// stop paging early by returning `ControlFlow::Break`. Returns whether it did.
+    pub async fn search_pages_with(
+        &self,
+        mut search_dto: MetadataSearchDto,
+        visit: &mut impl FnMut(AssetResponseDto) -> ControlFlow<()>,
+    ) -> Result<ControlFlow<()>> {
+        let start = Instant::now();
+        let mut page = search_dto.page.unwrap_or(NonZeroU64::MIN);
+        let mut pages = 0;
+        let flow = loop {
+            pages += 1;
             search_dto.page = Some(page);
-            let mut resp = self
+            let resp = self
                 .immich()?
                 .search_assets(None, None, &search_dto)
-                .await
-                .context("Search failed")?;
+                .api_context("Search failed")
+                .await?
+                .into_inner();
+            if resp
+                .assets
+                .items
+                .into_iter()
+                .try_for_each(&mut *visit)
+                .is_break()
+            {
+                break ControlFlow::Break(());
+            }
+            let Some(next_page) = Self::parse_next_page(resp.assets.next_page.as_deref())? else {
+                break ControlFlow::Continue(());
+            };
+            page = next_page;
+        };
+        self.eprint_timing(&format!("search, {} pages", pages), start);
+        Ok(flow)
+    }
+
+    /// Like `search_pages` but assets need to have any (instead of all) of the `tag_ids`
+    /// and may be any of `ids`. Runs one search per tag and id, assets found by several
+    /// searches are returned once.
+    async fn search_pages_any_tag(
+        &self,
+        search_dto: MetadataSearchDto,
+        ids: &[Uuid],
+    ) -> Result<Vec<AssetResponseDto>> {
+        let mut results = Vec::new();
+        // collects all pages, never stops early
+        let _ = self
+            .search_pages_any_tag_with(search_dto, ids, &mut |asset| {
+                results.push(asset);
+                ControlFlow::Continue(())
+            })
+            .await?;
+        Ok(results)
+    }
+
+    /// Like `search_pages_with` for the searches of `search_pages_any_tag`, each asset is
+    /// visited once.
+    async fn search_pages_any_tag_with(
+        &self,
+        search_dto: MetadataSearchDto,
+        ids: &[Uuid],
+        visit: &mut impl FnMut(AssetResponseDto) -> ControlFlow<()>,
+    ) -> Result<ControlFlow<()>> {
+        let mut seen = std::collections::HashSet::new();
+        for search_dto in Self::split_search(search_dto, ids) {
+            let flow = self
+                .search_pages_with(search_dto, &mut |asset| {
+                    if seen.insert(asset.id) {
+                        visit(asset)
+                    } else {
+                        ControlFlow::Continue(())
+                    }
+                })
+                .await?;
+            if flow.is_break() {
+                return Ok(flow);
+            }
+        }
+        Ok(ControlFlow::Continue(()))
+    }
+
+    /// Smart search for the assets most similar to the `reference` asset, most similar first.
+    /// Other search flags (except `--id` and `--filename`) further restrict the result.
+    async fn search_similar(
+        &mut self,
+        reference: &str,
+        args: &AssetSearchArgs,
+    ) -> Result<Vec<AssetResponseDto>> {
+        let reference =
+            uuid::Uuid::parse_str(reference).context("Invalid asset id, expected uuid")?;
+        let limit = args.limit.unwrap_or(DEFAULT_SIMILAR_LIMIT).get();
+        let mut search_dto = SmartSearchDto {
+            query_asset_id: Some(reference),
+            is_favorite: args.favorite,
+            taken_after: args.taken_after.map(|t| t.with_timezone(&Utc)),
+            taken_before: args.taken_before.map(|t| t.with_timezone(&Utc)),
+            type_: args.asset_type.map(AssetTypeEnum::from),
+            city: args.city.clone(),
+            country: args.country.clone(),
+            make: args.make.clone(),
+            model: args.model.clone(),
+            visibility: Some(AssetVisibility::Timeline),
+            with_deleted: Some(args.include_trashed),
+            with_exif: Some(true),
+            size: args.page_size.or(NonZeroU64::new(limit.min(100))),
+            ..Default::default()
+        };
+        if let Some(tag_name) = &args.tag {
+            search_dto.tag_ids = Some(vec![self.find_tag_by_name(tag_name).await?]);
+        }
+        if let Some(album_name) = &args.album {
+            search_dto.album_ids = vec![self.find_album_by_name(album_name).await?];
+        }
+        if let Some(library) = &args.library {
+            search_dto.library_id = Some(self.find_library(library).await?);
+        }
+
+        let mut results = Vec::new();
+        let mut page = args.start_page.unwrap_or(NonZeroU64::MIN);
+        while (results.len() as u64) < limit {
+            search_dto.page = Some(page);
+            let mut resp = self
+                .immich()?
+                .search_smart(&search_dto)
+                .api_context("Similarity search failed, is smart search enabled on the server?")
+                .await?;
             results.append(&mut resp.assets.items);
-            let Some(next_page) = &resp.assets.next_page else {
+            let Some(next_page) = Self::parse_next_page(resp.assets.next_page.as_deref())? else {
                 break;
             };
-            let n = next_page
-                .parse::<u64>()
-                .context("Invalid next_page value")?;
-            page = std::num::NonZeroU64::new(n)
-                .ok_or_else(|| anyhow::anyhow!("Invalid next_page value: 0"))?;
+            page = next_page;
         }
+        results.truncate(limit as usize);
         Ok(results)
     }
 
-    async fn build_search_dto(&self, args: &AssetSearchArgs) -> Result<MetadataSearchDto> {
-        let mut search_dto = MetadataSearchDto::default();
-        if let Some(id) = &args.id {
-            let uuid = uuid::Uuid::parse_str(id).context("Invalid asset id, expected uuid")?;
-            search_dto.id = Some(uuid);
+    /// One search per id if several `ids` are given, each split by tag.
+    fn split_search(search_dto: MetadataSearchDto, ids: &[Uuid]) -> Vec<MetadataSearchDto> {
+        let by_id = if ids.len() > 1 {
+            ids.iter()
+                .map(|id| MetadataSearchDto {
+                    id: Some(*id),
+                    ..search_dto.clone()
+                })
+                .collect()
+        } else {
+            vec![search_dto]
+        };
+        by_id.into_iter().flat_map(Self::split_by_tag).collect()
+    }
+
+    /// Asset ids of `--id`, empty if not given.
+    fn search_ids(args: &AssetSearchArgs) -> Result<Vec<Uuid>> {
+        match &args.id {
+            Some(id) => parse_ids(id),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// One search per tag id, as a search with several tag ids finds only assets having all tags.
+    fn split_by_tag(search_dto: MetadataSearchDto) -> Vec<MetadataSearchDto> {
+        match &search_dto.tag_ids {
+            Some(tag_ids) if tag_ids.len() > 1 => tag_ids
+                .iter()
+                .map(|tag_id| MetadataSearchDto {
+                    tag_ids: Some(vec![*tag_id]),
+                    ..search_dto.clone()
+                })
+                .collect(),
+            _ => vec![search_dto],
+        }
+    }
+
+    /// Print the unparsed search response of each page, one json document per line.
+    async fn search_print_raw(&self, mut search_dto: MetadataSearchDto) -> Result<()> {
+        let immich = self.immich()?;
+        let url = format!("{}/search/metadata", immich.baseurl);
+        let mut page = search_dto.page.unwrap_or(NonZeroU64::MIN);
+        loop {
+            search_dto.page = Some(page);
+            let resp = immich
+                .client
+                .post(&url)
+                .header(reqwest::header::ACCEPT, "application/json")
+                .header(
+                    "api-version",
+                    <super::Client as ClientInfo<()>>::api_version(),
+                )
+                .json(&search_dto)
+                .send()
+                .await
+                .context("Search failed")?;
+            let body = api_error::error_for_status(resp, "Search failed")
+                .await?
+                .text()
+                .await
+                .context("Search failed")?;
+            println!("{}", body.trim_end());
+            let resp: serde_json::Value =
+                serde_json::from_str(&body).context("Search failed, invalid response")?;
+            let Some(next_page) = Self::parse_next_page(resp["assets"]["nextPage"].as_str())?
+            else {
+                break;
+            };
+            page = next_page;
+        }
+        Ok(())
+    }
+
+    /// Parses the `nextPage` of a search response. A missing, empty, null-ish or zero
+    /// value means that there are no more pages.
+    fn parse_next_page(next_page: Option<&str>) -> Result<Option<NonZeroU64>> {
+        let next_page = next_page.map(str::trim).unwrap_or_default();
+        if next_page.is_empty() || next_page.eq_ignore_ascii_case("null") {
+            return Ok(None);
         }
+        let n = next_page
+            .parse::<u64>()
+            .with_context(|| format!("Invalid next_page value: '{}'", next_page))?;
+        Ok(NonZeroU64::new(n))
+    }
+
+    async fn build_search_dto(&self, args: &AssetSearchArgs) -> Result<MetadataSearchDto> {
+        // several ids are searched one by one, see `split_search`
+        let mut search_dto = MetadataSearchDto {
+            id: Self::search_ids(args)?.first().copied(),
+            ..Default::default()
+        };
         if let Some(tag_name) = &args.tag {
-            search_dto.tag_ids = Some(vec![self.find_tag_by_name(tag_name).await?]);
+            let tag_match = if args.tag_glob {
+                TagMatch::Glob
+            } else if args.tag_regex {
+                TagMatch::Regex
+            } else {
+                TagMatch::Exact
+            };
+            let tags = self.find_tags(tag_name, tag_match).await?;
+            search_dto.tag_ids = Some(tags.into_iter().map(|t| t.id).collect());
         }
         if let Some(album_name) = &args.album {
             let album_id = self.find_album_by_name(album_name).await?;
             search_dto.album_ids.push(album_id);
         }
+        if let Some(library) = &args.library {
+            search_dto.library_id = Some(self.find_library(library).await?);
+        }
         if let Some(favorite) = args.favorite {
             search_dto.is_favorite = Some(favorite);
         }
@@ -287,12 +1735,23 @@ impl ImmichCtl {
         if let Some(taken_before) = args.taken_before {
             search_dto.taken_before = Some(taken_before.with_timezone(&Utc));
         }
-        // check that at least one search flag is provided
-        if search_dto == MetadataSearchDto::default() {
+        search_dto.type_ = args.asset_type.map(AssetTypeEnum::from);
+        search_dto.city = args.city.clone();
+        search_dto.country = args.country.clone();
+        search_dto.make = args.make.clone();
+        search_dto.model = args.model.clone();
+        search_dto.original_file_name = args.filename.clone();
+        search_dto.checksum = args.checksum.clone();
+        // check that at least one search flag is provided unless all assets are requested
+        if search_dto == MetadataSearchDto::default() && !args.all {
             bail!("Please provide at least one search flag.");
         }
         // hardcoded extra args
         search_dto.visibility = Some(AssetVisibility::Timeline);
+        search_dto.with_deleted = Some(args.include_trashed);
+        search_dto.order = args.order.map(AssetOrder::from);
+        search_dto.page = args.start_page;
+        search_dto.size = args.page_size;
         Ok(search_dto)
     }
 
@@ -300,49 +1759,176 @@ impl ImmichCtl {
         &mut self,
         offset: &TimeDelta,
         timezone: &Option<FixedOffset>,
-        dry_run: bool,
     ) -> Result<()> {
-        let mut assets = Assets::load(&self.assets_file);
+        let mut assets = self.load_assets();
+        let total = assets.len();
+        if total == 0 {
+            eprintln!("Selection is empty, nothing to adjust.");
+            return Ok(());
+        }
+        self.warn_implausible_timezones(assets.iter_assets());
+        let mut updates = Vec::with_capacity(total);
+        for asset in assets.iter_assets() {
+            let (old_date_time_original, new_date_time_original) =
+                Self::adjust_date_time_original(asset, offset, timezone, self.assume_tz);
+            if self.dry_run {
+                println!(
+                    "{}: {} -> {}",
+                    asset.original_file_name, old_date_time_original, new_date_time_original
+                );
+                continue;
+            }
+            updates.push((asset.id, new_date_time_original));
+        }
+        if self.dry_run {
+            return Ok(());
+        }
+
+        self.update_date_time_originals(&mut assets, &updates)
+            .await?;
+        eprintln!("Updated date/time for {} assets.", total);
+        self.record_history(&Self::datetime_history_command(offset, timezone), total);
+        let start = Instant::now();
+        assets.save()?;
+        self.eprint_timing("save", start);
+        Ok(())
+    }
+
+    /// Set the timezone of the selected assets. By default the wall clock reading is kept
+    /// (photos shot at 14:00 local time stay at 14:00, the instant changes), with
+    /// `convert_instant` the instant is kept and the clock reading is converted.
+    pub async fn assets_set_timezone(
+        &mut self,
+        timezone: TimeZoneValue,
+        convert_instant: bool,
+    ) -> Result<()> {
+        let mut assets = self.load_assets();
         let total = assets.len();
-        for (i, asset) in assets.iter_mut_assets().enumerate() {
+        if total == 0 {
+            eprintln!("Selection is empty, nothing to adjust.");
+            return Ok(());
+        }
+        self.warn_implausible_timezones(assets.iter_assets());
+        let mut updates = Vec::with_capacity(total);
+        for asset in assets.iter_assets() {
             let (old_date_time_original, new_date_time_original) =
-                Self::adjust_date_time_original(asset, offset, timezone);
-            if dry_run {
+                Self::set_timezone_date_time_original(
+                    asset,
+                    timezone,
+                    convert_instant,
+                    self.assume_tz,
+                );
+            if self.dry_run {
                 println!(
                     "{}: {} -> {}",
                     asset.original_file_name, old_date_time_original, new_date_time_original
                 );
                 continue;
             }
+            updates.push((asset.id, new_date_time_original));
+        }
+        if self.dry_run {
+            return Ok(());
+        }
 
-            let asset_res = self
-                .immich()?
-                .update_asset(
-                    &asset.id,
-                    &UpdateAssetDto {
-                        date_time_original: Some(new_date_time_original.to_rfc3339()),
-                        ..Default::default()
-                    },
-                )
-                .await
-                .with_context(|| format!("Could not update asset '{}'", asset.id))?;
+        self.update_date_time_originals(&mut assets, &updates)
+            .await?;
+        eprintln!("Updated timezone for {} assets.", total);
+        let mut command = format!("assets set-timezone {}", timezone);
+        if convert_instant {
+            command.push_str(" --convert-instant");
+        }
+        self.record_history(&command, total);
+        let start = Instant::now();
+        assets.save()?;
+        self.eprint_timing("save", start);
+        Ok(())
+    }
+
+    /// Write the new dateTimeOriginal of each asset of `updates` and put the updated assets
+    /// into `assets`.
+    async fn update_date_time_originals(
+        &self,
+        assets: &mut Assets,
+        updates: &[(Uuid, DateTime<FixedOffset>)],
+    ) -> Result<()> {
+        let total = updates.len();
+        let start = Instant::now();
+        let client = self.immich()?;
+        let mut responses = futures::stream::iter(updates)
+            .map(|(id, new_date_time_original)| async move {
+                let dto = UpdateAssetDto {
+                    date_time_original: Some(new_date_time_original.to_rfc3339()),
+                    ..Default::default()
+                };
+                let res = client
+                    .update_asset(id, &dto)
+                    .with_api_context(|| format!("Could not update asset '{}'", id))
+                    .await;
+                (new_date_time_original, res)
+            })
+            .buffer_unordered(self.write_concurrency.get());
+        let mut i = 0;
+        while let Some((new_date_time_original, asset_res)) = responses.next().await {
             // !!! response: file_created_at and local_date_time are not updated, only exif data is updated !!!
-            *asset = asset_res.into_inner();
+            let mut updated = asset_res?.into_inner();
+            let asset = &mut updated;
+            if !Self::has_date_time_original(asset, new_date_time_original) {
+                // without the new exif date/time, later timezone computations would use the
+                // stale timeline fields of the response
+                self.eprint_asset_warning(
+                    &format!(
+                        "Server response for asset '{}' doesn't contain the new date/time {}, caching the intended value.",
+                        asset.id, new_date_time_original
+                    ),
+                    &asset.id,
+                );
+                Self::set_exif_date_time_original(asset, new_date_time_original);
+            }
+            assets.add_asset(updated);
             self.eprint_progress_indicator(i, total, 50);
+            i += 1;
         }
-        if !dry_run {
-            eprintln!("Updated date/time for {} assets.", total);
-            assets.save()?;
-        }
+        self.eprint_timing(&format!("update of {} assets", total), start);
         Ok(())
     }
 
+    /// Whether the EXIF date/time of `asset` is `expected`, including the timezone.
+    fn has_date_time_original(
+        asset: &AssetResponseDto,
+        expected: &chrono::DateTime<FixedOffset>,
+    ) -> bool {
+        Self::get_exif_date_time_original(asset)
+            .is_some_and(|dt| dt == *expected && dt.offset() == expected.offset())
+    }
+
+    fn set_exif_date_time_original(
+        asset: &mut AssetResponseDto,
+        date_time_original: &chrono::DateTime<FixedOffset>,
+    ) {
+        let exif_info = asset.exif_info.get_or_insert_with(Default::default);
+        exif_info.date_time_original = Some(date_time_original.with_timezone(&Utc));
+        exif_info.time_zone = Some(format!("UTC{}", date_time_original.offset()));
+    }
+
+    fn datetime_history_command(offset: &TimeDelta, timezone: &Option<FixedOffset>) -> String {
+        let mut command = "assets datetime".to_string();
+        if !offset.is_zero() {
+            command.push_str(&format!(" --offset {}", offset));
+        }
+        if let Some(tz) = timezone {
+            command.push_str(&format!(" --timezone {}", tz));
+        }
+        command
+    }
+
     fn adjust_date_time_original(
         asset: &AssetResponseDto,
         offset: &TimeDelta,
         new_timezone: &Option<FixedOffset>,
+        assume_tz: Option<TimeZoneValue>,
     ) -> (chrono::DateTime<FixedOffset>, chrono::DateTime<FixedOffset>) {
-        let date_time_original = Self::get_date_time_original(asset);
+        let date_time_original = Self::get_date_time_original(asset, assume_tz);
 
         let asset_tz = date_time_original.timezone();
         let tz = if let Some(tz) = new_timezone {
@@ -356,11 +1942,45 @@ impl ImmichCtl {
         (date_time_original, new_date_time_original.with_timezone(tz))
     }
 
-    fn get_date_time_original(asset: &AssetResponseDto) -> chrono::DateTime<FixedOffset> {
+    /// Old and new dateTimeOriginal for `assets set-timezone`. Keeps the wall clock reading
+    /// or, with `convert_instant`, the instant. The offset of an IANA `timezone` is the one
+    /// valid when the asset was taken.
+    fn set_timezone_date_time_original(
+        asset: &AssetResponseDto,
+        timezone: TimeZoneValue,
+        convert_instant: bool,
+        assume_tz: Option<TimeZoneValue>,
+    ) -> (DateTime<FixedOffset>, DateTime<FixedOffset>) {
+        let date_time_original = Self::get_date_time_original(asset, assume_tz);
+        if convert_instant {
+            let offset = timezone.offset_at(&date_time_original.to_utc());
+            return (
+                date_time_original,
+                date_time_original.with_timezone(&offset),
+            );
+        }
+        let wall_clock = date_time_original.naive_local();
+        // the offset at the wall clock reading taken as UTC is close enough to find the
+        // instant, the offset at that instant honors a daylight saving time switch
+        let approx_offset = timezone.offset_at(&wall_clock.and_utc());
+        let offset = timezone.offset_at(&(wall_clock - approx_offset).and_utc());
+        let new_date_time_original = wall_clock
+            .and_local_timezone(offset)
+            .single()
+            .unwrap_or(date_time_original);
+        (date_time_original, new_date_time_original)
+    }
+
+    /// DateTimeOriginal with timezone. Timezone precedence: EXIF > `assume_tz` > derived
+    /// from asset metadata.
+    pub fn get_date_time_original(
+        asset: &AssetResponseDto,
+        assume_tz: Option<TimeZoneValue>,
+    ) -> chrono::DateTime<FixedOffset> {
         if let Some(date_time_original) = Self::get_exif_date_time_original(asset) {
             return date_time_original;
         }
-        Self::get_assert_date_time_original(asset)
+        Self::get_assert_date_time_original(asset, assume_tz)
     }
 
     fn get_exif_date_time_original(
@@ -386,17 +2006,66 @@ impl ImmichCtl {
         None
     }
 
-    fn get_assert_date_time_original(asset: &AssetResponseDto) -> chrono::DateTime<FixedOffset> {
-        let tz = Self::asset_timezone_offset(asset);
+    fn get_assert_date_time_original(
+        asset: &AssetResponseDto,
+        assume_tz: Option<TimeZoneValue>,
+    ) -> chrono::DateTime<FixedOffset> {
+        // the assumed timezone only applies to assets without EXIF timezone
+        let tz = match assume_tz.filter(|_| Self::exif_timezone_offset(asset).is_none()) {
+            Some(tz) => tz.offset_at(&asset.file_created_at),
+            None => Self::asset_timezone_offset(asset),
+        };
         asset.file_created_at.with_timezone(&tz)
     }
 
+    /// Timezone derived from asset metadata, falls back to the EXIF timezone or UTC
+    /// if the derived timezone is implausible.
     fn asset_timezone_offset(asset: &AssetResponseDto) -> FixedOffset {
-        let delta = asset
+        Self::derived_timezone_offset(asset)
+            .or_else(|| Self::exif_timezone_offset(asset))
+            .unwrap_or_else(|| FixedOffset::east_opt(0).unwrap())
+    }
+
+    /// Timezone from `localDateTime - fileCreatedAt`. `None` for placeholder (epoch)
+    /// timestamps and for offsets outside -12:00..+14:00 or not in 15 minute steps.
+    fn derived_timezone_offset(asset: &AssetResponseDto) -> Option<FixedOffset> {
+        if asset.file_created_at == DateTime::UNIX_EPOCH
+            && asset.local_date_time == DateTime::UNIX_EPOCH
+        {
+            return None;
+        }
+        let delta_sec = asset
             .local_date_time
-            .signed_duration_since(asset.file_created_at);
-        let delta_sec = delta.num_seconds() as i32;
-        FixedOffset::east_opt(delta_sec).unwrap_or_else(|| FixedOffset::east_opt(0).unwrap())
+            .signed_duration_since(asset.file_created_at)
+            .num_seconds();
+        if !(-12 * 3600..=14 * 3600).contains(&delta_sec) || delta_sec % 900 != 0 {
+            return None;
+        }
+        FixedOffset::east_opt(delta_sec as i32)
+    }
+
+    /// With `--verbose`, warn about assets whose timezone can't be derived from metadata.
+    fn warn_implausible_timezones<'a>(&self, assets: impl Iterator<Item = &'a AssetResponseDto>) {
+        if !self.verbose {
+            return;
+        }
+        for asset in assets {
+            if Self::derived_timezone_offset(asset).is_some() {
+                continue;
+            }
+            let fallback = match (self.assume_tz, Self::exif_timezone_offset(asset)) {
+                (Some(tz), _) => format!("assumed timezone {}", tz),
+                (None, Some(tz)) => format!("EXIF timezone {}", tz),
+                (None, None) => "UTC".to_string(),
+            };
+            self.eprint_asset_warning(
+                &format!(
+                    "Implausible timezone in metadata of asset '{}' ({}), using {}.",
+                    asset.id, asset.original_file_name, fallback
+                ),
+                &asset.id,
+            );
+        }
     }
 
     fn parse_exif_timezone(tz_str: &str) -> Result<FixedOffset> {
@@ -463,13 +2132,15 @@ pub mod tests {
     use crate::immichctl::album_cmd::tests::create_album;
     use crate::immichctl::tag_cmd::tests::create_tag;
     use crate::immichctl::tests::create_immichctl_with_server;
-    use crate::immichctl::types::{AssetTypeEnum, AssetVisibility, ExifResponseDto};
+    use crate::immichctl::types::{
+        AssetTypeEnum, AssetVisibility, ExifResponseDto, UserAvatarColor, UserResponseDto,
+    };
 
     use super::*;
     use chrono::{DateTime, TimeZone, Utc};
     use uuid::Uuid;
 
-    fn create_asset_with_timestamps(
+    pub fn create_asset_with_timestamps(
         file_created_at: DateTime<Utc>,
         local_date_time: DateTime<Utc>,
     ) -> AssetResponseDto {
@@ -568,424 +2239,2577 @@ pub mod tests {
             .create_async()
             .await;
 
-        let result = ctl.assets_refresh().await;
+        let result = ctl.assets_refresh(false).await;
         assert!(result.is_err());
         let msg = result.err().unwrap().to_string();
         assert!(msg.contains(&format!("Could not retrieve asset '{}'", asset_id)));
     }
 
-    #[test]
-    fn test_asset_timezone_offset() {
-        // Case 1: Positive offset (+2 hours)
-        let file_created_at = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
-        let local_date_time = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
-        let asset = create_asset_with_timestamps(file_created_at, local_date_time);
-        assert_eq!(
-            ImmichCtl::asset_timezone_offset(&asset),
-            FixedOffset::east_opt(2 * 3600).unwrap()
-        );
+    #[tokio::test]
+    async fn test_assets_refresh_date_window() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
 
-        // Case 2: Negative offset (-3 hours)
-        let local_date_time = Utc.with_ymd_and_hms(2024, 1, 1, 7, 0, 0).unwrap();
-        let asset = create_asset_with_timestamps(file_created_at, local_date_time);
-        assert_eq!(
-            ImmichCtl::asset_timezone_offset(&asset),
-            FixedOffset::east_opt(-3 * 3600).unwrap()
-        );
+        let mut sel = Assets::load(&ctl.assets_file);
+        let assets: Vec<_> = (1..=3)
+            .map(|day| {
+                let ts = Utc.with_ymd_and_hms(2024, 1, day, 10, 0, 0).unwrap();
+                create_asset_with_timestamps(ts, ts)
+            })
+            .collect();
+        for asset in &assets {
+            sel.add_asset(asset.clone());
+        }
+        sel.save()?;
 
-        // Case 3: Zero offset (UTC)
-        let local_date_time = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let mut mocks = vec![];
+        for (asset, expected) in assets.iter().zip([0, 1, 0]) {
+            mocks.push(
+                server
+                    .mock("GET", format!("/api/assets/{}", asset.id).as_str())
+                    .with_status(200)
+                    .with_header("content-type", "application/json")
+                    .with_body(serde_json::to_string(asset)?)
+                    .expect(expected)
+                    .create_async()
+                    .await,
+            );
+        }
+
+        let since = Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap();
+        let until = Utc.with_ymd_and_hms(2024, 1, 3, 0, 0, 0).unwrap();
+        ctl.set_date_window(Some(since.fixed_offset()), Some(until.fixed_offset()));
+        ctl.assets_refresh(false).await?;
+
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+        let sel = Assets::load(&ctl.assets_file);
+        let filtered: Vec<_> = ctl.filter_assets(&sel, &[]).map(|a| a.id).collect();
+        assert_eq!(filtered, vec![assets[1].id]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_refresh_server() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+        ctl.set_batch_size(std::num::NonZeroUsize::new(2).unwrap());
+
+        let mut sel = Assets::load(&ctl.assets_file);
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        for _ in 0..3 {
+            sel.add_asset(create_asset_with_timestamps(ts, ts));
+        }
+        sel.save()?;
+
+        let mock = server
+            .mock("POST", "/api/assets/jobs")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"name": "refresh-metadata"}),
+            ))
+            .with_status(204)
+            .expect(2)
+            .create_async()
+            .await;
+
+        assert_eq!(ctl.assets_refresh_server().await?, 3);
+        mock.assert_async().await;
+
+        ctl.set_dry_run(true);
+        assert_eq!(ctl.assets_refresh_server().await?, 0);
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_refresh_resumes_after_interruption() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let ids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        let mut sel = Assets::load(&ctl.assets_file);
+        for id in &ids {
+            sel.add_asset(create_asset_for_download(*id, "old.jpg", "old.jpg"));
+        }
+        sel.save()?;
+
+        let asset_body = |id: &Uuid| {
+            serde_json::to_string(&create_asset_for_download(*id, "new.jpg", "new.jpg")).unwrap()
+        };
+        // the first two assets must be retrieved exactly once over both runs
+        let mut ok_mocks = vec![];
+        for id in &ids[..2] {
+            let m = server
+                .mock("GET", format!("/api/assets/{}", id).as_str())
+                .with_status(200)
+                .with_header("content-type", "application/json")
+                .with_body(asset_body(id))
+                .expect(1)
+                .create_async()
+                .await;
+            ok_mocks.push(m);
+        }
+        let failing_path = format!("/api/assets/{}", ids[2]);
+        let m_fail = server
+            .mock("GET", failing_path.as_str())
+            .with_status(500)
+            .create_async()
+            .await;
+
+        // first run is interrupted by the failing asset, sequential retrieval so that
+        // no asset after it is retrieved
+        ctl.set_read_concurrency(std::num::NonZeroUsize::MIN);
+        let result = ctl.assets_refresh_with_checkpoint(false, 1).await;
+        assert!(result.is_err());
+        m_fail.remove_async().await;
+
+        // an asset added to the interrupted selection is refreshed on resume
+        let added_id = Uuid::new_v4();
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(create_asset_for_download(added_id, "old.jpg", "old.jpg"));
+        sel.save()?;
+        let m_added = server
+            .mock("GET", format!("/api/assets/{}", added_id).as_str())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(asset_body(&added_id))
+            .expect(1)
+            .create_async()
+            .await;
+
+        // resume refreshes only the remaining assets
+        let m_ok = server
+            .mock("GET", failing_path.as_str())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(asset_body(&ids[2]))
+            .expect(1)
+            .create_async()
+            .await;
+        ctl.assets_refresh_with_checkpoint(false, 1).await?;
+
+        for m in ok_mocks {
+            m.assert_async().await;
+        }
+        m_ok.assert_async().await;
+        m_added.assert_async().await;
+        let mut sel = Assets::load(&ctl.assets_file);
+        assert!(
+            sel.iter_assets()
+                .all(|asset| asset.original_file_name == "new.jpg")
+        );
+        // refresh is complete, next run starts from scratch
+        assert_eq!(sel.refresh_pending().len(), 4);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_refresh_continue_on_error() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let ok_id = Uuid::new_v4();
+        let failing_id = Uuid::new_v4();
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(create_asset_for_download(ok_id, "old.jpg", "old.jpg"));
+        sel.add_asset(create_asset_for_download(failing_id, "old.jpg", "old.jpg"));
+        sel.save()?;
+
+        let _m_ok = server
+            .mock("GET", format!("/api/assets/{}", ok_id).as_str())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::to_string(&create_asset_for_download(ok_id, "new.jpg", "new.jpg"))
+                    .unwrap(),
+            )
+            .create_async()
+            .await;
+        let _m_fail = server
+            .mock("GET", format!("/api/assets/{}", failing_id).as_str())
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let result = ctl.assets_refresh(true).await;
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Could not refresh 1 assets")
+        );
+        let mut sel = Assets::load(&ctl.assets_file);
+        let ok_asset = sel.iter_assets().find(|a| a.id == ok_id).unwrap();
+        assert_eq!(ok_asset.original_file_name, "new.jpg");
+        // only the failed asset is left for the next run
+        assert_eq!(sel.refresh_pending(), vec![failing_id]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_clear_dry_run() -> Result<()> {
+        let config_dir = tempfile::tempdir().unwrap();
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
+        ctl.set_dry_run(true);
+
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(create_asset_for_download(Uuid::new_v4(), "a.jpg", "a.jpg"));
+        sel.save()?;
+
+        ctl.assets_clear(&ClearFilterArgs::default(), false).await?;
+
+        assert_eq!(Assets::load(&ctl.assets_file).len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_clear_requires_yes() -> Result<()> {
+        let config_dir = tempfile::tempdir().unwrap();
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
+        // an empty selection is cleared without --yes
+        ctl.assets_clear(&ClearFilterArgs::default(), false).await?;
+
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(create_asset_for_download(Uuid::new_v4(), "a.jpg", "a.jpg"));
+        sel.save()?;
+        let err = ctl
+            .assets_clear(&ClearFilterArgs::default(), false)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Clearing all 1 selected assets requires --yes.")
+        );
+        assert_eq!(Assets::load(&ctl.assets_file).len(), 1);
+
+        ctl.assets_clear(&ClearFilterArgs::default(), true).await?;
+        assert!(Assets::load(&ctl.assets_file).is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_clear_filtered() -> Result<()> {
+        let config_dir = tempfile::tempdir().unwrap();
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let mut old_favorite = create_asset_with_timestamps(ts, ts);
+        old_favorite.is_favorite = true;
+        let old = create_asset_with_timestamps(ts, ts);
+        let new_ts = Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 0).unwrap();
+        let mut new_favorite = create_asset_with_timestamps(new_ts, new_ts);
+        new_favorite.is_favorite = true;
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(old_favorite.clone());
+        sel.add_asset(old.clone());
+        sel.add_asset(new_favorite.clone());
+        sel.save()?;
+
+        // filters don't need --yes, only assets matching all filters are removed
+        let filter = ClearFilterArgs {
+            favorite: Some(true),
+            taken_before: Some("2024-06-01T00:00:00+00:00".parse()?),
+            ..Default::default()
+        };
+        ctl.assets_clear(&filter, false).await?;
+        let sel = Assets::load(&ctl.assets_file);
+        assert_eq!(sel.len(), 2);
+        assert!(!sel.contains(&old_favorite.id));
+        assert!(sel.contains(&old.id));
+        assert!(sel.contains(&new_favorite.id));
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_summary() {
+        assert_eq!(ImmichCtl::list_summary(0), "# 0 assets");
+        assert_eq!(ImmichCtl::list_summary(1), "# 1 asset");
+        assert_eq!(ImmichCtl::list_summary(42), "# 42 assets");
+    }
+
+    #[test]
+    fn test_assets_list_to_output_file() -> Result<()> {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+        let asset_id = Uuid::new_v4();
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(create_asset_for_download(asset_id, "a.jpg", "a.jpg"));
+        sel.save()?;
+
+        let csv_file = config_dir.path().join("assets.csv");
+        ctl.assets_list_csv(
+            &[AssetColumns::Id, AssetColumns::OriginalFileName],
+            &[],
+            None,
+            Some(&csv_file),
+        )?;
+        assert_eq!(
+            std::fs::read_to_string(&csv_file)?,
+            format!("{},a.jpg\n", asset_id)
+        );
+
+        let json_file = config_dir.path().join("assets-list.json");
+        ctl.assets_list_json(false, &[], &[], None, Some(&json_file))?;
+        let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&json_file)?)?;
+        assert_eq!(json[0]["id"], asset_id.to_string());
+
+        ctl.assets_list_json(
+            false,
+            &[
+                AssetColumns::Id,
+                AssetColumns::OriginalFileName,
+                AssetColumns::IsFavorite,
+                AssetColumns::Make,
+            ],
+            &[],
+            None,
+            Some(&json_file),
+        )?;
+        let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&json_file)?)?;
+        assert_eq!(
+            json,
+            serde_json::json!([{
+                "id": asset_id.to_string(),
+                "original-file-name": "a.jpg",
+                "is-favorite": false,
+                "make": null
+            }])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_asset_filter() {
+        let filter: AssetFilter = "make=SONY".parse().unwrap();
+        assert!(matches!(filter.column, AssetColumns::Make));
+        assert_eq!(filter.op, FilterOp::Eq);
+        assert_eq!(filter.value, "sony");
+        let filter: AssetFilter = "file!=a=b.jpg".parse().unwrap();
+        assert!(matches!(filter.column, AssetColumns::OriginalFileName));
+        assert_eq!(filter.op, FilterOp::Ne);
+        assert_eq!(filter.value, "a=b.jpg");
+        let filter: AssetFilter = "timezone~+02".parse().unwrap();
+        assert_eq!(filter.op, FilterOp::Contains);
+
+        assert_eq!(
+            "make".parse::<AssetFilter>().unwrap_err().to_string(),
+            "Invalid filter 'make', expected <column>=|!=|~<value>"
+        );
+        assert_eq!(
+            "make!SONY".parse::<AssetFilter>().unwrap_err().to_string(),
+            "Invalid filter 'make!SONY', expected <column>=|!=|~<value>"
+        );
+        assert_eq!(
+            "lens=50mm".parse::<AssetFilter>().unwrap_err().to_string(),
+            "Unknown column 'lens' in filter 'lens=50mm'"
+        );
+    }
+
+    #[test]
+    fn test_assets_list_with_filters() -> Result<()> {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let create_asset = |file: &str, make: &str| {
+            let mut asset = create_asset_with_timestamps(ts, ts);
+            asset.original_file_name = file.to_string();
+            asset.exif_info = Some(ExifResponseDto {
+                make: Some(make.to_string()),
+                ..Default::default()
+            });
+            asset
+        };
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(create_asset("DSC_1.jpg", "SONY"));
+        sel.add_asset(create_asset("DSC_2.mp4", "SONY"));
+        sel.add_asset(create_asset("IMG_1.jpg", "Canon"));
+        sel.save()?;
+
+        let filters: Vec<AssetFilter> = vec!["make=sony".parse()?, "file~.JPG".parse()?];
+        let csv_file = config_dir.path().join("assets.csv");
+        ctl.assets_list_csv(
+            &[AssetColumns::OriginalFileName],
+            &filters,
+            None,
+            Some(&csv_file),
+        )?;
+        assert_eq!(std::fs::read_to_string(&csv_file)?, "DSC_1.jpg\n");
+
+        let filters: Vec<AssetFilter> = vec!["make!=SONY".parse()?];
+        ctl.assets_list_csv(
+            &[AssetColumns::OriginalFileName],
+            &filters,
+            None,
+            Some(&csv_file),
+        )?;
+        assert_eq!(std::fs::read_to_string(&csv_file)?, "IMG_1.jpg\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_assets_list_newest_oldest() -> Result<()> {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+        let mut sel = Assets::load(&ctl.assets_file);
+        for day in [3, 1, 5, 2, 4] {
+            let ts = Utc.with_ymd_and_hms(2024, 1, day, 10, 0, 0).unwrap();
+            let mut asset = create_asset_with_timestamps(ts, ts);
+            asset.original_file_name = format!("day{}.jpg", day);
+            sel.add_asset(asset);
+        }
+        sel.save()?;
+
+        let csv_file = config_dir.path().join("assets.csv");
+        let columns = [AssetColumns::OriginalFileName];
+        ctl.assets_list_csv(&columns, &[], Some(ListExtreme::Newest(2)), Some(&csv_file))?;
+        assert_eq!(std::fs::read_to_string(&csv_file)?, "day5.jpg\nday4.jpg\n");
+        ctl.assets_list_csv(&columns, &[], Some(ListExtreme::Oldest(3)), Some(&csv_file))?;
+        assert_eq!(
+            std::fs::read_to_string(&csv_file)?,
+            "day1.jpg\nday2.jpg\nday3.jpg\n"
+        );
+        ctl.assets_list_csv(
+            &columns,
+            &[],
+            Some(ListExtreme::Newest(10)),
+            Some(&csv_file),
+        )?;
+        assert_eq!(std::fs::read_to_string(&csv_file)?.lines().count(), 5);
+        ctl.assets_list_csv(&columns, &[], Some(ListExtreme::Oldest(0)), Some(&csv_file))?;
+        assert_eq!(std::fs::read_to_string(&csv_file)?, "");
+
+        let json_file = config_dir.path().join("assets-list.json");
+        ctl.assets_list_json(
+            false,
+            &columns,
+            &[],
+            Some(ListExtreme::Newest(1)),
+            Some(&json_file),
+        )?;
+        let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&json_file)?)?;
+        assert_eq!(
+            json,
+            serde_json::json!([{"original-file-name": "day5.jpg"}])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_assets_schema() -> Result<()> {
+        let schema = serde_json::to_value(schemars::schema_for!(Assets))?;
+        let properties = &schema["properties"];
+        assert!(properties["assets"].is_object());
+        assert!(properties["refresh_pending"].is_object());
+        assert!(properties.get("file").is_none());
+        assert!(schema["$defs"]["AssetResponseDto"]["properties"]["originalFileName"].is_object());
+        Ok(())
+    }
+
+    #[test]
+    fn test_review_assets() -> Result<()> {
+        let config_dir = tempfile::tempdir()?;
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+        let mut sel = Assets::load(&ctl.assets_file);
+        let mut assets: Vec<_> = (1..=4)
+            .map(|day| {
+                let ts = Utc.with_ymd_and_hms(2024, 1, day, 10, 0, 0).unwrap();
+                create_asset_with_timestamps(ts, ts)
+            })
+            .collect();
+        // the EXIF dateTimeOriginal is shown instead of fileCreatedAt
+        let exif_dt = Utc.with_ymd_and_hms(2024, 1, 4, 8, 0, 0).unwrap();
+        assets[3] = create_asset_with_exif(
+            assets[3].file_created_at,
+            assets[3].local_date_time,
+            Some(exif_dt),
+            Some("+02:00".to_string()),
+        );
+        for asset in &assets {
+            sel.add_asset(asset.clone());
+        }
+
+        // drop 1st, keep 2nd, drop 3rd, end of input at 4th
+        let mut input = std::io::Cursor::new("d\n\nD\n");
+        let mut output = Vec::new();
+        let removed = ctl.review_assets(&mut sel, &mut input, &mut output)?;
+
+        assert_eq!(removed, 2);
+        assert!(!sel.contains(&assets[0].id));
+        assert!(sel.contains(&assets[1].id));
+        assert!(!sel.contains(&assets[2].id));
+        assert!(sel.contains(&assets[3].id));
+        let output = String::from_utf8(output)?;
+        assert!(output.contains("[1/4] test.jpg  2024-01-01T10:00:00+00:00"));
+        assert!(output.contains("[4/4] test.jpg  2024-01-04T10:00:00+02:00"));
+
+        // quit at 1st of the remaining assets
+        let mut input = std::io::Cursor::new("q\nd\n");
+        let mut output = Vec::new();
+        assert_eq!(ctl.review_assets(&mut sel, &mut input, &mut output)?, 0);
+        assert_eq!(sel.len(), 2);
+        assert!(!String::from_utf8(output)?.contains("[2/2]"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_quoting() {
+        assert_eq!(CsvQuoting::Minimal.field("IMG_1.jpg"), "IMG_1.jpg");
+        assert_eq!(CsvQuoting::Minimal.field("a,b.jpg"), "\"a,b.jpg\"");
+        assert_eq!(
+            CsvQuoting::Minimal.field("say \"hi\""),
+            "\"say \"\"hi\"\"\""
+        );
+        assert_eq!(CsvQuoting::Always.field("IMG_1.jpg"), "\"IMG_1.jpg\"");
+        assert_eq!(CsvQuoting::Never.field("a,b.jpg"), "a,b.jpg");
+    }
+
+    #[tokio::test]
+    async fn test_write_assets_csv_quotes_file_names() -> Result<()> {
+        let (ctl, _server) = create_immichctl_with_server().await;
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let asset = |name: &str| {
+            let mut asset = create_asset_with_timestamps(ts, ts);
+            asset.original_file_name = name.to_string();
+            asset
+        };
+        let assets = [
+            asset("photo, edited.jpg"),
+            asset("say \"cheese\".jpg"),
+            asset("two\nlines.jpg"),
+            asset("plain.jpg"),
+        ];
+
+        let mut output = Vec::new();
+        let columns = [AssetColumns::OriginalFileName, AssetColumns::Id];
+        ctl.write_assets_csv(&mut output, assets.iter(), &columns)?;
+        assert_eq!(
+            String::from_utf8(output)?,
+            format!(
+                "\"photo, edited.jpg\",{}\n\"say \"\"cheese\"\".jpg\",{}\n\"two\nlines.jpg\",{}\nplain.jpg,{}\n",
+                assets[0].id, assets[1].id, assets[2].id, assets[3].id
+            )
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_assets_tsv() -> Result<()> {
+        let (mut ctl, _server) = create_immichctl_with_server().await;
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let mut asset = create_asset_with_timestamps(ts, ts);
+        asset.original_file_name = "photo, edited\t1.jpg".to_string();
+        ctl.set_list_header(true);
+
+        let mut output = Vec::new();
+        let columns = [AssetColumns::Id, AssetColumns::OriginalFileName];
+        ctl.write_assets_tsv(&mut output, [&asset].into_iter(), &columns)?;
+        assert_eq!(
+            String::from_utf8(output)?,
+            format!(
+                "id\toriginal-file-name\n{}\tphoto, edited 1.jpg\n",
+                asset.id
+            )
+        );
+        assert_eq!(tsv_field("a\r\nb"), "a  b");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_assets_csv_header() -> Result<()> {
+        let (mut ctl, _server) = create_immichctl_with_server().await;
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let mut asset = create_asset_with_timestamps(ts, ts);
+        asset.original_file_name = "IMG_1.jpg".to_string();
+        let write = |ctl: &ImmichCtl, columns: &[AssetColumns]| -> Result<String> {
+            let mut output = Vec::new();
+            ctl.write_assets_csv(&mut output, [&asset].into_iter(), columns)?;
+            Ok(String::from_utf8(output)?)
+        };
+
+        assert_eq!(write(&ctl, &[])?, "IMG_1.jpg\n");
+        ctl.set_list_header(true);
+        assert_eq!(write(&ctl, &[])?, "original-file-name\nIMG_1.jpg\n");
+        assert_eq!(
+            write(&ctl, &[AssetColumns::Id, AssetColumns::OriginalFileName])?,
+            format!("id,original-file-name\n{},IMG_1.jpg\n", asset.id)
+        );
+        ctl.set_csv_quoting(CsvQuoting::Always);
+        assert_eq!(write(&ctl, &[AssetColumns::Make])?, "\"make\"\n\"\"\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_recent_keeps_selection() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let asset = create_asset_with_timestamps(ts, ts);
+        let search_mock = server
+            .mock("POST", "/api/search/metadata")
+            .match_body(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::PartialJson(serde_json::json!({"order": "desc"})),
+                mockito::Matcher::Regex("\"createdAfter\"".to_string()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                    "assets": {"count": 1, "facets": [], "items": [asset], "nextPage": null, "total": 1}
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        ctl.assets_recent(7, NonZeroU64::new(10), ListFormat::Csv, &[AssetColumns::Id])
+            .await?;
+
+        search_mock.assert_async().await;
+        assert!(Assets::load(&ctl.assets_file).is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_recent_assets_by_upload_time() -> Result<()> {
+        let (ctl, mut server) = create_immichctl_with_server().await;
+
+        // taken order (server order) differs from upload order
+        let asset = |taken_day, uploaded_hour| {
+            let taken = Utc.with_ymd_and_hms(2024, 1, taken_day, 10, 0, 0).unwrap();
+            let mut asset = create_asset_with_timestamps(taken, taken);
+            asset.created_at = Utc
+                .with_ymd_and_hms(2024, 2, 1, uploaded_hour, 0, 0)
+                .unwrap();
+            asset
+        };
+        let newest_taken = asset(3, 8);
+        let middle = asset(2, 9);
+        let newest_uploaded = asset(1, 10);
+        let page = |items: &[&AssetResponseDto], next_page: Option<&str>| {
+            serde_json::json!({
+                "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                "assets": {"count": items.len(), "facets": [], "items": items, "nextPage": next_page, "total": 3}
+            })
+            .to_string()
+        };
+        let _page1_mock = server
+            .mock("POST", "/api/search/metadata")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"page": 1}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(page(&[&newest_taken, &middle], Some("2")))
+            .create_async()
+            .await;
+        let _page2_mock = server
+            .mock("POST", "/api/search/metadata")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"page": 2}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(page(&[&newest_uploaded], None))
+            .create_async()
+            .await;
+
+        let ids = |assets: Vec<AssetResponseDto>| assets.iter().map(|a| a.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(ctl.recent_assets(7, NonZeroU64::new(2)).await?),
+            vec![newest_uploaded.id, middle.id]
+        );
+        assert_eq!(
+            ids(ctl.recent_assets(7, None).await?),
+            vec![newest_uploaded.id, middle.id, newest_taken.id]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_empty_selection_needs_no_server() -> Result<()> {
+        // not logged in: any server request would fail
+        let config_dir = tempfile::tempdir().unwrap();
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
+
+        ctl.assets_refresh(false).await?;
+        ctl.assets_datetime_adjust(&TimeDelta::hours(1), &None)
+            .await?;
+        ctl.assets_list_json(false, &[], &[], None, None)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_datetime_adjust_incomplete_response() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let local = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        let asset = create_asset_with_timestamps(ts, local);
+        let mut assets = Assets::load(&ctl.assets_file);
+        assets.add_asset(asset.clone());
+        assets.save()?;
+
+        // response without exif data and with the old timeline fields
+        let update_mock = server
+            .mock("PUT", format!("/api/assets/{}", asset.id).as_str())
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "dateTimeOriginal": "2024-01-01T13:00:00+02:00"
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&asset)?)
+            .create_async()
+            .await;
+
+        ctl.assets_datetime_adjust(&TimeDelta::hours(1), &None)
+            .await?;
+        update_mock.assert_async().await;
+
+        let cached = Assets::load(&ctl.assets_file);
+        let cached = cached.iter_assets().next().unwrap();
+        let expected = FixedOffset::east_opt(2 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 13, 0, 0)
+            .unwrap();
+        assert!(ImmichCtl::has_date_time_original(cached, &expected));
+        assert_eq!(
+            ImmichCtl::get_date_time_original(cached, None).to_rfc3339(),
+            "2024-01-01T13:00:00+02:00"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_asset_timezone_offset() {
+        // Case 1: Positive offset (+2 hours)
+        let file_created_at = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let local_date_time = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        let asset = create_asset_with_timestamps(file_created_at, local_date_time);
+        assert_eq!(
+            ImmichCtl::asset_timezone_offset(&asset),
+            FixedOffset::east_opt(2 * 3600).unwrap()
+        );
+
+        // Case 2: Negative offset (-3 hours)
+        let local_date_time = Utc.with_ymd_and_hms(2024, 1, 1, 7, 0, 0).unwrap();
         let asset = create_asset_with_timestamps(file_created_at, local_date_time);
         assert_eq!(
-            ImmichCtl::asset_timezone_offset(&asset),
-            FixedOffset::east_opt(0).unwrap()
+            ImmichCtl::asset_timezone_offset(&asset),
+            FixedOffset::east_opt(-3 * 3600).unwrap()
+        );
+
+        // Case 3: Zero offset (UTC)
+        let local_date_time = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let asset = create_asset_with_timestamps(file_created_at, local_date_time);
+        assert_eq!(
+            ImmichCtl::asset_timezone_offset(&asset),
+            FixedOffset::east_opt(0).unwrap()
+        );
+
+        // Case 4: Out-of-range offset (> 24 hours), should default to UTC
+        let local_date_time = Utc.with_ymd_and_hms(2024, 1, 2, 12, 0, 0).unwrap(); // 26 hours difference
+        let asset = create_asset_with_timestamps(file_created_at, local_date_time);
+        assert_eq!(
+            ImmichCtl::asset_timezone_offset(&asset),
+            FixedOffset::east_opt(0).unwrap()
+        );
+
+        // Case 5: implausible offsets fall back to the EXIF timezone
+        let exif_tz = FixedOffset::east_opt(5 * 3600 + 1800).unwrap();
+        for (local_date_time, expected) in [
+            // +13h is plausible
+            (
+                Utc.with_ymd_and_hms(2024, 1, 1, 23, 0, 0).unwrap(),
+                FixedOffset::east_opt(13 * 3600).unwrap(),
+            ),
+            // +15h
+            (Utc.with_ymd_and_hms(2024, 1, 2, 1, 0, 0).unwrap(), exif_tz),
+            // +2:07
+            (Utc.with_ymd_and_hms(2024, 1, 1, 12, 7, 0).unwrap(), exif_tz),
+        ] {
+            let asset = create_asset_with_exif(
+                file_created_at,
+                local_date_time,
+                None,
+                Some("+05:30".to_string()),
+            );
+            assert_eq!(ImmichCtl::asset_timezone_offset(&asset), expected);
+        }
+
+        // Case 6: placeholder timestamps
+        let asset = create_asset_with_exif(
+            DateTime::UNIX_EPOCH,
+            DateTime::UNIX_EPOCH,
+            None,
+            Some("-03:00".to_string()),
+        );
+        assert_eq!(ImmichCtl::derived_timezone_offset(&asset), None);
+        assert_eq!(
+            ImmichCtl::asset_timezone_offset(&asset),
+            FixedOffset::west_opt(3 * 3600).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_asset_column() {
+        let file_created_at = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let local_date_time = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap(); // +2h offset
+        let asset = create_asset_with_timestamps(file_created_at, local_date_time);
+
+        // Test basic columns
+        assert_eq!(
+            ImmichCtl::asset_column(&asset, AssetColumns::Id, None),
+            asset.id.to_string()
+        );
+        assert_eq!(
+            ImmichCtl::asset_column(&asset, AssetColumns::OriginalFileName, None),
+            "test.jpg"
+        );
+        assert_eq!(
+            ImmichCtl::asset_column(&asset, AssetColumns::FileCreatedAt, None),
+            "2024-01-01T10:00:00+00:00"
+        );
+        assert_eq!(
+            ImmichCtl::asset_column(&asset, AssetColumns::Timezone, None),
+            "+02:00"
+        );
+        assert_eq!(
+            ImmichCtl::asset_column(&asset, AssetColumns::DateTimeOriginal, None),
+            "2024-01-01T12:00:00+02:00"
+        );
+
+        // Test flag columns
+        assert_eq!(
+            ImmichCtl::asset_column(&asset, AssetColumns::IsFavorite, None),
+            "false"
+        );
+        assert_eq!(
+            ImmichCtl::asset_column(&asset, AssetColumns::Visibility, None),
+            "timeline"
+        );
+        let mut flagged_asset = asset.clone();
+        flagged_asset.is_favorite = true;
+        flagged_asset.is_archived = true;
+        flagged_asset.is_trashed = true;
+        flagged_asset.visibility = AssetVisibility::Archive;
+        assert_eq!(
+            ImmichCtl::asset_column(&flagged_asset, AssetColumns::IsFavorite, None),
+            "true"
+        );
+        assert_eq!(
+            ImmichCtl::asset_column(&flagged_asset, AssetColumns::IsArchived, None),
+            "true"
+        );
+        assert_eq!(
+            ImmichCtl::asset_column(&flagged_asset, AssetColumns::IsTrashed, None),
+            "true"
+        );
+        assert_eq!(
+            ImmichCtl::asset_column(&flagged_asset, AssetColumns::Visibility, None),
+            "archive"
+        );
+
+        // Test owner and library columns, owner and library are not always provided
+        assert_eq!(
+            ImmichCtl::asset_column(&asset, AssetColumns::OwnerId, None),
+            asset.owner_id.to_string()
+        );
+        assert_eq!(
+            ImmichCtl::asset_column(&asset, AssetColumns::OwnerName, None),
+            ""
+        );
+        assert_eq!(
+            ImmichCtl::asset_column(&asset, AssetColumns::LibraryId, None),
+            ""
+        );
+        let library_id = Uuid::new_v4();
+        let mut owned_asset = asset.clone();
+        owned_asset.library_id = Some(library_id);
+        owned_asset.owner = Some(UserResponseDto {
+            avatar_color: UserAvatarColor::Primary,
+            email: "jane@example.com".to_string(),
+            id: asset.owner_id,
+            name: "Jane".to_string(),
+            profile_changed_at: file_created_at,
+            profile_image_path: String::new(),
+        });
+        assert_eq!(
+            ImmichCtl::asset_column(&owned_asset, AssetColumns::OwnerName, None),
+            "Jane"
+        );
+        assert_eq!(
+            ImmichCtl::asset_column(&owned_asset, AssetColumns::LibraryId, None),
+            library_id.to_string()
+        );
+
+        // Test EXIF columns with full data (with changed month to verify correctness)
+        let exif_dt = Utc.with_ymd_and_hms(2024, 2, 1, 10, 0, 0).unwrap();
+        let asset_with_exif = create_asset_with_exif(
+            file_created_at,
+            local_date_time,
+            Some(exif_dt),
+            Some("+02:00".to_string()),
+        );
+
+        assert_eq!(
+            ImmichCtl::asset_column(&asset_with_exif, AssetColumns::ExifTimezone, None),
+            "+02:00"
+        );
+        assert_eq!(
+            ImmichCtl::asset_column(&asset_with_exif, AssetColumns::ExifDateTimeOriginal, None),
+            "2024-02-01T12:00:00+02:00"
+        );
+
+        // Test EXIF columns with missing timezone in EXIF -> no exif datetime output
+        let asset_with_partial_exif =
+            create_asset_with_exif(file_created_at, local_date_time, Some(exif_dt), None);
+        assert_eq!(
+            ImmichCtl::asset_column(&asset_with_partial_exif, AssetColumns::ExifTimezone, None),
+            ""
+        );
+        assert_eq!(
+            ImmichCtl::asset_column(
+                &asset_with_partial_exif,
+                AssetColumns::ExifDateTimeOriginal,
+                None
+            ),
+            ""
+        );
+
+        // Test EXIF columns with no EXIF data at all
+        assert_eq!(
+            ImmichCtl::asset_column(&asset, AssetColumns::ExifTimezone, None),
+            ""
+        );
+        assert_eq!(
+            ImmichCtl::asset_column(&asset, AssetColumns::ExifDateTimeOriginal, None),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_parse_exif_timezone() {
+        assert_eq!(
+            ImmichCtl::parse_exif_timezone("+02:00").unwrap(),
+            FixedOffset::east_opt(2 * 3600).unwrap()
+        );
+        assert_eq!(
+            ImmichCtl::parse_exif_timezone("UTC+2").unwrap(),
+            FixedOffset::east_opt(2 * 3600).unwrap()
+        );
+        for tz_str in &[
+            "UTC",
+            "UTC+0",
+            "UTC-0",
+            "UTC+00:00",
+            "+00:00",
+            "-00:00",
+            "+0",
+            "-0",
+        ] {
+            assert_eq!(
+                ImmichCtl::parse_exif_timezone(tz_str).unwrap(),
+                FixedOffset::east_opt(0).unwrap()
+            );
+        }
+        assert_eq!(
+            ImmichCtl::parse_exif_timezone("-0530").unwrap(),
+            FixedOffset::east_opt(-5 * 3600 - 30 * 60).unwrap()
+        );
+        assert_eq!(
+            ImmichCtl::parse_exif_timezone("+1").unwrap(),
+            FixedOffset::east_opt(3600).unwrap()
+        );
+        assert!(ImmichCtl::parse_exif_timezone("invalid").is_err());
+        assert!(ImmichCtl::parse_exif_timezone("").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_build_search_dto_no_flags() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+
+        let result = ctl.build_search_dto(&AssetSearchArgs::default()).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "Please provide at least one search flag."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_search_dto_with_id() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+
+        let args = AssetSearchArgs {
+            id: Some("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1".to_string()),
+            ..Default::default()
+        };
+        let mut result = ctl.build_search_dto(&args).await;
+        assert!(result.is_ok());
+        assert_eq!(
+            result.unwrap(),
+            MetadataSearchDto {
+                id: Some(Uuid::parse_str("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1").unwrap()),
+                visibility: Some(AssetVisibility::Timeline),
+                with_deleted: Some(false),
+                ..Default::default()
+            }
+        );
+
+        let args = AssetSearchArgs {
+            id: Some("no-uuid".to_string()),
+            ..Default::default()
+        };
+        result = ctl.build_search_dto(&args).await;
+        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "Invalid asset id 'no-uuid', expected uuid"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_assets_search_add_several_ids() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let mut mocks = vec![];
+        let mut ids = vec![];
+        for _ in 0..2 {
+            let asset = create_asset_with_timestamps(ts, ts);
+            let mock = server
+                .mock("POST", "/api/search/metadata")
+                .match_body(mockito::Matcher::PartialJson(
+                    serde_json::json!({"id": asset.id}),
+                ))
+                .with_status(200)
+                .with_header("content-type", "application/json")
+                .with_body(
+                    serde_json::json!({
+                        "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                        "assets": {"count": 1, "facets": [], "items": [asset], "nextPage": null, "total": 1}
+                    })
+                    .to_string(),
+                )
+                .expect(1)
+                .create_async()
+                .await;
+            mocks.push(mock);
+            ids.push(asset.id.to_string());
+        }
+
+        let args = AssetSearchArgs {
+            id: Some(format!("{},\n{}", ids[0], ids[1])),
+            ..Default::default()
+        };
+        ctl.assets_search_add(&args).await?;
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+        assert_eq!(Assets::load(&ctl.assets_file).len(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_search_replace() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let old = create_asset_with_timestamps(ts, ts);
+        let found = create_asset_with_timestamps(ts, ts);
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(old.clone());
+        sel.save()?;
+
+        let args = AssetSearchArgs {
+            favorite: Some(true),
+            replace: true,
+            ..Default::default()
+        };
+
+        // a failed search keeps the old selection
+        let failing_mock = server
+            .mock("POST", "/api/search/metadata")
+            .with_status(500)
+            .create_async()
+            .await;
+        assert!(ctl.assets_search_add(&args).await.is_err());
+        failing_mock.assert_async().await;
+        failing_mock.remove_async().await;
+        let sel = Assets::load(&ctl.assets_file);
+        assert_eq!(sel.len(), 1);
+        assert!(sel.contains(&old.id));
+
+        let mock = server
+            .mock("POST", "/api/search/metadata")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                    "assets": {"count": 1, "facets": [], "items": [found], "nextPage": null, "total": 1}
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        ctl.assets_search_add(&args).await?;
+        mock.assert_async().await;
+        let sel = Assets::load(&ctl.assets_file);
+        assert_eq!(sel.len(), 1);
+        assert!(sel.contains(&found.id));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_search_add_refreshes_existing() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let mut existing = create_asset_with_timestamps(ts, ts);
+        existing.original_file_name = "old.jpg".to_string();
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(existing.clone());
+        sel.save()?;
+        existing.original_file_name = "new.jpg".to_string();
+        let found = create_asset_with_timestamps(ts, ts);
+
+        let mock = server
+            .mock("POST", "/api/search/metadata")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                    "assets": {"count": 2, "facets": [], "items": [&existing, &found], "nextPage": null, "total": 2}
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let args = AssetSearchArgs {
+            favorite: Some(true),
+            ..Default::default()
+        };
+        ctl.assets_search_add(&args).await?;
+        mock.assert_async().await;
+
+        let sel = Assets::load(&ctl.assets_file);
+        assert_eq!(sel.len(), 2);
+        assert!(sel.contains(&found.id));
+        let refreshed = sel.iter_assets().find(|a| a.id == existing.id).unwrap();
+        assert_eq!(refreshed.original_file_name, "new.jpg");
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_assets_counts() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let existing = create_asset_with_timestamps(ts, ts);
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(existing.clone());
+
+        let found = vec![existing, create_asset_with_timestamps(ts, ts)];
+        let (added, refreshed) = ImmichCtl::add_assets(&mut sel, found.into_iter());
+        assert_eq!((added, refreshed), (1, 1));
+        assert_eq!(sel.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_assets_search_add_server_error() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+        let body = r#"{"message":"invalid tagId","error":"Bad Request","statusCode":400}"#;
+        let mock = server
+            .mock("POST", "/api/search/metadata")
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let args = AssetSearchArgs {
+            favorite: Some(true),
+            ..Default::default()
+        };
+        let err = ctl.assets_search_add(&args).await.unwrap_err();
+        mock.assert_async().await;
+        assert_eq!(err.to_string(), "Search failed (400): invalid tagId");
+        // --verbose prints the error chain including the full body
+        assert_eq!(
+            err.root_cause().to_string(),
+            format!("HTTP 400 Bad Request: {}", body)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_search_print_raw() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let asset = create_asset_with_timestamps(ts, ts);
+        let mut page_mocks = vec![];
+        for (page, next_page) in [(1, serde_json::json!("2")), (2, serde_json::Value::Null)] {
+            page_mocks.push(
+                server
+                    .mock("POST", "/api/search/metadata")
+                    .match_body(mockito::Matcher::PartialJson(
+                        serde_json::json!({"isFavorite": true, "page": page}),
+                    ))
+                    .with_status(200)
+                    .with_header("content-type", "application/json")
+                    .with_body(
+                        serde_json::json!({
+                            "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                            "assets": {"count": 1, "facets": [], "items": [&asset], "nextPage": next_page, "total": 1}
+                        })
+                        .to_string(),
+                    )
+                    .expect(1)
+                    .create_async()
+                    .await,
+            );
+        }
+
+        let args = AssetSearchArgs {
+            favorite: Some(true),
+            print_raw: true,
+            ..Default::default()
+        };
+        ctl.assets_search_add(&args).await?;
+
+        for mock in page_mocks {
+            mock.assert_async().await;
+        }
+        assert!(Assets::load(&ctl.assets_file).is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_search_print_raw_error() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+        let mock = server
+            .mock("POST", "/api/search/metadata")
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"message":"size must not be less than 1","statusCode":400}"#)
+            .create_async()
+            .await;
+
+        let args = AssetSearchArgs {
+            favorite: Some(true),
+            print_raw: true,
+            ..Default::default()
+        };
+        let err = ctl.assets_search_add(&args).await.unwrap_err();
+
+        mock.assert_async().await;
+        assert_eq!(
+            err.to_string(),
+            "Search failed (400): size must not be less than 1"
+        );
+        assert!(err.downcast_ref::<api_error::ApiError>().is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_search_similar_to() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let reference = create_asset_with_timestamps(ts, ts);
+        let page: Vec<_> = (0..2)
+            .map(|_| create_asset_with_timestamps(ts, ts))
+            .collect();
+        // 2 pages with 2 assets each, but limit 3
+        let mut page_mocks = vec![];
+        for p in 1..=2 {
+            page_mocks.push(
+                server
+                    .mock("POST", "/api/search/smart")
+                    .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                        "queryAssetId": reference.id,
+                        "size": 3,
+                        "page": p
+                    })))
+                    .with_status(200)
+                    .with_header("content-type", "application/json")
+                    .with_body(
+                        serde_json::json!({
+                            "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                            "assets": {"count": 2, "facets": [], "items": if p == 1 { vec![&reference, &page[0]] } else { vec![&page[1], &page[0]] }, "nextPage": (p + 1).to_string(), "total": 2}
+                        })
+                        .to_string(),
+                    )
+                    .expect(1)
+                    .create_async()
+                    .await,
+            );
+        }
+
+        let args = AssetSearchArgs {
+            similar_to: Some(reference.id.to_string()),
+            limit: NonZeroU64::new(3),
+            ..Default::default()
+        };
+        ctl.assets_search_add(&args).await?;
+
+        for mock in page_mocks {
+            mock.assert_async().await;
+        }
+        let sel = Assets::load(&ctl.assets_file);
+        assert_eq!(sel.len(), 3);
+        assert!(sel.contains(&reference.id));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_search_add_tag_glob() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let tags = vec![
+            create_tag("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1", "events/a", None),
+            create_tag("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab2", "events/b", None),
+            create_tag("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab3", "other", None),
+        ];
+        let _tags_mock = server
+            .mock("GET", "/api/tags")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&tags)?)
+            .create_async()
+            .await;
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let both = create_asset_with_timestamps(ts, ts);
+        let only_b = create_asset_with_timestamps(ts, ts);
+        let mut search_mocks = Vec::new();
+        for (tag_id, items) in [
+            ("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1", vec![&both]),
+            ("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab2", vec![&both, &only_b]),
+        ] {
+            search_mocks.push(
+                server
+                    .mock("POST", "/api/search/metadata")
+                    .match_body(mockito::Matcher::PartialJson(
+                        serde_json::json!({"tagIds": [tag_id]}),
+                    ))
+                    .with_status(200)
+                    .with_header("content-type", "application/json")
+                    .with_body(
+                        serde_json::json!({
+                            "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                            "assets": {"count": items.len(), "facets": [], "items": items, "nextPage": null, "total": items.len()}
+                        })
+                        .to_string(),
+                    )
+                    .expect(1)
+                    .create_async()
+                    .await,
+            );
+        }
+
+        let args = AssetSearchArgs {
+            tag: Some("events/*".to_string()),
+            tag_glob: true,
+            ..Default::default()
+        };
+        ctl.assets_search_add(&args).await?;
+
+        for mock in search_mocks {
+            mock.assert_async().await;
+        }
+        let sel = Assets::load(&ctl.assets_file);
+        assert_eq!(sel.len(), 2);
+        assert!(sel.contains(&both.id));
+        assert!(sel.contains(&only_b.id));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_build_search_dto_with_tag() -> Result<()> {
+        let (ctl, mut server) = create_immichctl_with_server().await;
+
+        let tags = vec![create_tag(
+            "a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1",
+            "tag1",
+            None,
+        )];
+        let tags_mock = server
+            .mock("GET", "/api/tags")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&tags).unwrap())
+            .create_async()
+            .await;
+
+        let args = AssetSearchArgs {
+            tag: Some("tag1".to_string()),
+            ..Default::default()
+        };
+        let mut result = ctl.build_search_dto(&args).await;
+        tags_mock.assert_async().await;
+        assert!(result.is_ok());
+        assert_eq!(
+            result.unwrap(),
+            MetadataSearchDto {
+                tag_ids: Some(vec!(
+                    Uuid::parse_str("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1").unwrap()
+                )),
+                visibility: Some(AssetVisibility::Timeline),
+                with_deleted: Some(false),
+                ..Default::default()
+            }
+        );
+
+        let args = AssetSearchArgs {
+            tag: Some("no-tag".to_string()),
+            ..Default::default()
+        };
+        result = ctl.build_search_dto(&args).await;
+        tags_mock.expect(2).assert_async().await;
+        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "Tag not found or not unique: 'no-tag'"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_build_search_dto_with_album() -> Result<()> {
+        let (ctl, mut server) = create_immichctl_with_server().await;
+
+        let albums = vec![create_album(
+            "a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1",
+            "album1",
+        )];
+        let albums_mock = server
+            .mock("GET", "/api/albums")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&albums).unwrap())
+            .create_async()
+            .await;
+
+        let args = AssetSearchArgs {
+            album: Some("album1".to_string()),
+            ..Default::default()
+        };
+        let mut result = ctl.build_search_dto(&args).await;
+        albums_mock.assert_async().await;
+        assert!(result.is_ok());
+        assert_eq!(
+            result.unwrap(),
+            MetadataSearchDto {
+                album_ids: vec!(Uuid::parse_str("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1").unwrap()),
+                visibility: Some(AssetVisibility::Timeline),
+                with_deleted: Some(false),
+                ..Default::default()
+            }
+        );
+
+        let args = AssetSearchArgs {
+            album: Some("no-album".to_string()),
+            ..Default::default()
+        };
+        result = ctl.build_search_dto(&args).await;
+        albums_mock.expect(2).assert_async().await;
+        assert!(result.is_err());
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "Album not found: 'no-album'"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_build_search_dto_with_favorite() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+
+        let args = AssetSearchArgs {
+            favorite: Some(true),
+            ..Default::default()
+        };
+        let result = ctl.build_search_dto(&args).await;
+
+        assert!(result.is_ok());
+        let search_dto = result.unwrap();
+        assert_eq!(search_dto.is_favorite, Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_build_search_dto_with_city_and_country() -> Result<()> {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+
+        let args = AssetSearchArgs {
+            city: Some("Paris".to_string()),
+            ..Default::default()
+        };
+        let search_dto = ctl.build_search_dto(&args).await?;
+        assert_eq!(search_dto.city.as_deref(), Some("Paris"));
+        assert_eq!(search_dto.country, None);
+
+        let args = AssetSearchArgs {
+            country: Some("France".to_string()),
+            ..Default::default()
+        };
+        let search_dto = ctl.build_search_dto(&args).await?;
+        assert_eq!(search_dto.city, None);
+        assert_eq!(search_dto.country.as_deref(), Some("France"));
+
+        let args = AssetSearchArgs {
+            city: Some("Paris".to_string()),
+            country: Some("France".to_string()),
+            ..Default::default()
+        };
+        let search_dto = ctl.build_search_dto(&args).await?;
+        assert_eq!(search_dto.city.as_deref(), Some("Paris"));
+        assert_eq!(search_dto.country.as_deref(), Some("France"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_build_search_dto_with_type() -> Result<()> {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+
+        let args = AssetSearchArgs {
+            asset_type: Some(AssetType::Image),
+            ..Default::default()
+        };
+        let search_dto = ctl.build_search_dto(&args).await?;
+        assert_eq!(search_dto.type_, Some(AssetTypeEnum::Image));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_build_search_dto_with_make_and_model() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+
+        let args = AssetSearchArgs {
+            make: Some("SONY".to_string()),
+            model: Some("ILCE-7M3".to_string()),
+            ..Default::default()
+        };
+        let result = ctl.build_search_dto(&args).await;
+
+        assert!(result.is_ok());
+        let search_dto = result.unwrap();
+        assert_eq!(search_dto.make.as_deref(), Some("SONY"));
+        assert_eq!(search_dto.model.as_deref(), Some("ILCE-7M3"));
+    }
+
+    #[tokio::test]
+    async fn test_build_search_dto_all() -> Result<()> {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+
+        let args = AssetSearchArgs {
+            all: true,
+            yes: true,
+            ..Default::default()
+        };
+        let search_dto = ctl.build_search_dto(&args).await?;
+        assert_eq!(
+            search_dto,
+            MetadataSearchDto {
+                visibility: Some(AssetVisibility::Timeline),
+                with_deleted: Some(false),
+                ..Default::default()
+            }
         );
+        Ok(())
+    }
 
-        // Case 4: Out-of-range offset (> 24 hours), should default to UTC
-        let local_date_time = Utc.with_ymd_and_hms(2024, 1, 2, 12, 0, 0).unwrap(); // 26 hours difference
-        let asset = create_asset_with_timestamps(file_created_at, local_date_time);
+    #[tokio::test]
+    async fn test_build_search_dto_order() -> Result<()> {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+
+        let mut args = AssetSearchArgs {
+            favorite: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(ctl.build_search_dto(&args).await?.order, None);
+        args.order = Some(SearchOrder::Newest);
         assert_eq!(
-            ImmichCtl::asset_timezone_offset(&asset),
-            FixedOffset::east_opt(0).unwrap()
+            ctl.build_search_dto(&args).await?.order,
+            Some(AssetOrder::Desc)
+        );
+        args.order = Some(SearchOrder::Oldest);
+        assert_eq!(
+            ctl.build_search_dto(&args).await?.order,
+            Some(AssetOrder::Asc)
+        );
+
+        // --order alone is not a search condition
+        let args = AssetSearchArgs {
+            order: Some(SearchOrder::Newest),
+            ..Default::default()
+        };
+        assert!(ctl.build_search_dto(&args).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_build_search_dto_excludes_trashed() -> Result<()> {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+
+        let mut args = AssetSearchArgs {
+            favorite: Some(true),
+            ..Default::default()
+        };
+        let search_dto = ctl.build_search_dto(&args).await?;
+        assert_eq!(search_dto.with_deleted, Some(false));
+
+        args.include_trashed = true;
+        let search_dto = ctl.build_search_dto(&args).await?;
+        assert_eq!(search_dto.with_deleted, Some(true));
+
+        // --include-trashed alone is not a search condition
+        let args = AssetSearchArgs {
+            include_trashed: true,
+            ..Default::default()
+        };
+        assert!(ctl.build_search_dto(&args).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_build_search_dto_with_taken_before_after() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+
+        let taken_after_str = "2024-07-18T00:00:00+00:00";
+        let taken_before_str = "2024-07-18T23:59:59+00:00";
+        let taken_after = DateTime::parse_from_rfc3339(taken_after_str).ok();
+        let taken_before = DateTime::parse_from_rfc3339(taken_before_str).ok();
+
+        let args = AssetSearchArgs {
+            taken_after,
+            taken_before,
+            ..Default::default()
+        };
+        let result = ctl.build_search_dto(&args).await;
+
+        assert!(result.is_ok());
+        let search_dto = result.unwrap();
+        assert_eq!(
+            search_dto.taken_after,
+            Some(taken_after.unwrap().with_timezone(&Utc))
+        );
+        assert_eq!(
+            search_dto.taken_before,
+            Some(taken_before.unwrap().with_timezone(&Utc))
         );
     }
 
     #[test]
-    fn test_asset_column() {
+    fn test_adjust_date_time_original_no_exif() {
         let file_created_at = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
         let local_date_time = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap(); // +2h offset
         let asset = create_asset_with_timestamps(file_created_at, local_date_time);
 
-        // Test basic columns
+        // No offset, no timezone change
+        let offset = TimeDelta::zero();
+        let new_timezone = None;
+        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone, None);
+        assert_eq!(result.0.to_rfc3339(), "2024-01-01T12:00:00+02:00");
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T12:00:00+02:00");
+
+        // Positive offset, no timezone change
+        let offset = TimeDelta::hours(1);
+        let new_timezone = None;
+        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T13:00:00+02:00");
+
+        // Negative offset, no timezone change
+        let offset = TimeDelta::hours(-3);
+        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T09:00:00+02:00");
+
+        // Timezone change, no offset
+        let offset = TimeDelta::zero();
+        let new_timezone = Some(FixedOffset::east_opt(0).unwrap()); // UTC
+        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T10:00:00+00:00");
+        let new_timezone = Some(FixedOffset::east_opt(5 * 3600).unwrap()); // +5h
+        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T15:00:00+05:00");
+
+        // Both offset and timezone change
+        let offset = TimeDelta::minutes(30);
+        let new_timezone = Some(FixedOffset::east_opt(-4 * 3600).unwrap()); // -4h
+        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T06:30:00-04:00");
+    }
+
+    #[test]
+    fn test_assume_tz() {
+        let file_created_at = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let local_date_time = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap(); // +2h offset
+        let asset = create_asset_with_timestamps(file_created_at, local_date_time);
+        let assume_tz = FixedOffset::east_opt(-5 * 3600).map(TimeZoneValue::from);
+
+        // assumed timezone wins over timezone derived from asset metadata
         assert_eq!(
-            ImmichCtl::asset_column(&asset, AssetColumns::Id),
-            asset.id.to_string()
+            ImmichCtl::asset_column(&asset, AssetColumns::Timezone, assume_tz),
+            "-05:00"
         );
         assert_eq!(
-            ImmichCtl::asset_column(&asset, AssetColumns::OriginalFileName),
-            "test.jpg"
+            ImmichCtl::asset_column(&asset, AssetColumns::DateTimeOriginal, assume_tz),
+            "2024-01-01T05:00:00-05:00"
+        );
+        let (old, new) =
+            ImmichCtl::adjust_date_time_original(&asset, &TimeDelta::hours(1), &None, assume_tz);
+        assert_eq!(old.to_rfc3339(), "2024-01-01T05:00:00-05:00");
+        assert_eq!(new.to_rfc3339(), "2024-01-01T06:00:00-05:00");
+
+        // EXIF timezone wins over assumed timezone
+        let exif_dt = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let asset_with_exif = create_asset_with_exif(
+            file_created_at,
+            local_date_time,
+            Some(exif_dt),
+            Some("+02:00".to_string()),
         );
         assert_eq!(
-            ImmichCtl::asset_column(&asset, AssetColumns::FileCreatedAt),
-            "2024-01-01T10:00:00+00:00"
+            ImmichCtl::get_date_time_original(&asset_with_exif, assume_tz).to_rfc3339(),
+            "2024-01-01T12:00:00+02:00"
         );
         assert_eq!(
-            ImmichCtl::asset_column(&asset, AssetColumns::Timezone),
+            ImmichCtl::asset_column(&asset_with_exif, AssetColumns::Timezone, assume_tz),
             "+02:00"
         );
         assert_eq!(
-            ImmichCtl::asset_column(&asset, AssetColumns::DateTimeOriginal),
+            ImmichCtl::asset_column(&asset_with_exif, AssetColumns::DateTimeOriginal, assume_tz),
             "2024-01-01T12:00:00+02:00"
         );
+        let filter: AssetFilter = "timezone=+02:00".parse().unwrap();
+        assert!(filter.matches(&asset_with_exif, assume_tz));
+        assert!(!filter.matches(&asset, assume_tz));
+    }
 
-        // Test EXIF columns with full data (with changed month to verify correctness)
-        let exif_dt = Utc.with_ymd_and_hms(2024, 2, 1, 10, 0, 0).unwrap();
-        let asset_with_exif = create_asset_with_exif(
+    #[test]
+    fn test_assume_tz_fallback_chain() -> Result<()> {
+        let config_dir = tempfile::tempdir()?;
+        let file_created_at = Utc.with_ymd_and_hms(2024, 7, 1, 10, 0, 0).unwrap();
+        let local_date_time = Utc.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap(); // +2h offset
+        let asset = create_asset_with_timestamps(file_created_at, local_date_time);
+        let timezone = |ctl: &ImmichCtl| {
+            ImmichCtl::asset_column(&asset, AssetColumns::Timezone, ctl.assume_tz).into_owned()
+        };
+
+        // derived from asset metadata
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
+        ctl.set_assume_tz(None);
+        assert_eq!(timezone(&ctl), "+02:00");
+
+        // config default wins over derived timezone, IANA names honor daylight saving time
+        ctl.config_set_timezone(Some("America/New_York".parse()?))?;
+        assert_eq!(timezone(&ctl), "-04:00");
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
+        assert_eq!(timezone(&ctl), "-04:00");
+        ctl.set_assume_tz(None);
+        assert_eq!(timezone(&ctl), "-04:00");
+
+        // explicit --assume-tz wins over config default
+        ctl.set_assume_tz(FixedOffset::east_opt(9 * 3600));
+        assert_eq!(timezone(&ctl), "+09:00");
+
+        ctl.config_set_timezone(None)?;
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+        assert_eq!(timezone(&ctl), "+02:00");
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_timezone_keeps_exif_timezone() -> Result<()> {
+        let config_dir = tempfile::tempdir()?;
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
+        ctl.config_set_timezone(Some("-05:00".parse()?))?;
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+
+        let file_created_at = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let local_date_time = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap(); // +2h offset
+        let mut with_exif = create_asset_with_exif(
             file_created_at,
             local_date_time,
-            Some(exif_dt),
+            Some(file_created_at),
             Some("+02:00".to_string()),
         );
+        with_exif.original_file_name = "exif.jpg".to_string();
+        let mut without_exif = create_asset_with_timestamps(file_created_at, local_date_time);
+        without_exif.original_file_name = "plain.jpg".to_string();
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(with_exif);
+        sel.add_asset(without_exif);
+        sel.save()?;
 
-        assert_eq!(
-            ImmichCtl::asset_column(&asset_with_exif, AssetColumns::ExifTimezone),
-            "+02:00"
+        let csv_file = config_dir.path().join("assets.csv");
+        let columns = [AssetColumns::OriginalFileName, AssetColumns::Timezone];
+        ctl.assets_list_csv(&columns, &[], None, Some(&csv_file))?;
+        let mut lines: Vec<_> = std::fs::read_to_string(&csv_file)?
+            .lines()
+            .map(str::to_string)
+            .collect();
+        lines.sort();
+        assert_eq!(lines, vec!["exif.jpg,+02:00", "plain.jpg,-05:00"]);
+
+        let filters: Vec<AssetFilter> = vec!["timezone=+02:00".parse()?];
+        ctl.assets_list_csv(&columns[..1], &filters, None, Some(&csv_file))?;
+        assert_eq!(std::fs::read_to_string(&csv_file)?, "exif.jpg\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_adjust_date_time_original_with_exif() {
+        let file_created_at = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 1).unwrap(); // modified seconds
+        let local_date_time = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 1).unwrap(); // +2h offset
+        let exif_date_time = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let asset = create_asset_with_exif(
+            file_created_at,
+            local_date_time,
+            Some(exif_date_time),
+            Some("+02:00".to_string()),
         );
-        assert_eq!(
-            ImmichCtl::asset_column(&asset_with_exif, AssetColumns::ExifDateTimeOriginal),
-            "2024-02-01T12:00:00+02:00"
+
+        // No offset, no timezone change
+        let offset = TimeDelta::zero();
+        let new_timezone = None;
+        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone, None);
+        assert_eq!(result.0.to_rfc3339(), "2024-01-01T12:00:00+02:00");
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T12:00:00+02:00");
+
+        // Positive offset, no timezone change
+        let offset = TimeDelta::hours(1);
+        let new_timezone = None;
+        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T13:00:00+02:00");
+
+        // Negative offset, no timezone change
+        let offset = TimeDelta::hours(-3);
+        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T09:00:00+02:00");
+
+        // Timezone change, no offset
+        let offset = TimeDelta::zero();
+        let new_timezone = Some(FixedOffset::east_opt(0).unwrap()); // UTC
+        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T10:00:00+00:00");
+        let new_timezone = Some(FixedOffset::east_opt(5 * 3600).unwrap()); // +5h
+        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T15:00:00+05:00");
+
+        // Both offset and timezone change
+        let offset = TimeDelta::minutes(30);
+        let new_timezone = Some(FixedOffset::east_opt(-4 * 3600).unwrap()); // -4h
+        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T06:30:00-04:00");
+    }
+
+    #[test]
+    fn test_set_timezone_date_time_original_no_exif() {
+        let file_created_at = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let local_date_time = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap(); // +2h offset
+        let asset = create_asset_with_timestamps(file_created_at, local_date_time);
+        let tz = |s: &str| s.parse::<TimeZoneValue>().unwrap();
+
+        // wall clock reading is kept
+        let result = ImmichCtl::set_timezone_date_time_original(&asset, tz("+05:00"), false, None);
+        assert_eq!(result.0.to_rfc3339(), "2024-01-01T12:00:00+02:00");
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T12:00:00+05:00");
+        let result = ImmichCtl::set_timezone_date_time_original(&asset, tz("-04:00"), false, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T12:00:00-04:00");
+        let result = ImmichCtl::set_timezone_date_time_original(&asset, tz("+02:00"), false, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T12:00:00+02:00");
+
+        // instant is kept
+        let result = ImmichCtl::set_timezone_date_time_original(&asset, tz("+05:00"), true, None);
+        assert_eq!(result.0.to_rfc3339(), "2024-01-01T12:00:00+02:00");
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T15:00:00+05:00");
+        let result = ImmichCtl::set_timezone_date_time_original(&asset, tz("+00:00"), true, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T10:00:00+00:00");
+        let result = ImmichCtl::set_timezone_date_time_original(&asset, tz("-04:00"), true, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T06:00:00-04:00");
+
+        // assumed timezone for assets without EXIF timezone
+        let assume_tz = Some(tz("+00:00"));
+        let result =
+            ImmichCtl::set_timezone_date_time_original(&asset, tz("+02:00"), false, assume_tz);
+        assert_eq!(result.0.to_rfc3339(), "2024-01-01T10:00:00+00:00");
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T10:00:00+02:00");
+    }
+
+    #[test]
+    fn test_set_timezone_date_time_original_with_exif() {
+        let file_created_at = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 1).unwrap(); // modified seconds
+        let local_date_time = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 1).unwrap(); // +2h offset
+        let exif_date_time = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let asset = create_asset_with_exif(
+            file_created_at,
+            local_date_time,
+            Some(exif_date_time),
+            Some("UTC".to_string()),
         );
+        let tz = |s: &str| s.parse::<TimeZoneValue>().unwrap();
 
-        // Test EXIF columns with missing timezone in EXIF -> no exif datetime output
-        let asset_with_partial_exif =
-            create_asset_with_exif(file_created_at, local_date_time, Some(exif_dt), None);
-        assert_eq!(
-            ImmichCtl::asset_column(&asset_with_partial_exif, AssetColumns::ExifTimezone),
-            ""
-        );
-        assert_eq!(
-            ImmichCtl::asset_column(&asset_with_partial_exif, AssetColumns::ExifDateTimeOriginal),
-            ""
-        );
+        // labeled UTC but shot in +02:00: keep the clock reading
+        let result = ImmichCtl::set_timezone_date_time_original(&asset, tz("+02:00"), false, None);
+        assert_eq!(result.0.to_rfc3339(), "2024-01-01T10:00:00+00:00");
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T10:00:00+02:00");
 
-        // Test EXIF columns with no EXIF data at all
-        assert_eq!(
-            ImmichCtl::asset_column(&asset, AssetColumns::ExifTimezone),
-            ""
-        );
-        assert_eq!(
-            ImmichCtl::asset_column(&asset, AssetColumns::ExifDateTimeOriginal),
-            ""
-        );
+        // instant is kept
+        let result = ImmichCtl::set_timezone_date_time_original(&asset, tz("+02:00"), true, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T12:00:00+02:00");
+
+        // EXIF timezone wins over assumed timezone
+        let assume_tz = Some(tz("-05:00"));
+        let result =
+            ImmichCtl::set_timezone_date_time_original(&asset, tz("+02:00"), false, assume_tz);
+        assert_eq!(result.1.to_rfc3339(), "2024-01-01T10:00:00+02:00");
     }
 
     #[test]
-    fn test_parse_exif_timezone() {
-        assert_eq!(
-            ImmichCtl::parse_exif_timezone("+02:00").unwrap(),
-            FixedOffset::east_opt(2 * 3600).unwrap()
-        );
-        assert_eq!(
-            ImmichCtl::parse_exif_timezone("UTC+2").unwrap(),
-            FixedOffset::east_opt(2 * 3600).unwrap()
-        );
-        for tz_str in &[
-            "UTC",
-            "UTC+0",
-            "UTC-0",
-            "UTC+00:00",
-            "+00:00",
-            "-00:00",
-            "+0",
-            "-0",
-        ] {
-            assert_eq!(
-                ImmichCtl::parse_exif_timezone(tz_str).unwrap(),
-                FixedOffset::east_opt(0).unwrap()
-            );
-        }
-        assert_eq!(
-            ImmichCtl::parse_exif_timezone("-0530").unwrap(),
-            FixedOffset::east_opt(-5 * 3600 - 30 * 60).unwrap()
-        );
-        assert_eq!(
-            ImmichCtl::parse_exif_timezone("+1").unwrap(),
-            FixedOffset::east_opt(3600).unwrap()
-        );
-        assert!(ImmichCtl::parse_exif_timezone("invalid").is_err());
-        assert!(ImmichCtl::parse_exif_timezone("").is_err());
+    fn test_set_timezone_date_time_original_iana() {
+        let tz = TimeZoneValue::Iana("Europe/Berlin");
+        let at = |m, d, h| {
+            let ts = Utc.with_ymd_and_hms(2024, m, d, h, 0, 0).unwrap();
+            create_asset_with_timestamps(ts, ts)
+        };
+
+        // offset valid at the wall clock reading, including daylight saving time
+        let result = ImmichCtl::set_timezone_date_time_original(&at(1, 15, 12), tz, false, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-01-15T12:00:00+01:00");
+        let result = ImmichCtl::set_timezone_date_time_original(&at(7, 15, 12), tz, false, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-07-15T12:00:00+02:00");
+        // 31 March 2024 switch to summer time at 02:00 local time
+        let result = ImmichCtl::set_timezone_date_time_original(&at(3, 31, 1), tz, false, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-03-31T01:00:00+01:00");
+        let result = ImmichCtl::set_timezone_date_time_original(&at(3, 31, 4), tz, false, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-03-31T04:00:00+02:00");
+
+        let result = ImmichCtl::set_timezone_date_time_original(&at(7, 15, 12), tz, true, None);
+        assert_eq!(result.1.to_rfc3339(), "2024-07-15T14:00:00+02:00");
     }
 
     #[tokio::test]
-    async fn test_build_search_dto_no_flags() {
-        let config_dir = tempfile::tempdir().unwrap();
-        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+    async fn test_assets_set_timezone() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let asset = create_asset_with_exif(ts, ts, Some(ts), Some("UTC".to_string()));
+        let mut assets = Assets::load(&ctl.assets_file);
+        assets.add_asset(asset.clone());
+        assets.save()?;
 
-        let result = ctl.build_search_dto(&AssetSearchArgs::default()).await;
+        let mut updated = asset.clone();
+        let expected = FixedOffset::east_opt(2 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2024, 1, 1, 10, 0, 0)
+            .unwrap();
+        ImmichCtl::set_exif_date_time_original(&mut updated, &expected);
+        let update_mock = server
+            .mock("PUT", format!("/api/assets/{}", asset.id).as_str())
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "dateTimeOriginal": "2024-01-01T10:00:00+02:00"
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&updated)?)
+            .expect(1)
+            .create_async()
+            .await;
 
-        assert!(result.is_err());
-        assert_eq!(
-            result.err().unwrap().to_string(),
-            "Please provide at least one search flag."
-        );
+        ctl.set_dry_run(true);
+        ctl.assets_set_timezone("+02:00".parse()?, false).await?;
+        ctl.set_dry_run(false);
+        ctl.assets_set_timezone("+02:00".parse()?, false).await?;
+        update_mock.assert_async().await;
+
+        let cached = Assets::load(&ctl.assets_file);
+        let cached = cached.iter_assets().next().unwrap();
+        assert!(ImmichCtl::has_date_time_original(cached, &expected));
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_build_search_dto_with_id() {
-        let config_dir = tempfile::tempdir().unwrap();
-        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+    async fn test_assets_search_add_with_start_page_and_page_size() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let asset1 = create_asset_with_timestamps(ts, ts);
+        let asset2 = create_asset_with_timestamps(ts, ts);
+        let search_result = |asset: &AssetResponseDto, next_page: Option<&str>| {
+            serde_json::json!({
+                "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                "assets": {"count": 1, "facets": [], "items": [asset], "nextPage": next_page, "total": 1}
+            })
+            .to_string()
+        };
+        let page2_mock = server
+            .mock("POST", "/api/search/metadata")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"isFavorite": true, "page": 2, "size": 1}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(search_result(&asset1, Some("3")))
+            .create_async()
+            .await;
+        let page3_mock = server
+            .mock("POST", "/api/search/metadata")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"isFavorite": true, "page": 3, "size": 1}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(search_result(&asset2, None))
+            .create_async()
+            .await;
 
         let args = AssetSearchArgs {
-            id: Some("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1".to_string()),
+            favorite: Some(true),
+            start_page: NonZeroU64::new(2),
+            page_size: NonZeroU64::new(1),
             ..Default::default()
         };
-        let mut result = ctl.build_search_dto(&args).await;
-        assert!(result.is_ok());
-        assert_eq!(
-            result.unwrap(),
-            MetadataSearchDto {
-                id: Some(Uuid::parse_str("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1").unwrap()),
-                visibility: Some(AssetVisibility::Timeline),
-                ..Default::default()
-            }
-        );
+        ctl.assets_search_add(&args).await?;
+
+        page2_mock.assert_async().await;
+        page3_mock.assert_async().await;
+        let sel = Assets::load(&ctl.assets_file);
+        assert_eq!(sel.len(), 2);
+        assert!(sel.contains(&asset1.id));
+        assert!(sel.contains(&asset2.id));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_search_add_max_new() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let selected = create_asset_with_timestamps(ts, ts);
+        let first_new = create_asset_with_timestamps(ts, ts);
+        let second_new = create_asset_with_timestamps(ts, ts);
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(selected.clone());
+        sel.save()?;
+
+        let search_result = |assets: &[&AssetResponseDto], next_page: Option<&str>| {
+            serde_json::json!({
+                "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                "assets": {"count": assets.len(), "facets": [], "items": assets, "nextPage": next_page, "total": 3}
+            })
+            .to_string()
+        };
+        // the already selected asset doesn't count
+        let page1_mock = server
+            .mock("POST", "/api/search/metadata")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"page": 1}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(search_result(&[&selected], Some("2")))
+            .expect(1)
+            .create_async()
+            .await;
+        let page2_mock = server
+            .mock("POST", "/api/search/metadata")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"page": 2}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(search_result(&[&first_new, &second_new], Some("3")))
+            .expect(1)
+            .create_async()
+            .await;
+        let page3_mock = server
+            .mock("POST", "/api/search/metadata")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"page": 3}),
+            ))
+            .expect(0)
+            .create_async()
+            .await;
 
         let args = AssetSearchArgs {
-            id: Some("no-uuid".to_string()),
+            favorite: Some(true),
+            max_new: NonZeroUsize::new(1),
             ..Default::default()
         };
-        result = ctl.build_search_dto(&args).await;
-        assert!(result.is_err());
-        assert_eq!(
-            result.err().unwrap().to_string(),
-            "Invalid asset id, expected uuid"
-        );
+        ctl.assets_search_add(&args).await?;
+
+        page1_mock.assert_async().await;
+        page2_mock.assert_async().await;
+        page3_mock.assert_async().await;
+        let sel = Assets::load(&ctl.assets_file);
+        assert_eq!(sel.len(), 2);
+        assert!(sel.contains(&first_new.id));
+        assert!(!sel.contains(&second_new.id));
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_build_search_dto_with_tag() -> Result<()> {
-        let (ctl, mut server) = create_immichctl_with_server().await;
+    async fn test_assets_search_add_by_type() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
 
-        let tags = vec![create_tag(
-            "a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1",
-            "tag1",
-            None,
-        )];
-        let tags_mock = server
-            .mock("GET", "/api/tags")
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let image = create_asset_with_timestamps(ts, ts);
+        let mut video = create_asset_with_timestamps(ts, ts);
+        video.type_ = AssetTypeEnum::Video;
+        let search_mock = server
+            .mock("POST", "/api/search/metadata")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"type": "VIDEO"}),
+            ))
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(serde_json::to_string(&tags).unwrap())
+            .with_body(
+                serde_json::json!({
+                    "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                    "assets": {"count": 2, "facets": [], "items": [image, video], "total": 2}
+                })
+                .to_string(),
+            )
             .create_async()
             .await;
 
         let args = AssetSearchArgs {
-            tag: Some("tag1".to_string()),
+            asset_type: Some(AssetType::Video),
             ..Default::default()
         };
-        let mut result = ctl.build_search_dto(&args).await;
-        tags_mock.assert_async().await;
-        assert!(result.is_ok());
-        assert_eq!(
-            result.unwrap(),
-            MetadataSearchDto {
-                tag_ids: Some(vec!(
-                    Uuid::parse_str("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1").unwrap()
-                )),
-                visibility: Some(AssetVisibility::Timeline),
-                ..Default::default()
-            }
-        );
+        ctl.assets_search_add(&args).await?;
+
+        search_mock.assert_async().await;
+        let sel = Assets::load(&ctl.assets_file);
+        assert_eq!(sel.len(), 1);
+        assert!(sel.contains(&video.id));
+        Ok(())
+    }
+
+    #[test]
+    fn test_matches_extension() {
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let mut asset = create_asset_with_timestamps(ts, ts);
+        asset.original_file_name = "IMG_0001.HEIC".to_string();
+        let ext = |exts: &[&str]| exts.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+
+        assert!(ImmichCtl::matches_extension(&asset, &[]));
+        assert!(ImmichCtl::matches_extension(&asset, &ext(&["heic"])));
+        assert!(ImmichCtl::matches_extension(&asset, &ext(&[".HEIC"])));
+        assert!(ImmichCtl::matches_extension(&asset, &ext(&[".Heic"])));
+        assert!(!ImmichCtl::matches_extension(&asset, &ext(&["jpg"])));
+        assert!(ImmichCtl::matches_extension(&asset, &ext(&["jpg", "heic"])));
+        // suffix of the extension only
+        assert!(!ImmichCtl::matches_extension(&asset, &ext(&["eic"])));
+        assert!(!ImmichCtl::matches_extension(&asset, &ext(&["."])));
+    }
+
+    #[tokio::test]
+    async fn test_assets_search_remove_by_extension() -> Result<()> {
+        let config_dir = tempfile::tempdir()?;
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
+
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let mut sel = Assets::load(&ctl.assets_file);
+        let mut assets = vec![];
+        for file in ["a.HEIC", "b.jpg", "c.JPG", "d.png"] {
+            let mut asset = create_asset_with_timestamps(ts, ts);
+            asset.original_file_name = file.to_string();
+            sel.add_asset(asset.clone());
+            assets.push(asset);
+        }
+        sel.save()?;
 
         let args = AssetSearchArgs {
-            tag: Some("no-tag".to_string()),
+            extension: vec![".heic".to_string(), "JPG".to_string()],
             ..Default::default()
         };
-        result = ctl.build_search_dto(&args).await;
-        tags_mock.expect(2).assert_async().await;
-        assert!(result.is_err());
-        assert_eq!(
-            result.err().unwrap().to_string(),
-            "Tag not found or not unique: 'no-tag'"
-        );
+        ctl.assets_search_remove(&args).await?;
+        let sel = Assets::load(&ctl.assets_file);
+        assert_eq!(sel.len(), 1);
+        assert!(sel.contains(&assets[3].id));
         Ok(())
     }
 
     #[tokio::test]
-    async fn test_build_search_dto_with_album() -> Result<()> {
-        let (ctl, mut server) = create_immichctl_with_server().await;
+    async fn test_assets_search_remove_by_type() -> Result<()> {
+        let config_dir = tempfile::tempdir()?;
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
 
-        let albums = vec![create_album(
-            "a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1",
-            "album1",
-        )];
-        let albums_mock = server
-            .mock("GET", "/api/albums")
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let image = create_asset_with_timestamps(ts, ts);
+        let mut video = create_asset_with_timestamps(ts, ts);
+        video.type_ = AssetTypeEnum::Video;
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(image.clone());
+        sel.add_asset(video.clone());
+        sel.save()?;
+
+        let args = AssetSearchArgs {
+            asset_type: Some(AssetType::Video),
+            ..Default::default()
+        };
+        ctl.assets_search_remove(&args).await?;
+        let sel = Assets::load(&ctl.assets_file);
+        assert_eq!(sel.len(), 1);
+        assert!(sel.contains(&image.id));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_sync() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let still_tagged = create_asset_with_timestamps(ts, ts);
+        let untagged = create_asset_with_timestamps(ts, ts);
+        let newly_tagged = create_asset_with_timestamps(ts, ts);
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(still_tagged.clone());
+        sel.add_asset(untagged.clone());
+        sel.save()?;
+
+        let _tags_mock = server
+            .mock("GET", "/api/tags")
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(serde_json::to_string(&albums).unwrap())
+            .with_body(serde_json::to_string(&vec![create_tag(
+                "a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1",
+                "vacation",
+                None,
+            )])?)
+            .create_async()
+            .await;
+        let _search_mock = server
+            .mock("POST", "/api/search/metadata")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                    "assets": {"count": 2, "facets": [], "items": [still_tagged, newly_tagged], "nextPage": null, "total": 2}
+                })
+                .to_string(),
+            )
             .create_async()
             .await;
 
-        let args = AssetSearchArgs {
-            album: Some("album1".to_string()),
+        ctl.assets_sync(Some("vacation"), None).await?;
+        let sel = Assets::load(&ctl.assets_file);
+        assert_eq!(sel.len(), 2);
+        assert!(sel.contains(&still_tagged.id));
+        assert!(sel.contains(&newly_tagged.id));
+        assert!(!sel.contains(&untagged.id));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_search_add_max_selection() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let assets = [
+            create_asset_with_timestamps(ts, ts),
+            create_asset_with_timestamps(ts, ts),
+            create_asset_with_timestamps(ts, ts),
+        ];
+        let search_result = |assets: &[AssetResponseDto], next_page: Option<&str>| {
+            serde_json::json!({
+                "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                "assets": {"count": assets.len(), "facets": [], "items": assets, "nextPage": next_page, "total": 3}
+            })
+            .to_string()
+        };
+        let _page1_mock = server
+            .mock("POST", "/api/search/metadata")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"page": 1}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(search_result(&assets[..2], Some("2")))
+            .create_async()
+            .await;
+        let page2_mock = server
+            .mock("POST", "/api/search/metadata")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"page": 2}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(search_result(&assets[2..], None))
+            .expect(0)
+            .create_async()
+            .await;
+
+        // paging stops as soon as the cap is exceeded
+        let mut args = AssetSearchArgs {
+            favorite: Some(true),
+            max_selection: 1,
             ..Default::default()
         };
-        let mut result = ctl.build_search_dto(&args).await;
-        albums_mock.assert_async().await;
-        assert!(result.is_ok());
+        let result = ctl.assets_search_add(&args).await;
         assert_eq!(
-            result.unwrap(),
-            MetadataSearchDto {
-                album_ids: vec!(Uuid::parse_str("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1").unwrap()),
-                visibility: Some(AssetVisibility::Timeline),
-                ..Default::default()
-            }
+            result.unwrap_err().to_string(),
+            "Selection would grow from 0 to more than 1 assets, exceeding --max-selection 1. Use --yes to continue anyway."
         );
+        assert!(Assets::load(&ctl.assets_file).is_empty());
+        page2_mock.assert_async().await;
 
-        let args = AssetSearchArgs {
-            album: Some("no-album".to_string()),
+        page2_mock.remove_async().await;
+        let page2_mock = server
+            .mock("POST", "/api/search/metadata")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"page": 2}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(search_result(&assets[2..], None))
+            .expect(2)
+            .create_async()
+            .await;
+        args.yes = true;
+        ctl.assets_search_add(&args).await?;
+        assert_eq!(Assets::load(&ctl.assets_file).len(), 3);
+
+        // 0 = unlimited
+        args.yes = false;
+        args.max_selection = 0;
+        ctl.assets_search_add(&args).await?;
+        assert_eq!(Assets::load(&ctl.assets_file).len(), 3);
+        page2_mock.assert_async().await;
+        Ok(())
+    }
+
+    fn create_asset_with_dimensions(width: Option<i64>, height: Option<i64>) -> AssetResponseDto {
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let mut asset = create_asset_with_timestamps(ts, ts);
+        asset.width = width;
+        asset.height = height;
+        asset
+    }
+
+    #[test]
+    fn test_asset_orientation() {
+        let orientation =
+            |w, h| ImmichCtl::asset_orientation(&create_asset_with_dimensions(Some(w), Some(h)));
+        assert_eq!(orientation(4000, 3000), Some(Orientation::Landscape));
+        assert_eq!(orientation(3000, 4000), Some(Orientation::Portrait));
+        assert_eq!(orientation(1000, 1000), Some(Orientation::Square));
+        assert_eq!(orientation(0, 1000), None);
+
+        // missing dimensions
+        let mut asset = create_asset_with_dimensions(None, Some(1000));
+        assert_eq!(ImmichCtl::asset_orientation(&asset), None);
+
+        // fallback to EXIF dimensions, rotated by EXIF orientation
+        asset.exif_info = Some(ExifResponseDto {
+            exif_image_width: Some(4000),
+            exif_image_height: Some(3000),
             ..Default::default()
-        };
-        result = ctl.build_search_dto(&args).await;
-        albums_mock.expect(2).assert_async().await;
-        assert!(result.is_err());
+        });
         assert_eq!(
-            result.err().unwrap().to_string(),
-            "Album not found: 'no-album'"
+            ImmichCtl::asset_orientation(&asset),
+            Some(Orientation::Landscape)
+        );
+        asset.exif_info.as_mut().unwrap().orientation = Some("6".to_string());
+        assert_eq!(
+            ImmichCtl::asset_orientation(&asset),
+            Some(Orientation::Portrait)
         );
-        Ok(())
     }
 
     #[tokio::test]
-    async fn test_build_search_dto_with_favorite() {
-        let config_dir = tempfile::tempdir().unwrap();
-        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+    async fn test_assets_search_add_orientation() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let landscape = create_asset_with_dimensions(Some(4000), Some(3000));
+        let portrait = create_asset_with_dimensions(Some(3000), Some(4000));
+        let unknown = create_asset_with_dimensions(None, None);
+        let assets = vec![&landscape, &portrait, &unknown];
+        let _search_mock = server
+            .mock("POST", "/api/search/metadata")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                    "assets": {"count": 3, "facets": [], "items": assets, "nextPage": null, "total": 3}
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
 
+        ctl.set_verbose(true);
         let args = AssetSearchArgs {
             favorite: Some(true),
+            orientation: Some(Orientation::Landscape),
             ..Default::default()
         };
-        let result = ctl.build_search_dto(&args).await;
-
-        assert!(result.is_ok());
-        let search_dto = result.unwrap();
-        assert_eq!(search_dto.is_favorite, Some(true));
+        ctl.assets_search_add(&args).await?;
+        let sel = Assets::load(&ctl.assets_file);
+        assert_eq!(sel.len(), 1);
+        assert!(sel.contains(&landscape.id));
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_build_search_dto_with_taken_before_after() {
-        let config_dir = tempfile::tempdir().unwrap();
-        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+    async fn test_assets_search_remove_by_orientation() -> Result<()> {
+        let config_dir = tempfile::tempdir()?;
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
 
-        let taken_after_str = "2024-07-18T00:00:00+00:00";
-        let taken_before_str = "2024-07-18T23:59:59+00:00";
-        let taken_after = DateTime::parse_from_rfc3339(taken_after_str).ok();
-        let taken_before = DateTime::parse_from_rfc3339(taken_before_str).ok();
+        let landscape = create_asset_with_dimensions(Some(4000), Some(3000));
+        let portrait = create_asset_with_dimensions(Some(3000), Some(4000));
+        let square = create_asset_with_dimensions(Some(1000), Some(1000));
+        let unknown = create_asset_with_dimensions(None, None);
+        let mut sel = Assets::load(&ctl.assets_file);
+        for asset in [&landscape, &portrait, &square, &unknown] {
+            sel.add_asset(asset.clone());
+        }
+        sel.save()?;
 
-        let args = AssetSearchArgs {
-            taken_after,
-            taken_before,
-            ..Default::default()
-        };
-        let result = ctl.build_search_dto(&args).await;
+        for orientation in [Orientation::Portrait, Orientation::Square] {
+            let args = AssetSearchArgs {
+                orientation: Some(orientation),
+                ..Default::default()
+            };
+            ctl.assets_search_remove(&args).await?;
+        }
 
-        assert!(result.is_ok());
-        let search_dto = result.unwrap();
+        // assets without dimensions are not removed
+        let sel = Assets::load(&ctl.assets_file);
+        assert_eq!(sel.len(), 2);
+        assert!(sel.contains(&landscape.id));
+        assert!(sel.contains(&unknown.id));
+        Ok(())
+    }
+
+    #[test]
+    fn test_matches_live() {
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let photo = create_asset_with_timestamps(ts, ts);
+        let mut live = create_asset_with_timestamps(ts, ts);
+        live.live_photo_video_id = Some("b1a7f1a9-7394-49f7-a5a3-e876a7e16ab1".to_string());
+        assert!(ImmichCtl::matches_live(&photo, false, false));
+        assert!(ImmichCtl::matches_live(&live, false, false));
+        assert!(!ImmichCtl::matches_live(&photo, true, false));
+        assert!(ImmichCtl::matches_live(&live, true, false));
+        assert!(ImmichCtl::matches_live(&photo, false, true));
+        assert!(!ImmichCtl::matches_live(&live, false, true));
         assert_eq!(
-            search_dto.taken_after,
-            Some(taken_after.unwrap().with_timezone(&Utc))
+            ImmichCtl::asset_column(&live, AssetColumns::LivePhotoVideoId, None),
+            "b1a7f1a9-7394-49f7-a5a3-e876a7e16ab1"
         );
         assert_eq!(
-            search_dto.taken_before,
-            Some(taken_before.unwrap().with_timezone(&Utc))
+            ImmichCtl::asset_column(&photo, AssetColumns::LivePhotoVideoId, None),
+            ""
         );
     }
 
-    #[test]
-    fn test_adjust_date_time_original_no_exif() {
-        let file_created_at = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
-        let local_date_time = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap(); // +2h offset
-        let asset = create_asset_with_timestamps(file_created_at, local_date_time);
-
-        // No offset, no timezone change
-        let offset = TimeDelta::zero();
-        let new_timezone = None;
-        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone);
-        assert_eq!(result.0.to_rfc3339(), "2024-01-01T12:00:00+02:00");
-        assert_eq!(result.1.to_rfc3339(), "2024-01-01T12:00:00+02:00");
-
-        // Positive offset, no timezone change
-        let offset = TimeDelta::hours(1);
-        let new_timezone = None;
-        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone);
-        assert_eq!(result.1.to_rfc3339(), "2024-01-01T13:00:00+02:00");
+    #[tokio::test]
+    async fn test_assets_search_remove_live() -> Result<()> {
+        let config_dir = tempfile::tempdir()?;
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
 
-        // Negative offset, no timezone change
-        let offset = TimeDelta::hours(-3);
-        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone);
-        assert_eq!(result.1.to_rfc3339(), "2024-01-01T09:00:00+02:00");
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let photo = create_asset_with_timestamps(ts, ts);
+        let mut live = create_asset_with_timestamps(ts, ts);
+        live.live_photo_video_id = Some("b1a7f1a9-7394-49f7-a5a3-e876a7e16ab1".to_string());
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(photo.clone());
+        sel.add_asset(live.clone());
+        sel.save()?;
 
-        // Timezone change, no offset
-        let offset = TimeDelta::zero();
-        let new_timezone = Some(FixedOffset::east_opt(0).unwrap()); // UTC
-        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone);
-        assert_eq!(result.1.to_rfc3339(), "2024-01-01T10:00:00+00:00");
-        let new_timezone = Some(FixedOffset::east_opt(5 * 3600).unwrap()); // +5h
-        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone);
-        assert_eq!(result.1.to_rfc3339(), "2024-01-01T15:00:00+05:00");
+        let args = AssetSearchArgs {
+            live_only: true,
+            ..Default::default()
+        };
+        ctl.assets_search_remove(&args).await?;
+        let sel = Assets::load(&ctl.assets_file);
+        assert_eq!(sel.len(), 1);
+        assert!(sel.contains(&photo.id));
+        Ok(())
+    }
 
-        // Both offset and timezone change
-        let offset = TimeDelta::minutes(30);
-        let new_timezone = Some(FixedOffset::east_opt(-4 * 3600).unwrap()); // -4h
-        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone);
-        assert_eq!(result.1.to_rfc3339(), "2024-01-01T06:30:00-04:00");
+    #[test]
+    fn test_refreshed_suffix() {
+        assert_eq!(ImmichCtl::refreshed_suffix(0), "");
+        assert_eq!(ImmichCtl::refreshed_suffix(3), ", refreshed 3 existing");
     }
 
     #[test]
-    fn test_adjust_date_time_original_with_exif() {
-        let file_created_at = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 1).unwrap(); // modified seconds
-        let local_date_time = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 1).unwrap(); // +2h offset
-        let exif_date_time = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
-        let asset = create_asset_with_exif(
-            file_created_at,
-            local_date_time,
-            Some(exif_date_time),
-            Some("+02:00".to_string()),
+    fn test_parse_next_page() {
+        assert_eq!(
+            ImmichCtl::parse_next_page(Some("2")).unwrap(),
+            NonZeroU64::new(2)
         );
+        assert_eq!(
+            ImmichCtl::parse_next_page(Some(" 3 ")).unwrap(),
+            NonZeroU64::new(3)
+        );
+        assert_eq!(ImmichCtl::parse_next_page(None).unwrap(), None);
+        assert_eq!(ImmichCtl::parse_next_page(Some("")).unwrap(), None);
+        assert_eq!(ImmichCtl::parse_next_page(Some("null")).unwrap(), None);
+        assert_eq!(ImmichCtl::parse_next_page(Some("0")).unwrap(), None);
+        let err = ImmichCtl::parse_next_page(Some("2.5")).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid next_page value: '2.5'");
+    }
 
-        // No offset, no timezone change
-        let offset = TimeDelta::zero();
-        let new_timezone = None;
-        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone);
-        assert_eq!(result.0.to_rfc3339(), "2024-01-01T12:00:00+02:00");
-        assert_eq!(result.1.to_rfc3339(), "2024-01-01T12:00:00+02:00");
-
-        // Positive offset, no timezone change
-        let offset = TimeDelta::hours(1);
-        let new_timezone = None;
-        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone);
-        assert_eq!(result.1.to_rfc3339(), "2024-01-01T13:00:00+02:00");
+    #[tokio::test]
+    async fn test_search_pages_until_next_page_is_absent() -> Result<()> {
+        let (ctl, mut server) = create_immichctl_with_server().await;
 
-        // Negative offset, no timezone change
-        let offset = TimeDelta::hours(-3);
-        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone);
-        assert_eq!(result.1.to_rfc3339(), "2024-01-01T09:00:00+02:00");
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let asset1 = create_asset_with_timestamps(ts, ts);
+        let asset2 = create_asset_with_timestamps(ts, ts);
+        let page1_mock = server
+            .mock("POST", "/api/search/metadata")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"page": 1})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                    "assets": {"count": 1, "facets": [], "items": [asset1], "nextPage": "2", "total": 2}
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        // last page without nextPage field
+        let page2_mock = server
+            .mock("POST", "/api/search/metadata")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"page": 2}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                    "assets": {"count": 1, "facets": [], "items": [asset2], "total": 2}
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
 
-        // Timezone change, no offset
-        let offset = TimeDelta::zero();
-        let new_timezone = Some(FixedOffset::east_opt(0).unwrap()); // UTC
-        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone);
-        assert_eq!(result.1.to_rfc3339(), "2024-01-01T10:00:00+00:00");
-        let new_timezone = Some(FixedOffset::east_opt(5 * 3600).unwrap()); // +5h
-        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone);
-        assert_eq!(result.1.to_rfc3339(), "2024-01-01T15:00:00+05:00");
+        let assets = ctl.search_pages(MetadataSearchDto::default()).await?;
 
-        // Both offset and timezone change
-        let offset = TimeDelta::minutes(30);
-        let new_timezone = Some(FixedOffset::east_opt(-4 * 3600).unwrap()); // -4h
-        let result = ImmichCtl::adjust_date_time_original(&asset, &offset, &new_timezone);
-        assert_eq!(result.1.to_rfc3339(), "2024-01-01T06:30:00-04:00");
+        page1_mock.assert_async().await;
+        page2_mock.assert_async().await;
+        let ids: Vec<_> = assets.iter().map(|a| a.id).collect();
+        assert_eq!(ids, vec![asset1.id, asset2.id]);
+        Ok(())
     }
 
     #[tokio::test]
@@ -1025,6 +4849,35 @@ pub mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_assets_search_remove_by_library_id() -> Result<()> {
+        let config_dir = tempfile::tempdir()?;
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
+
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let library_id = Uuid::parse_str("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1")?;
+        let mut external_asset = create_asset_with_timestamps(ts, ts);
+        external_asset.library_id = Some(library_id);
+        let upload_asset = create_asset_with_timestamps(ts, ts);
+
+        let mut assets = Assets::load(&ctl.assets_file);
+        assets.add_asset(external_asset.clone());
+        assets.add_asset(upload_asset.clone());
+        assets.save()?;
+
+        // not logged in: an id is matched locally without server requests
+        let args = AssetSearchArgs {
+            library: Some(library_id.to_string()),
+            ..Default::default()
+        };
+        ctl.assets_search_remove(&args).await?;
+
+        let assets = Assets::load(&ctl.assets_file);
+        assert_eq!(assets.len(), 1);
+        assert!(assets.contains(&upload_asset.id));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_assets_search_remove_by_taken_after_and_before() {
         let config_dir = tempfile::tempdir().unwrap();
@@ -1065,6 +4918,196 @@ pub mod tests {
         assert!(remaining_ids.contains(&&asset3.id));
     }
 
+    #[tokio::test]
+    async fn test_assets_search_remove_by_make_and_model() -> Result<()> {
+        let config_dir = tempfile::tempdir().unwrap();
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
+
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let create_asset = |make: &str, model: &str| {
+            let mut asset = create_asset_with_timestamps(ts, ts);
+            asset.exif_info = Some(ExifResponseDto {
+                make: Some(make.to_string()),
+                model: Some(model.to_string()),
+                ..Default::default()
+            });
+            asset
+        };
+        let canon_r6 = create_asset("Canon", "EOS R6");
+        let canon_r5 = create_asset("Canon", "EOS R5");
+        let sony_r6 = create_asset("Sony", "EOS R6");
+        let no_exif = create_asset_with_timestamps(ts, ts);
+
+        let mut assets = Assets::load(&ctl.assets_file);
+        assets.add_asset(canon_r6.clone());
+        assets.add_asset(canon_r5.clone());
+        assets.add_asset(sony_r6.clone());
+        assets.add_asset(no_exif.clone());
+        assets.save()?;
+
+        // only assets matching all filters are removed, no server needed
+        let args = AssetSearchArgs {
+            make: Some("canon".to_string()),
+            model: Some("EOS R6".to_string()),
+            ..Default::default()
+        };
+        ctl.assets_search_remove(&args).await?;
+
+        let assets_after_remove = Assets::load(&ctl.assets_file);
+        assert_eq!(assets_after_remove.len(), 3);
+        assert!(!assets_after_remove.contains(&canon_r6.id));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_search_remove_by_city_queries_server() -> Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let found = create_asset_with_timestamps(ts, ts);
+        // cached city is outdated, the server search decides
+        let mut stale = create_asset_with_timestamps(ts, ts);
+        stale.exif_info = Some(ExifResponseDto {
+            city: Some("Paris".to_string()),
+            ..Default::default()
+        });
+        let mut assets = Assets::load(&ctl.assets_file);
+        assets.add_asset(found.clone());
+        assets.add_asset(stale.clone());
+        assets.save()?;
+
+        let search_mock = server
+            .mock("POST", "/api/search/metadata")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"city": "Paris", "country": "France"}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                    "assets": {"count": 1, "facets": [], "items": [found], "total": 1}
+                })
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let args = AssetSearchArgs {
+            city: Some("Paris".to_string()),
+            country: Some("France".to_string()),
+            ..Default::default()
+        };
+        ctl.assets_search_remove(&args).await?;
+
+        search_mock.assert_async().await;
+        let assets_after_remove = Assets::load(&ctl.assets_file);
+        assert_eq!(assets_after_remove.len(), 1);
+        assert!(assets_after_remove.contains(&stale.id));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_search_remove_by_filename_and_favorite() -> Result<()> {
+        let config_dir = tempfile::tempdir().unwrap();
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
+
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let mut favorite_pxl = create_asset_with_timestamps(ts, ts);
+        favorite_pxl.original_file_name = "PXL_20240101.jpg".to_string();
+        favorite_pxl.is_favorite = true;
+        let mut pxl = favorite_pxl.clone();
+        pxl.id = Uuid::new_v4();
+        pxl.is_favorite = false;
+        let mut favorite_img = favorite_pxl.clone();
+        favorite_img.id = Uuid::new_v4();
+        favorite_img.original_file_name = "IMG_0001.jpg".to_string();
+
+        let mut assets = Assets::load(&ctl.assets_file);
+        assets.add_asset(favorite_pxl.clone());
+        assets.add_asset(pxl.clone());
+        assets.add_asset(favorite_img.clone());
+        assets.save()?;
+
+        let args = AssetSearchArgs {
+            filename: Some("pxl_".to_string()),
+            favorite: Some(true),
+            ..Default::default()
+        };
+        ctl.assets_search_remove(&args).await?;
+
+        let assets_after_remove = Assets::load(&ctl.assets_file);
+        assert_eq!(assets_after_remove.len(), 2);
+        assert!(!assets_after_remove.contains(&favorite_pxl.id));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_search_remove_by_checksum() -> Result<()> {
+        let config_dir = tempfile::tempdir().unwrap();
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
+
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let mut asset = create_asset_with_timestamps(ts, ts);
+        asset.checksum = "qvTGHdzF6KLavt4PO0gs2a6pQ00=".to_string();
+        let other = create_asset_with_timestamps(ts, ts);
+
+        let mut assets = Assets::load(&ctl.assets_file);
+        assets.add_asset(asset.clone());
+        assets.add_asset(other.clone());
+        assets.save()?;
+
+        let args = AssetSearchArgs {
+            checksum: Some(parse_checksum("aaf4c61ddcc5e8a2dabede0f3b482cd9aea9434d").unwrap()),
+            ..Default::default()
+        };
+        ctl.assets_search_remove(&args).await?;
+
+        let assets_after_remove = Assets::load(&ctl.assets_file);
+        assert_eq!(assets_after_remove.len(), 1);
+        assert!(assets_after_remove.contains(&other.id));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_checksum() {
+        assert_eq!(
+            parse_checksum(" qvTGHdzF6KLavt4PO0gs2a6pQ00= ").as_deref(),
+            Ok("qvTGHdzF6KLavt4PO0gs2a6pQ00=")
+        );
+        assert_eq!(
+            parse_checksum("AAF4C61DDCC5E8A2DABEDE0F3B482CD9AEA9434D").as_deref(),
+            Ok("qvTGHdzF6KLavt4PO0gs2a6pQ00=")
+        );
+        assert_eq!(
+            parse_checksum("aGVsbG8=").unwrap_err(),
+            "expected a SHA-1 checksum of 20 bytes, got 5 bytes"
+        );
+        assert!(
+            parse_checksum("not base64!")
+                .unwrap_err()
+                .starts_with("expected a base64 or hex SHA-1 checksum")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_search_dto_with_checksum() -> Result<()> {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+
+        let args = AssetSearchArgs {
+            checksum: Some("qvTGHdzF6KLavt4PO0gs2a6pQ00=".to_string()),
+            ..Default::default()
+        };
+        let search_dto = ctl.build_search_dto(&args).await?;
+        assert_eq!(
+            search_dto.checksum.as_deref(),
+            Some("qvTGHdzF6KLavt4PO0gs2a6pQ00=")
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_assets_search_remove_bad_params() {
         let config_dir = tempfile::tempdir().unwrap();
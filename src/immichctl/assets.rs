@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
@@ -9,12 +10,20 @@ use uuid::Uuid;
 use crate::immichctl::types::AssetResponseDto;
 
 // could keep asset data on disk only to avoid large memory usage
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 pub struct Assets {
     #[serde(skip)]
     file: PathBuf,
 
+    /// URL of the server the selection was built on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    server: Option<String>,
+
     assets: HashMap<Uuid, AssetResponseDto>,
+
+    /// Checkpoint of an interrupted `assets refresh`: ids not refreshed yet.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    refresh_pending: HashSet<Uuid>,
 }
 
 impl Assets {
@@ -26,7 +35,9 @@ impl Assets {
             }
             None => Assets {
                 file: file.to_path_buf(),
+                server: None,
                 assets: HashMap::new(),
+                refresh_pending: HashSet::new(),
             },
         }
     }
@@ -53,7 +64,20 @@ impl Assets {
     }
 
     pub fn clear(&mut self) {
+        self.server = None;
         self.assets.clear();
+        self.refresh_pending.clear();
+    }
+
+    /// URL of the server the selection was built on, `None` for selections of older
+    /// versions or without assets.
+    pub fn server(&self) -> Option<&str> {
+        self.server.as_deref()
+    }
+
+    /// Record the server the selection is built on, saved with the selection.
+    pub fn set_server(&mut self, server: &str) {
+        self.server = Some(server.to_string());
     }
 
     #[allow(dead_code)]
@@ -61,7 +85,12 @@ impl Assets {
         self.assets.contains_key(asset_id)
     }
 
+    /// Add or replace an asset. A new asset added while a refresh is interrupted is
+    /// refreshed when the refresh is resumed.
     pub fn add_asset(&mut self, asset: AssetResponseDto) {
+        if !self.refresh_pending.is_empty() && !self.assets.contains_key(&asset.id) {
+            self.refresh_pending.insert(asset.id);
+        }
         self.assets.insert(asset.id, asset);
     }
 
@@ -80,14 +109,25 @@ impl Assets {
         self.assets.values()
     }
 
-    pub fn iter_mut_assets(&mut self) -> impl Iterator<Item = &mut AssetResponseDto> {
-        self.assets.values_mut()
-    }
-
     pub fn asset_uuids(&self) -> Vec<Uuid> {
         self.assets.keys().copied().collect()
     }
 
+    /// Ids of assets that still need to be refreshed. If no interrupted refresh
+    /// is pending, a new refresh of all assets is started.
+    pub fn refresh_pending(&mut self) -> Vec<Uuid> {
+        let assets = &self.assets;
+        self.refresh_pending.retain(|id| assets.contains_key(id));
+        if self.refresh_pending.is_empty() {
+            self.refresh_pending = self.assets.keys().copied().collect();
+        }
+        self.refresh_pending.iter().copied().collect()
+    }
+
+    pub fn mark_refreshed(&mut self, asset_id: &Uuid) {
+        self.refresh_pending.remove(asset_id);
+    }
+
     pub fn len(&self) -> usize {
         self.assets.len()
     }
@@ -149,7 +189,9 @@ mod tests {
     fn add_remove_list_assets() {
         let mut sel = Assets {
             file: PathBuf::from("test_selection.json"),
+            server: None,
             assets: HashMap::new(),
+            refresh_pending: HashSet::new(),
         };
         let asset = default_asset();
         let asset_id = asset.id.clone();
@@ -233,7 +275,9 @@ mod tests {
     fn asset_uuids() {
         let mut sel = Assets {
             file: PathBuf::from("test_selection.json"),
+            server: None,
             assets: HashMap::new(),
+            refresh_pending: HashSet::new(),
         };
         let asset = default_asset();
         let asset_id = asset.id.clone();
@@ -244,6 +288,64 @@ mod tests {
         assert_eq!(uuids[0], asset_id);
     }
 
+    #[test]
+    fn refresh_pending() {
+        let mut sel = Assets {
+            file: PathBuf::from("test_selection.json"),
+            server: None,
+            assets: HashMap::new(),
+            refresh_pending: HashSet::new(),
+        };
+        let asset = default_asset();
+        let asset_id = asset.id;
+        sel.add_asset(asset);
+
+        // new refresh round contains all assets
+        assert_eq!(sel.refresh_pending(), vec![asset_id]);
+        // pending ids survive until refreshed
+        assert_eq!(sel.refresh_pending(), vec![asset_id]);
+        sel.mark_refreshed(&asset_id);
+        assert!(sel.refresh_pending.is_empty());
+
+        // ids removed from the selection are not pending anymore
+        sel.refresh_pending();
+        sel.remove_asset(&asset_id);
+        assert!(sel.refresh_pending().is_empty());
+    }
+
+    #[test]
+    fn refresh_pending_with_added_assets() {
+        let mut sel = Assets {
+            file: PathBuf::from("test_selection.json"),
+            server: None,
+            assets: HashMap::new(),
+            refresh_pending: HashSet::new(),
+        };
+        let refreshed = default_asset();
+        let refreshed_id = refreshed.id;
+        let mut pending = default_asset();
+        let pending_id = Uuid::new_v4();
+        pending.id = pending_id;
+        sel.add_asset(refreshed.clone());
+        sel.add_asset(pending);
+
+        // interrupted refresh
+        sel.refresh_pending();
+        sel.add_asset(refreshed);
+        sel.mark_refreshed(&refreshed_id);
+
+        // assets added before resuming are refreshed, too
+        let mut added = default_asset();
+        let added_id = Uuid::new_v4();
+        added.id = added_id;
+        sel.add_asset(added);
+        let mut ids = sel.refresh_pending();
+        ids.sort();
+        let mut expected = vec![pending_id, added_id];
+        expected.sort();
+        assert_eq!(ids, expected);
+    }
+
     #[test]
     fn retain_assets() {
         let id1 = Uuid::parse_str("d8f91992-7329-4319-a4cb-33025753354a").unwrap();
@@ -251,7 +353,9 @@ mod tests {
 
         let mut sel = Assets {
             file: PathBuf::from("test_selection.json"),
+            server: None,
             assets: HashMap::new(),
+            refresh_pending: HashSet::new(),
         };
         let mut asset1 = default_asset();
         asset1.id = id1;
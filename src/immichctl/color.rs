@@ -0,0 +1,70 @@
+use std::io::IsTerminal;
+
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[33m";
+const RESET: &str = "\x1b[0m";
+
+/// When to use colored output.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Color only on a terminal and if `NO_COLOR` is not set
+    #[default]
+    Auto,
+    /// Always color
+    Always,
+    /// Never color
+    Never,
+}
+
+impl ColorMode {
+    /// Whether messages written to stderr should be colored.
+    pub fn stderr_enabled(self) -> bool {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        self.enabled(no_color, std::io::stderr().is_terminal())
+    }
+
+    fn enabled(self, no_color: bool, is_terminal: bool) -> bool {
+        match self {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => !no_color && is_terminal,
+        }
+    }
+}
+
+pub fn red(text: &str, color: bool) -> String {
+    paint(text, RED, color)
+}
+
+pub fn yellow(text: &str, color: bool) -> String {
+    paint(text, YELLOW, color)
+}
+
+fn paint(text: &str, code: &str, color: bool) -> String {
+    if color {
+        format!("{}{}{}", code, text, RESET)
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_mode_enabled() {
+        assert!(ColorMode::Auto.enabled(false, true));
+        assert!(!ColorMode::Auto.enabled(false, false));
+        assert!(!ColorMode::Auto.enabled(true, true));
+        assert!(ColorMode::Always.enabled(true, false));
+        assert!(!ColorMode::Never.enabled(false, true));
+    }
+
+    #[test]
+    fn test_paint() {
+        assert_eq!(red("Error:", false), "Error:");
+        assert_eq!(red("Error:", true), "\x1b[31mError:\x1b[0m");
+        assert_eq!(yellow("Warning:", true), "\x1b[33mWarning:\x1b[0m");
+    }
+}
@@ -1,4 +1,6 @@
-use anyhow::{Context, Result};
+use super::netrc::{self, NetrcEntry};
+use super::timezone::TimeZoneValue;
+use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::{Read, Write};
@@ -10,11 +12,26 @@ pub struct Config {
     config_file: PathBuf,
     pub server: String,
     pub apikey: String,
+    /// Timezone for assets without EXIF timezone if `--assume-tz` isn't given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_timezone: Option<TimeZoneValue>,
+    /// Server and API key of the config file if they were completed from a netrc file.
+    /// They are saved instead of the netrc values.
+    #[serde(skip)]
+    stored_login: Option<(String, String)>,
+    /// Why an existing config file could not be loaded. Such a config is empty and never
+    /// saved, it would overwrite the login of the broken file.
+    #[serde(skip)]
+    load_error: Option<String>,
 }
 
 impl Config {
     pub fn load(config_file: &Path) -> Config {
-        match Self::load_config(config_file) {
+        let (config, load_error) = match Self::load_config(config_file) {
+            Ok(config) => (config, None),
+            Err(err) => (None, Some(format!("{:#}", err))),
+        };
+        match config {
             Some(mut cfg) => {
                 cfg.config_file = config_file.to_path_buf();
                 cfg
@@ -23,14 +40,30 @@ impl Config {
                 config_file: config_file.to_path_buf(),
                 server: String::new(),
                 apikey: String::new(),
+                default_timezone: None,
+                stored_login: None,
+                load_error,
             },
         }
     }
 
     pub fn save(&self) -> Result<()> {
+        if let Some(err) = &self.load_error {
+            bail!(
+                "Configuration not saved. {}, fix or remove the file first.",
+                err
+            );
+        }
         fs::create_dir_all(self.config_file.parent().unwrap())?;
-        let contents = serde_json::to_string_pretty(&self)
-            .context("Could not save configuration, serialization error")?;
+        let contents = match &self.stored_login {
+            Some((server, apikey)) => serde_json::to_string_pretty(&Config {
+                server: server.clone(),
+                apikey: apikey.clone(),
+                ..self.clone()
+            }),
+            None => serde_json::to_string_pretty(&self),
+        }
+        .context("Could not save configuration, serialization error")?;
         #[cfg(unix)]
         {
             use std::os::unix::fs::OpenOptionsExt;
@@ -54,6 +87,15 @@ impl Config {
         Ok(())
     }
 
+    pub fn config_file(&self) -> &Path {
+        &self.config_file
+    }
+
+    /// Why the existing config file could not be loaded, if it couldn't.
+    pub fn load_error(&self) -> Option<&str> {
+        self.load_error.as_deref()
+    }
+
     pub fn logged_in(&self) -> bool {
         !self.server.is_empty() && !self.apikey.is_empty()
     }
@@ -61,17 +103,97 @@ impl Config {
     pub fn logout(&mut self) {
         self.server.clear();
         self.apikey.clear();
+        self.stored_login = None;
+    }
+
+    /// The server URL with `${VAR}` references replaced by environment variables, without
+    /// trailing `/`. Fails if a referenced variable is not set.
+    pub fn server_url(&self) -> Result<String> {
+        let url = expand_env_vars(&self.server, |var| std::env::var(var).ok())?;
+        Ok(url.trim_end_matches('/').to_string())
+    }
+
+    /// Set server and API key, e.g. after a successful login.
+    pub fn set_login(&mut self, server: &str, apikey: &str) {
+        self.server = server.to_string();
+        self.apikey = apikey.to_string();
+        self.stored_login = None;
+    }
+
+    /// Fill a missing server and/or API key from the netrc `entries`: the entry of the
+    /// server's host or, without server, the entry with login `immichctl`. The server is
+    /// taken from the `account` token or `https://<machine>`, the API key from the
+    /// `password` token. Returns whether the config was changed.
+    pub fn complete_from_netrc(&mut self, entries: &[NetrcEntry]) -> bool {
+        if self.logged_in() {
+            return false;
+        }
+        let entry = if self.server.is_empty() {
+            netrc::find_by_login(entries)
+        } else {
+            self.server_url()
+                .ok()
+                .and_then(|server| reqwest::Url::parse(&server).ok())
+                .and_then(|url| netrc::find_by_host(entries, url.host_str()?))
+        };
+        let Some(entry) = entry else {
+            return false;
+        };
+        let stored = (self.server.clone(), self.apikey.clone());
+        if self.server.is_empty() {
+            self.server = match &entry.account {
+                Some(account) => account.trim_end_matches('/').to_string(),
+                None => format!("https://{}", entry.machine),
+            };
+        }
+        if self.apikey.is_empty()
+            && let Some(password) = &entry.password
+        {
+            self.apikey = password.clone();
+        }
+        self.stored_login = Some(stored);
+        true
     }
 
-    fn load_config(config_file: &Path) -> Option<Config> {
+    fn load_config(config_file: &Path) -> Result<Option<Config>> {
         if !config_file.exists() {
-            return None;
+            return Ok(None);
         }
-        let mut file = fs::File::open(config_file).ok()?;
         let mut contents = String::new();
-        file.read_to_string(&mut contents).ok()?;
-        serde_json::from_str(&contents).ok()
+        fs::File::open(config_file)
+            .and_then(|mut file| file.read_to_string(&mut contents))
+            .with_context(|| format!("Could not read config file '{}'", config_file.display()))?;
+        let config = serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid config file '{}'", config_file.display()))?;
+        Ok(Some(config))
+    }
+}
+
+/// Replace `${VAR}` references in `value` by `lookup(VAR)`. Other `$` characters are kept.
+fn expand_env_vars(value: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String> {
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        expanded.push_str(&rest[..start]);
+        let Some(len) = rest[start + 2..].find('}') else {
+            bail!("Unterminated '${{' in '{}'", value);
+        };
+        let var = &rest[start + 2..start + 2 + len];
+        if var.is_empty() {
+            bail!("Empty variable reference '${{}}' in '{}'", value);
+        }
+        let Some(var_value) = lookup(var) else {
+            bail!(
+                "Environment variable '{}' used in '{}' is not set",
+                var,
+                value
+            );
+        };
+        expanded.push_str(&var_value);
+        rest = &rest[start + 2 + len + 1..];
     }
+    expanded.push_str(rest);
+    Ok(expanded)
 }
 
 #[cfg(test)]
@@ -95,6 +217,9 @@ mod tests {
             config_file: config_path.clone(),
             server: "http://localhost".to_string(),
             apikey: "testkey".to_string(),
+            default_timezone: Some(TimeZoneValue::Iana("Europe/Berlin")),
+            stored_login: None,
+            load_error: None,
         };
         config.save().unwrap();
         let loaded = Config::load(&config_path);
@@ -110,12 +235,18 @@ mod tests {
             config_file: PathBuf::new(),
             server: "http://localhost".to_string(),
             apikey: "testkey".to_string(),
+            default_timezone: None,
+            stored_login: None,
+            load_error: None,
         };
         assert!(config.logged_in());
         let config = Config {
             config_file: PathBuf::new(),
             server: String::new(),
             apikey: String::new(),
+            default_timezone: None,
+            stored_login: None,
+            load_error: None,
         };
         assert!(!config.logged_in());
     }
@@ -126,9 +257,108 @@ mod tests {
             config_file: PathBuf::new(),
             server: "http://localhost".to_string(),
             apikey: "testkey".to_string(),
+            default_timezone: None,
+            stored_login: None,
+            load_error: None,
         };
         config.logout();
         assert!(config.server.is_empty());
         assert!(config.apikey.is_empty());
     }
+
+    #[test]
+    fn test_complete_from_netrc() {
+        let entries = netrc::parse_netrc(
+            "machine immich.example.com login immichctl password key1\n\
+             machine other.example.com login immichctl password key2 account http://other:2283/",
+        );
+        let config_dir = tempfile::tempdir().unwrap();
+        let config_path = config_dir.path().join("config.json");
+
+        // without server, the first entry with login immichctl is used
+        let mut config = Config::load(&config_path);
+        assert!(config.complete_from_netrc(&entries));
+        assert_eq!(config.server, "https://immich.example.com");
+        assert_eq!(config.apikey, "key1");
+        // netrc values are not saved
+        config.save().unwrap();
+        assert!(!Config::load(&config_path).logged_in());
+
+        // the configured server selects the entry by host and is kept
+        let mut config = Config::load(&config_path);
+        config.server = "http://other.example.com".to_string();
+        assert!(config.complete_from_netrc(&entries));
+        assert_eq!(config.server, "http://other.example.com");
+        assert_eq!(config.apikey, "key2");
+        config.save().unwrap();
+        assert_eq!(Config::load(&config_path).apikey, "");
+
+        // complete config is not changed
+        let mut config = Config::load(&config_path);
+        config.set_login("http://localhost", "testkey");
+        assert!(!config.complete_from_netrc(&entries));
+        config.save().unwrap();
+        assert_eq!(Config::load(&config_path).apikey, "testkey");
+    }
+
+    #[test]
+    fn test_expand_env_vars() {
+        let lookup = |var: &str| match var {
+            "IMMICH_HOST" => Some("https://immich.example.com".to_string()),
+            "PORT" => Some("2283".to_string()),
+            _ => None,
+        };
+        let expand = |value| expand_env_vars(value, lookup).map_err(|e| e.to_string());
+        assert_eq!(
+            expand("https://immich.example.com"),
+            Ok("https://immich.example.com".to_string())
+        );
+        assert_eq!(
+            expand("${IMMICH_HOST}:${PORT}/"),
+            Ok("https://immich.example.com:2283/".to_string())
+        );
+        assert_eq!(expand("http://$host"), Ok("http://$host".to_string()));
+        assert_eq!(
+            expand("${UNDEFINED}/"),
+            Err("Environment variable 'UNDEFINED' used in '${UNDEFINED}/' is not set".to_string())
+        );
+        assert_eq!(
+            expand("${IMMICH_HOST"),
+            Err("Unterminated '${' in '${IMMICH_HOST'".to_string())
+        );
+        assert_eq!(
+            expand("http://${}"),
+            Err("Empty variable reference '${}' in 'http://${}'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_invalid_config_is_reported_and_not_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.json");
+        let contents = r#"{"server": "http://localhost", "apikey": "testkey", "default_timezone": "Mars/Olympus"}"#;
+        fs::write(&config_path, contents).unwrap();
+
+        let mut config = Config::load(&config_path);
+        assert!(!config.logged_in());
+        assert!(
+            config
+                .load_error()
+                .unwrap()
+                .starts_with(&format!("Invalid config file '{}'", config_path.display()))
+        );
+        // saving the empty fallback would lose server and API key
+        config.default_timezone = Some(TimeZoneValue::Iana("Europe/Berlin"));
+        assert!(config.save().is_err());
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), contents);
+    }
+
+    #[test]
+    fn test_server_url() {
+        let mut config = Config::load(&temp_config_path());
+        config.set_login("https://immich.example.com/", "key");
+        assert_eq!(config.server_url().unwrap(), "https://immich.example.com");
+        config.set_login("${IMMICHCTL_TEST_UNDEFINED_VAR}", "key");
+        assert!(config.server_url().is_err());
+    }
 }
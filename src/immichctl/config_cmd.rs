@@ -0,0 +1,115 @@
+use super::ImmichCtl;
+use super::output::OutputMode;
+use super::timezone::TimeZoneValue;
+use anyhow::Result;
+use serde::Serialize;
+use std::path::Path;
+
+/// Effective settings as printed by `config show`. The api key itself is never included.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+struct ConfigInfo<'a> {
+    config_file: &'a Path,
+    selection_file: &'a Path,
+    history_file: &'a Path,
+    server: &'a str,
+    apikey_set: bool,
+    batch_size: usize,
+    read_concurrency: usize,
+    write_concurrency: usize,
+    default_timezone: Option<TimeZoneValue>,
+    request_id: &'a str,
+}
+
+impl ImmichCtl {
+    /// Print the resolved configuration (files, server, whether an api key is set).
+    pub fn config_show(&self, format: OutputMode) -> Result<()> {
+        let info = self.config_info();
+        match format {
+            OutputMode::Json => self.print_json(&info)?,
+            OutputMode::Text => {
+                println!("Config file:    {}", info.config_file.display());
+                println!("Selection file: {}", info.selection_file.display());
+                println!("History file:   {}", info.history_file.display());
+                println!(
+                    "Server:         {}",
+                    if info.server.is_empty() {
+                        "<not logged in>"
+                    } else {
+                        info.server
+                    }
+                );
+                println!(
+                    "API key:        {}",
+                    if info.apikey_set {
+                        "<set>"
+                    } else {
+                        "<not set>"
+                    }
+                );
+                println!("Batch size:     {}", info.batch_size);
+                println!(
+                    "Concurrency:    {} reads, {} writes",
+                    info.read_concurrency, info.write_concurrency
+                );
+                match info.default_timezone {
+                    Some(tz) => println!("Timezone:       {}", tz),
+                    None => println!("Timezone:       <derived from asset metadata>"),
+                }
+                println!("Request id:     {}", info.request_id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Save `timezone` as default for assets without EXIF timezone, `None` removes it.
+    pub fn config_set_timezone(&mut self, timezone: Option<TimeZoneValue>) -> Result<()> {
+        self.config.default_timezone = timezone;
+        self.config.save()?;
+        self.assume_tz = timezone;
+        match timezone {
+            Some(tz) => eprintln!("Default timezone set to {}.", tz),
+            None => eprintln!("Default timezone removed."),
+        }
+        Ok(())
+    }
+
+    fn config_info(&self) -> ConfigInfo<'_> {
+        ConfigInfo {
+            config_file: self.config.config_file(),
+            selection_file: &self.assets_file,
+            history_file: &self.history_file,
+            server: &self.config.server,
+            apikey_set: !self.config.apikey.is_empty(),
+            batch_size: self.batch_size.get(),
+            read_concurrency: self.read_concurrency.get(),
+            write_concurrency: self.write_concurrency.get(),
+            default_timezone: self.config.default_timezone,
+            request_id: &self.request_id,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::immichctl::ImmichCtl;
+    use crate::immichctl::tests::create_immichctl_with_server;
+
+    #[tokio::test]
+    async fn test_config_info_redacts_apikey() {
+        let (ctl, server) = create_immichctl_with_server().await;
+        let info = ctl.config_info();
+        assert_eq!(info.server, server.url());
+        assert!(info.apikey_set);
+        assert_eq!(info.selection_file, ctl.assets_file);
+        let json = serde_json::to_string(&info).unwrap();
+        // neither the field nor the value ("apikey") of the key is printed
+        assert!(!json.contains("\"apikey\""));
+
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+        let info = ctl.config_info();
+        assert!(!info.apikey_set);
+        assert_eq!(info.config_file, config_dir.path().join("config.json"));
+        assert_eq!(info.history_file, config_dir.path().join("history.jsonl"));
+    }
+}
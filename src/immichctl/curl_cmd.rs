@@ -1,5 +1,6 @@
-use anyhow::{Error, Result, bail};
+use anyhow::{Context, Error, Result, bail};
 use progenitor_client::{ClientHooks, ClientInfo, OperationInfo};
+use reqwest::header::HeaderValue;
 
 use super::Client;
 use super::ImmichCtl;
@@ -18,24 +19,41 @@ pub enum CurlMethod {
 }
 
 impl ImmichCtl {
-    pub async fn curl(&self, path: &str, method: CurlMethod, data: &Option<String>) -> Result<()> {
+    pub async fn curl(
+        &self,
+        path: &str,
+        method: CurlMethod,
+        data: &Option<String>,
+        api_version: &Option<String>,
+        fields: &[String],
+    ) -> Result<()> {
         self.assert_logged_in()?;
+        let api_version = Self::api_version_header(api_version)?;
 
-        match method {
-            CurlMethod::Get => self.curl_get(path).await,
-            CurlMethod::Post => self.curl_post(path, data).await,
-            CurlMethod::Put => self.curl_put(path, data).await,
-            CurlMethod::Delete => self.curl_delete(path, data).await,
+        if self.dry_run && !matches!(method, CurlMethod::Get) {
+            eprintln!("Dry run: would send {:?} request to '{}'.", method, path);
+            if let Some(json) = Self::parse_data_to_json(data) {
+                println!("{}", json);
+            }
+            return Ok(());
         }
+
+        let request = match method {
+            CurlMethod::Get => self.curl_get(path, api_version)?,
+            CurlMethod::Post => self.curl_post(path, data, api_version)?,
+            CurlMethod::Put => self.curl_put(path, data, api_version)?,
+            CurlMethod::Delete => self.curl_delete(path, data, api_version)?,
+        };
+        self.exec_request(request, fields).await
     }
 
-    async fn curl_get(&self, path: &str) -> Result<()> {
+    fn curl_get(&self, path: &str, api_version: HeaderValue) -> Result<reqwest::Request> {
         let immich = self.immich()?;
         let url = format!("{}/{}", immich.baseurl, path);
         let mut header_map = ::reqwest::header::HeaderMap::with_capacity(1usize);
         header_map.append(
             ::reqwest::header::HeaderName::from_static("api-version"),
-            ::reqwest::header::HeaderValue::from_static(Client::api_version()),
+            api_version,
         );
 
         let request = immich
@@ -47,16 +65,21 @@ impl ImmichCtl {
             )
             .headers(header_map)
             .build()?;
-        self.exec_request(request).await
+        Ok(request)
     }
 
-    async fn curl_post(&self, path: &str, data: &Option<String>) -> Result<()> {
+    fn curl_post(
+        &self,
+        path: &str,
+        data: &Option<String>,
+        api_version: HeaderValue,
+    ) -> Result<reqwest::Request> {
         let immich = self.immich()?;
         let url = format!("{}/{}", immich.baseurl, path);
         let mut header_map = ::reqwest::header::HeaderMap::with_capacity(1usize);
         header_map.append(
             ::reqwest::header::HeaderName::from_static("api-version"),
-            ::reqwest::header::HeaderValue::from_static(Client::api_version()),
+            api_version,
         );
 
         let mut request_builder = immich
@@ -72,17 +95,21 @@ impl ImmichCtl {
             request_builder = request_builder.json(&json);
         }
 
-        let request = request_builder.build()?;
-        self.exec_request(request).await
+        Ok(request_builder.build()?)
     }
 
-    async fn curl_put(&self, path: &str, data: &Option<String>) -> Result<()> {
+    fn curl_put(
+        &self,
+        path: &str,
+        data: &Option<String>,
+        api_version: HeaderValue,
+    ) -> Result<reqwest::Request> {
         let immich = self.immich()?;
         let url = format!("{}/{}", immich.baseurl, path);
         let mut header_map = ::reqwest::header::HeaderMap::with_capacity(1usize);
         header_map.append(
             ::reqwest::header::HeaderName::from_static("api-version"),
-            ::reqwest::header::HeaderValue::from_static(Client::api_version()),
+            api_version,
         );
 
         let mut request_builder = immich
@@ -98,17 +125,21 @@ impl ImmichCtl {
             request_builder = request_builder.json(&json);
         }
 
-        let request = request_builder.build()?;
-        self.exec_request(request).await
+        Ok(request_builder.build()?)
     }
 
-    async fn curl_delete(&self, path: &str, data: &Option<String>) -> Result<()> {
+    fn curl_delete(
+        &self,
+        path: &str,
+        data: &Option<String>,
+        api_version: HeaderValue,
+    ) -> Result<reqwest::Request> {
         let immich = self.immich()?;
         let url = format!("{}/{}", immich.baseurl, path);
         let mut header_map = ::reqwest::header::HeaderMap::with_capacity(1usize);
         header_map.append(
             ::reqwest::header::HeaderName::from_static("api-version"),
-            ::reqwest::header::HeaderValue::from_static(Client::api_version()),
+            api_version,
         );
 
         let mut request_builder = immich
@@ -124,11 +155,21 @@ impl ImmichCtl {
             request_builder = request_builder.json(&json);
         }
 
-        let request = request_builder.build()?;
-        self.exec_request(request).await
+        Ok(request_builder.build()?)
+    }
+
+    /// `api-version` header value, defaults to the API version of the generated client.
+    fn api_version_header(api_version: &Option<String>) -> Result<HeaderValue> {
+        match api_version {
+            Some(v) => {
+                HeaderValue::from_str(v).with_context(|| format!("Invalid API version '{}'", v))
+            }
+            None => Ok(HeaderValue::from_static(Client::api_version())),
+        }
     }
 
-    async fn exec_request(&self, request: reqwest::Request) -> Result<()> {
+    /// Send `request` and print the response body, only the values at `fields` if given.
+    async fn exec_request(&self, request: reqwest::Request, fields: &[String]) -> Result<()> {
         let immich = self.immich()?;
         let info = OperationInfo {
             operation_id: "curl",
@@ -144,14 +185,26 @@ impl ImmichCtl {
         match response.status().as_u16() {
             200u16..300u16 => {
                 let body = response.bytes().await?.to_vec();
-                // Print response body as formatted JSON if possible
+                // Print response body as JSON (pretty or compact) if possible
                 match serde_json::from_slice::<serde_json::Value>(&body) {
+                    Ok(json) if !fields.is_empty() => {
+                        // resolve all fields before printing anything
+                        let values = fields
+                            .iter()
+                            .map(|field| Self::select_field(&json, field))
+                            .collect::<Result<Vec<_>>>()?;
+                        for value in values {
+                            match value {
+                                serde_json::Value::String(s) => println!("{}", s),
+                                value => self.print_json(value)?,
+                            }
+                        }
+                    }
                     Ok(json) => {
-                        println!(
-                            "{}",
-                            serde_json::to_string_pretty(&json)
-                                .unwrap_or_else(|_| String::from_utf8_lossy(&body).to_string())
-                        );
+                        self.print_json(&json)?;
+                    }
+                    Err(_) if !fields.is_empty() => {
+                        bail!("Response is not json, --field can't be applied")
                     }
                     Err(_) => {
                         // Fallback: print as plain text
@@ -166,6 +219,47 @@ impl ImmichCtl {
         }
     }
 
+    /// Value at the dotted `path` in `json`, e.g. `assets.items[0].id`. Array elements are
+    /// selected by `[index]` or a numeric segment, a leading `.` is optional.
+    fn select_field<'a>(json: &'a serde_json::Value, path: &str) -> Result<&'a serde_json::Value> {
+        let mut value = json;
+        let trimmed = path.strip_prefix('.').unwrap_or(path);
+        if trimmed.is_empty() {
+            return Ok(value);
+        }
+        for segment in trimmed.split('.') {
+            let (name, indexes) = match segment.find('[') {
+                Some(i) => segment.split_at(i),
+                None => (segment, ""),
+            };
+            if !name.is_empty() {
+                value = match (value, name.parse::<usize>()) {
+                    (serde_json::Value::Array(items), Ok(index)) => items.get(index),
+                    _ => value.get(name),
+                }
+                .with_context(|| format!("Field '{}' not found in response", path))?;
+            }
+            let mut rest = indexes;
+            while !rest.is_empty() {
+                let Some((index, tail)) = rest
+                    .strip_prefix('[')
+                    .and_then(|r| r.split_once(']'))
+                    .and_then(|(i, tail)| Some((i.trim().parse::<usize>().ok()?, tail)))
+                else {
+                    bail!(
+                        "Invalid field '{}', expected a dotted path like assets.items[0].id",
+                        path
+                    );
+                };
+                value = value
+                    .get(index)
+                    .with_context(|| format!("Field '{}' not found in response", path))?;
+                rest = tail;
+            }
+        }
+        Ok(value)
+    }
+
     /// Parse `--data` into a JSON value.
     ///
     /// Accepts three forms:
@@ -230,6 +324,47 @@ mod curl_cmd_tests {
         assert_eq!(v, serde_json::Value::String("hello".to_string()));
     }
 
+    #[test]
+    fn api_version_header() {
+        assert_eq!(
+            ImmichCtl::api_version_header(&None).unwrap(),
+            Client::api_version()
+        );
+        assert_eq!(
+            ImmichCtl::api_version_header(&Some("2.5.0".to_string())).unwrap(),
+            "2.5.0"
+        );
+        let err = ImmichCtl::api_version_header(&Some("2.5\n0".to_string())).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid API version '2.5\n0'");
+    }
+
+    #[test]
+    fn select_field() {
+        let json = serde_json::json!({
+            "assets": {"items": [{"id": "a1", "tags": ["x", "y"]}, {"id": "a2"}], "total": 2},
+            "matrix": [[1, 2], [3, 4]]
+        });
+        let field = |path| ImmichCtl::select_field(&json, path);
+        assert_eq!(field("assets.items[0].id").unwrap(), "a1");
+        assert_eq!(field(".assets.items.1.id").unwrap(), "a2");
+        assert_eq!(field("assets.total").unwrap(), 2);
+        assert_eq!(field("assets.items[0].tags[1]").unwrap(), "y");
+        assert_eq!(field("matrix[1][0]").unwrap(), 3);
+        assert_eq!(field(".").unwrap(), &json);
+        assert_eq!(
+            field("assets.items[2].id").unwrap_err().to_string(),
+            "Field 'assets.items[2].id' not found in response"
+        );
+        assert_eq!(
+            field("assets.count").unwrap_err().to_string(),
+            "Field 'assets.count' not found in response"
+        );
+        assert_eq!(
+            field("assets.items[x]").unwrap_err().to_string(),
+            "Invalid field 'assets.items[x]', expected a dotted path like assets.items[0].id"
+        );
+    }
+
     #[test]
     fn parse_none() {
         let data: Option<String> = None;
@@ -1,16 +1,81 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::num::NonZeroUsize;
 use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 
-use anyhow::{Context, Result};
-use futures::TryStreamExt;
+use anyhow::{Context, Result, bail};
+use base64::Engine;
+use futures::{StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
 use tokio::io::AsyncWriteExt;
 use uuid::Uuid;
 
 use super::ImmichCtl;
-use super::assets::Assets;
-use super::types::{DownloadArchiveDto, DownloadInfoDto};
+use super::api_error::ApiContext;
+use super::types::{AssetResponseDto, DownloadArchiveDto, DownloadInfoDto};
+
+/// Sidecar file in the download directory listing the ids of assets that failed
+/// to download, one per line. Read by `--retry-failed`.
+const FAILED_FILE: &str = "download.failed";
+
+/// Manifest in the download directory with the local file and download status of
+/// each asset. Assets that are `done` or `verified` are skipped on the next run.
+const MANIFEST_FILE: &str = "download.manifest.json";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum DownloadStatus {
+    Pending,
+    Done,
+    Failed,
+    /// Downloaded and the checksum matched
+    Verified,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ManifestEntry {
+    /// File name relative to the download directory
+    path: String,
+    status: DownloadStatus,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    assets: BTreeMap<Uuid, ManifestEntry>,
+}
+
+impl Manifest {
+    /// Manifest of `dir`, empty if there is none yet.
+    fn load(dir: &Path) -> Result<Manifest> {
+        let file = dir.join(MANIFEST_FILE);
+        if !file.exists() {
+            return Ok(Manifest::default());
+        }
+        let content = std::fs::read_to_string(&file)
+            .with_context(|| format!("Could not read '{}'", file.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid download manifest '{}'", file.display()))
+    }
+
+    /// Write the manifest to `dir`. Written to a temp file first and renamed, so an
+    /// interruption never leaves a truncated manifest behind.
+    fn save(&self, dir: &Path) -> Result<()> {
+        let file = dir.join(MANIFEST_FILE);
+        let temp = dir.join(format!("{}.tmp", MANIFEST_FILE));
+        std::fs::write(&temp, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Could not write '{}'", temp.display()))?;
+        std::fs::rename(&temp, &file)
+            .with_context(|| format!("Could not write '{}'", file.display()))
+    }
+
+    fn set_status(&mut self, id: &Uuid, status: DownloadStatus) {
+        if let Some(entry) = self.assets.get_mut(id) {
+            entry.status = status;
+        }
+    }
+}
 
 /// Shared progress counters updated from both the async download loop and
 /// the blocking extract task. Printed by [`Progress::render`] on a single
@@ -90,29 +155,153 @@ impl ImmichCtl {
     /// `originalPath` (the Immich storage-template filename), with any
     /// directory components dropped. On filename collision a numeric suffix
     /// is appended (e.g. `IMG.jpg`, `IMG (1).jpg`).
-    pub async fn assets_download(&self, dir: &Path) -> Result<()> {
-        let sel = Assets::load(&self.assets_file);
-        if sel.is_empty() {
-            eprintln!("Selection is empty, nothing to download.");
+    ///
+    /// Up to `concurrency` archives are downloaded at the same time. A failed
+    /// archive doesn't abort the download: the ids of its assets are written to
+    /// [`FAILED_FILE`] in `dir` and the command fails after all archives were
+    /// processed. With `retry_failed` only the selected assets listed in that
+    /// file are downloaded.
+    ///
+    /// With `verify` the SHA-1 of each downloaded file is compared to the asset
+    /// checksum. Corrupt files are deleted and handled like failed downloads.
+    /// Edited assets can't be verified because the checksum is the one of the
+    /// original file.
+    ///
+    /// The status of each asset is tracked in [`MANIFEST_FILE`] in `dir` and
+    /// updated after each archive. Assets that are `done` or `verified` and whose
+    /// file still exists are skipped, so an interrupted download can be resumed
+    /// by running it again. With `verify`, skipped `done` files are verified
+    /// locally and downloaded again on mismatch. `force` downloads all assets.
+    pub async fn assets_download(
+        &self,
+        dir: &Path,
+        retry_failed: bool,
+        concurrency: NonZeroUsize,
+        verify: bool,
+        force: bool,
+        live_videos: bool,
+    ) -> Result<()> {
+        let mut sel = self.load_assets();
+        let failed_file = dir.join(FAILED_FILE);
+        let failed_ids = if retry_failed {
+            Some(read_failed_ids(&failed_file)?)
+        } else {
+            None
+        };
+        // paired videos of live photos, they are not part of the selection
+        let mut video_ids: Vec<Uuid> = if live_videos {
+            sel.iter_assets()
+                .filter_map(live_photo_video_id)
+                .filter(|id| failed_ids.as_ref().is_none_or(|failed| failed.contains(id)))
+                .collect()
+        } else {
+            vec![]
+        };
+        if let Some(failed_ids) = &failed_ids {
+            sel.retain(|a| failed_ids.contains(&a.id));
+        }
+        if sel.is_empty() && video_ids.is_empty() {
+            if retry_failed {
+                eprintln!("No failed downloads of the selection to retry.");
+            } else {
+                eprintln!("Selection is empty, nothing to download.");
+            }
             return Ok(());
         }
 
         std::fs::create_dir_all(dir)
             .with_context(|| format!("Could not create directory '{}'", dir.display()))?;
 
+        let mut manifest = Manifest::load(dir)?;
+        let mut skipped = 0usize;
+        if !force {
+            let mut completed = HashSet::new();
+            for asset in sel.iter_assets() {
+                let Some(entry) = manifest.assets.get_mut(&asset.id) else {
+                    continue;
+                };
+                let path = dir.join(&entry.path);
+                match entry.status {
+                    DownloadStatus::Verified if path.exists() => {}
+                    DownloadStatus::Done if path.exists() && verify && !asset.is_edited => {
+                        if sha1_base64(&path)? != asset.checksum {
+                            self.eprint_asset_warning(
+                                &format!(
+                                    "Checksum mismatch for downloaded asset '{}', downloading it again.",
+                                    asset.id
+                                ),
+                                &asset.id,
+                            );
+                            std::fs::remove_file(&path).with_context(|| {
+                                format!("Could not delete '{}'", path.display())
+                            })?;
+                            continue;
+                        }
+                        entry.status = DownloadStatus::Verified;
+                    }
+                    DownloadStatus::Done if path.exists() => {}
+                    _ => continue,
+                }
+                completed.insert(asset.id);
+            }
+            skipped = completed.len();
+            sel.retain(|a| !completed.contains(&a.id));
+            video_ids.retain(|id| match manifest.assets.get(id) {
+                Some(entry) => {
+                    let done = matches!(
+                        entry.status,
+                        DownloadStatus::Done | DownloadStatus::Verified
+                    ) && dir.join(&entry.path).exists();
+                    if done {
+                        skipped += 1;
+                    }
+                    !done
+                }
+                None => true,
+            });
+        }
+        if skipped > 0 {
+            eprintln!(
+                "Skipping {} asset(s) already downloaded according to '{}'.",
+                skipped,
+                dir.join(MANIFEST_FILE).display()
+            );
+        }
+        if sel.is_empty() && video_ids.is_empty() {
+            manifest.save(dir)?;
+            if failed_file.exists() {
+                std::fs::remove_file(&failed_file)
+                    .with_context(|| format!("Could not remove '{}'", failed_file.display()))?;
+            }
+            eprintln!("All selected assets are downloaded to {}.", dir.display());
+            return Ok(());
+        }
+
         // Lookup table: asset id -> filename derived from `originalPath`.
         // We deliberately use the basename of `originalPath` (the Immich
         // storage template name, e.g. `20260602-105253.jpg`) rather than
         // `originalFileName` (the original camera filename), so that the
         // downloaded files match what Immich has on disk.
-        let mut asset_ids = Vec::with_capacity(sel.len());
+        let videos = self.live_photo_videos(&video_ids).await?;
+        let mut asset_ids = Vec::with_capacity(sel.len() + videos.len());
         let name_by_id: HashMap<Uuid, String> = sel
             .iter_assets()
+            .chain(&videos)
             .map(|a| {
                 asset_ids.push(a.id);
                 (a.id, basename_of(&a.original_path).to_string())
             })
             .collect();
+        // Lookup table: asset id -> expected checksum of verifiable assets.
+        let checksum_by_id: HashMap<Uuid, &str> = if verify {
+            sel.iter_assets()
+                .chain(&videos)
+                .filter(|a| !a.is_edited)
+                .map(|a| (a.id, a.checksum.as_str()))
+                .collect()
+        } else {
+            HashMap::new()
+        };
 
         let info_dto = DownloadInfoDto {
             asset_ids,
@@ -121,8 +310,8 @@ impl ImmichCtl {
         let info = self
             .immich()?
             .get_download_info(None, None, &info_dto)
-            .await
-            .context("Could not retrieve download info")?
+            .api_context("Could not retrieve download info")
+            .await?
             .into_inner();
 
         let total_archives = info.archives.len();
@@ -132,8 +321,10 @@ impl ImmichCtl {
         // Assign final destination filenames upfront for all archives.
         // Deduplicate file names by appending a suffix if the same name appears more than once.
         // This should not happen if Immich storage template is well configured.
+        // Assets of a previous run keep their file name from the manifest.
         let archive_filenames: Vec<Vec<String>> = {
             let mut used: HashMap<String, u32> = HashMap::new();
+            let taken: HashSet<String> = manifest.assets.values().map(|e| e.path.clone()).collect();
             info.archives
                 .iter()
                 .map(|archive| {
@@ -141,13 +332,32 @@ impl ImmichCtl {
                         .asset_ids
                         .iter()
                         .map(|id| {
+                            if let Some(entry) = manifest.assets.get(id) {
+                                return entry.path.clone();
+                            }
                             let base = name_by_id.get(id).map(|s| s.as_str()).unwrap_or("unknown");
-                            unique_name(&mut used, base)
+                            let mut name = unique_name(&mut used, base);
+                            while taken.contains(&name) {
+                                name = unique_name(&mut used, base);
+                            }
+                            name
                         })
                         .collect()
                 })
                 .collect()
         };
+        for (archive, filenames) in info.archives.iter().zip(&archive_filenames) {
+            for (id, path) in archive.asset_ids.iter().zip(filenames) {
+                manifest.assets.insert(
+                    *id,
+                    ManifestEntry {
+                        path: path.clone(),
+                        status: DownloadStatus::Pending,
+                    },
+                );
+            }
+        }
+        manifest.save(dir)?;
 
         let progress = Progress::new(total_bytes, total_files);
 
@@ -173,63 +383,47 @@ impl ImmichCtl {
             })
         });
 
-        let mut written = 0usize;
-
-        for (i, (archive, filenames)) in info.archives.iter().zip(archive_filenames).enumerate() {
-            let dto = DownloadArchiveDto {
-                asset_ids: archive.asset_ids.clone(),
-                edited: Some(true),
-            };
-            let resp = self
-                .immich()?
-                .download_archive(None, None, &dto)
-                .await
-                .with_context(|| {
-                    format!("Could not download archive {}/{}", i + 1, total_archives)
-                })?;
-
-            // Stream the response into a temp file so the in-memory footprint
-            // is just tokio's copy buffer (~8 KiB), not the whole archive.
-            let temp =
-                tempfile::NamedTempFile::new().context("Could not create temp file for archive")?;
-            let temp_path = temp.path().to_path_buf();
-            let mut out = tokio::fs::File::from_std(
-                temp.reopen()
-                    .context("Could not open temp file for writing")?,
-            );
-            let mut byte_stream = resp.into_inner_stream().map_err(std::io::Error::other);
-            while let Some(chunk) = byte_stream
-                .try_next()
-                .await
-                .with_context(|| format!("Could not read archive {}/{}", i + 1, total_archives))?
-            {
-                out.write_all(&chunk).await.with_context(|| {
-                    format!("Could not write archive {}/{}", i + 1, total_archives)
-                })?;
-                progress
-                    .downloaded
-                    .fetch_add(chunk.len() as u64, Ordering::Relaxed);
+        let mut downloads =
+            futures::stream::iter(info.archives.iter().zip(archive_filenames).enumerate())
+                .map(|(i, (archive, filenames))| {
+                    let progress = &progress;
+                    let checksums = archive
+                        .asset_ids
+                        .iter()
+                        .map(|id| checksum_by_id.get(id).map(|c| c.to_string()))
+                        .collect();
+                    async move {
+                        let res = self
+                            .download_archive_into(
+                                dir,
+                                &archive.asset_ids,
+                                filenames,
+                                checksums,
+                                progress,
+                                (i + 1, total_archives),
+                            )
+                            .await;
+                        (archive, res)
+                    }
+                })
+                .buffer_unordered(concurrency.get());
+        // Record each finished archive in the manifest right away, so an
+        // interrupted download resumes after the last completed archive.
+        let mut results = Vec::with_capacity(total_archives);
+        while let Some((archive, res)) = downloads.next().await {
+            for id in &archive.asset_ids {
+                let status = match &res {
+                    Ok((_, corrupt)) if corrupt.contains(id) => DownloadStatus::Failed,
+                    Ok(_) if checksum_by_id.contains_key(id) => DownloadStatus::Verified,
+                    Ok(_) => DownloadStatus::Done,
+                    Err(_) => DownloadStatus::Failed,
+                };
+                manifest.set_status(id, status);
             }
-            out.flush().await.ok();
-            drop(out);
-
-            // Extract on a blocking thread — `zip` is synchronous and entry
-            // streaming uses `std::io::copy`, which calls into the OS.
-            let dir_owned = dir.to_path_buf();
-            let extracted_counter = progress.extracted.clone();
-
-            let count = tokio::task::spawn_blocking(move || -> Result<_> {
-                let file = std::fs::File::open(&temp_path)
-                    .context("Could not open temp archive for extraction")?;
-                extract_zip(file, &dir_owned, &filenames, &extracted_counter)
-            })
-            .await
-            .context("ZIP extraction task failed")?
-            .with_context(|| format!("Could not extract archive {}/{}", i + 1, total_archives))?;
-            written += count;
-            // `temp` drops here, removing the temp archive from disk.
-            drop(temp);
+            manifest.save(dir)?;
+            results.push((archive, res));
         }
+        drop(downloads);
 
         // Abort the painter (via drop guard) and render the final 100% state
         // before emitting the trailing newline.
@@ -237,11 +431,298 @@ impl ImmichCtl {
         progress.render();
         eprintln!();
 
-        eprintln!("Downloaded {} asset(s) to {}.", written, dir.display());
-        Ok(())
+        let mut written = 0usize;
+        let mut corrupt_count = 0usize;
+        let not_verified = sel.len() + videos.len() - checksum_by_id.len();
+        let mut failed_ids = Vec::new();
+        for (archive, res) in results {
+            match res {
+                Ok((count, corrupt)) => {
+                    written += count - corrupt.len();
+                    corrupt_count += corrupt.len();
+                    for id in corrupt {
+                        self.eprint_asset_warning(
+                            &format!(
+                                "Checksum mismatch for asset '{}', deleted the downloaded file.",
+                                id
+                            ),
+                            &id,
+                        );
+                        failed_ids.push(id);
+                    }
+                }
+                Err(err) => {
+                    self.eprint_warning(&format!("{:#}", err));
+                    failed_ids.extend(archive.asset_ids.iter().copied());
+                }
+            }
+        }
+
+        if failed_ids.is_empty() {
+            if failed_file.exists() {
+                std::fs::remove_file(&failed_file)
+                    .with_context(|| format!("Could not remove '{}'", failed_file.display()))?;
+            }
+            eprintln!("Downloaded {} asset(s) to {}.", written, dir.display());
+            self.eprint_verify_summary(verify, corrupt_count, not_verified);
+            return Ok(());
+        }
+        write_failed_ids(&failed_file, &failed_ids)?;
+        eprintln!(
+            "Downloaded {} asset(s) to {}, {} failed.",
+            written,
+            dir.display(),
+            failed_ids.len()
+        );
+        self.eprint_verify_summary(verify, corrupt_count, not_verified);
+        bail!(
+            "Could not download {} asset(s), their ids are listed in '{}'. Run 'immichctl assets download --retry-failed' to retry.",
+            failed_ids.len(),
+            failed_file.display()
+        )
+    }
+
+    /// Fetch the paired video assets of live photos, they are needed for the file name
+    /// and checksum of the download.
+    async fn live_photo_videos(&self, ids: &[Uuid]) -> Result<Vec<AssetResponseDto>> {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let client = self.immich()?;
+        futures::stream::iter(ids)
+            .map(|id| async move {
+                client
+                    .get_asset_info(id, None, None)
+                    .with_api_context(|| format!("Could not retrieve live photo video '{}'", id))
+                    .await
+                    .map(|asset| asset.into_inner())
+            })
+            .buffered(self.read_concurrency.get())
+            .try_collect()
+            .await
+    }
+
+    /// Export all selected assets into the ZIP archive `zip_file`.
+    ///
+    /// Each original is fetched via `GET /assets/{id}/original` and streamed to a
+    /// temporary file, up to `concurrency` assets at the same time. The temp files
+    /// are copied into the archive in selection order as uncompressed entries
+    /// (photos and videos are already compressed), so neither downloads nor
+    /// entries are ever fully buffered in memory. Entries are named by the
+    /// asset's `originalFileName`, colliding names get a numeric suffix like in
+    /// [`ImmichCtl::assets_download`].
+    ///
+    /// An asset that can't be downloaded is skipped with a warning, the command
+    /// fails after the archive was completed with the other assets.
+    pub async fn assets_export_zip(
+        &self,
+        zip_file: &Path,
+        concurrency: NonZeroUsize,
+    ) -> Result<()> {
+        let sel = self.load_assets();
+        if sel.is_empty() {
+            eprintln!("Selection is empty, nothing to export.");
+            return Ok(());
+        }
+
+        let mut used = HashMap::new();
+        let entries: Vec<(Uuid, String)> = sel
+            .iter_assets()
+            .map(|a| (a.id, unique_name(&mut used, &a.original_file_name)))
+            .collect();
+        let file = std::fs::File::create(zip_file)
+            .with_context(|| format!("Could not create '{}'", zip_file.display()))?;
+        let mut zip = zip::ZipWriter::new(std::io::BufWriter::new(file));
+
+        let mut downloads = futures::stream::iter(&entries)
+            .map(|(id, name)| async move { (id, name, self.download_original(id).await) })
+            .buffered(concurrency.get());
+        let mut written = 0usize;
+        let mut failed = 0usize;
+        let mut i = 0;
+        while let Some((id, name, res)) = downloads.next().await {
+            match res {
+                Ok(temp) => {
+                    // `zip` is synchronous, write the entry on a blocking thread and
+                    // hand the writer back for the next entry.
+                    let name = name.clone();
+                    let res;
+                    (zip, res) = tokio::task::spawn_blocking(move || {
+                        let res = add_zip_entry(&mut zip, &name, temp.path());
+                        (zip, res)
+                    })
+                    .await
+                    .context("ZIP writer task failed")?;
+                    res.with_context(|| format!("Could not write '{}'", zip_file.display()))?;
+                    written += 1;
+                }
+                Err(err) => {
+                    self.eprint_asset_warning(&format!("{:#}", err), id);
+                    failed += 1;
+                }
+            }
+            self.eprint_progress_indicator(i, entries.len(), 10);
+            i += 1;
+        }
+        let mut out = zip
+            .finish()
+            .with_context(|| format!("Could not write '{}'", zip_file.display()))?;
+        std::io::Write::flush(&mut out)
+            .with_context(|| format!("Could not write '{}'", zip_file.display()))?;
+
+        if failed == 0 {
+            eprintln!("Exported {} asset(s) to {}.", written, zip_file.display());
+            return Ok(());
+        }
+        eprintln!(
+            "Exported {} asset(s) to {}, {} failed.",
+            written,
+            zip_file.display(),
+            failed
+        );
+        bail!("Could not export {} asset(s), see warnings above.", failed)
+    }
+
+    /// Stream the original file of asset `id` into a temp file, removed on drop.
+    async fn download_original(&self, id: &Uuid) -> Result<tempfile::NamedTempFile> {
+        let resp = self
+            .immich()?
+            .download_asset(id, None, None, None)
+            .with_api_context(|| format!("Could not download asset '{}'", id))
+            .await?;
+        let temp = tempfile::NamedTempFile::new().context("Could not create temp file")?;
+        let mut out = tokio::fs::File::from_std(
+            temp.reopen()
+                .context("Could not open temp file for writing")?,
+        );
+        let mut byte_stream = resp.into_inner_stream().map_err(std::io::Error::other);
+        while let Some(chunk) = byte_stream
+            .try_next()
+            .await
+            .with_context(|| format!("Could not download asset '{}'", id))?
+        {
+            out.write_all(&chunk)
+                .await
+                .context("Could not write temp file")?;
+        }
+        out.flush().await.context("Could not write temp file")?;
+        Ok(temp)
+    }
+
+    fn eprint_verify_summary(&self, verify: bool, corrupt: usize, not_verified: usize) {
+        if verify {
+            eprintln!(
+                "Checksum verification: {} corrupt file(s) deleted, {} edited asset(s) not verified.",
+                corrupt, not_verified
+            );
+        }
+    }
+
+    /// Download one archive (`part` is the 1-based index and the total number of
+    /// archives) and extract it into `dir`. `checksums[i]` is the expected checksum
+    /// of ZIP entry `i` if it should be verified.
+    ///
+    /// Returns the number of written files and the ids of assets whose file was
+    /// corrupt (and deleted).
+    async fn download_archive_into(
+        &self,
+        dir: &Path,
+        asset_ids: &[Uuid],
+        filenames: Vec<String>,
+        checksums: Vec<Option<String>>,
+        progress: &Progress,
+        part: (usize, usize),
+    ) -> Result<(usize, Vec<Uuid>)> {
+        let (i, total_archives) = part;
+        let dto = DownloadArchiveDto {
+            asset_ids: asset_ids.to_vec(),
+            edited: Some(true),
+        };
+        let resp = self
+            .immich()?
+            .download_archive(None, None, &dto)
+            .with_api_context(|| format!("Could not download archive {}/{}", i, total_archives))
+            .await?;
+
+        // Stream the response into a temp file so the in-memory footprint
+        // is just tokio's copy buffer (~8 KiB), not the whole archive.
+        let temp =
+            tempfile::NamedTempFile::new().context("Could not create temp file for archive")?;
+        let temp_path = temp.path().to_path_buf();
+        let mut out = tokio::fs::File::from_std(
+            temp.reopen()
+                .context("Could not open temp file for writing")?,
+        );
+        let mut byte_stream = resp.into_inner_stream().map_err(std::io::Error::other);
+        while let Some(chunk) = byte_stream
+            .try_next()
+            .await
+            .with_context(|| format!("Could not read archive {}/{}", i, total_archives))?
+        {
+            out.write_all(&chunk)
+                .await
+                .with_context(|| format!("Could not write archive {}/{}", i, total_archives))?;
+            progress
+                .downloaded
+                .fetch_add(chunk.len() as u64, Ordering::Relaxed);
+        }
+        out.flush().await.ok();
+        drop(out);
+
+        // Extract on a blocking thread — `zip` is synchronous and entry
+        // streaming uses `std::io::copy`, which calls into the OS.
+        let dir_owned = dir.to_path_buf();
+        let extracted_counter = progress.extracted.clone();
+
+        let (count, corrupt) = tokio::task::spawn_blocking(move || -> Result<_> {
+            let file = std::fs::File::open(&temp_path)
+                .context("Could not open temp archive for extraction")?;
+            let count = extract_zip(file, &dir_owned, &filenames, &extracted_counter)?;
+            let corrupt = verify_files(&dir_owned, &filenames, &checksums)?;
+            Ok((count, corrupt))
+        })
+        .await
+        .context("ZIP extraction task failed")?
+        .with_context(|| format!("Could not extract archive {}/{}", i, total_archives))?;
+        // `temp` drops here, removing the temp archive from disk.
+        drop(temp);
+        Ok((count, corrupt.into_iter().map(|i| asset_ids[i]).collect()))
     }
 }
 
+/// Asset ids listed in the `failed_file` of a previous download.
+fn read_failed_ids(failed_file: &Path) -> Result<HashSet<Uuid>> {
+    if !failed_file.exists() {
+        bail!(
+            "No failed downloads recorded in '{}'.",
+            failed_file.display()
+        );
+    }
+    let content = std::fs::read_to_string(failed_file)
+        .with_context(|| format!("Could not read '{}'", failed_file.display()))?;
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            Uuid::parse_str(line).with_context(|| {
+                format!("Invalid asset id '{}' in '{}'", line, failed_file.display())
+            })
+        })
+        .collect()
+}
+
+fn write_failed_ids(failed_file: &Path, ids: &[Uuid]) -> Result<()> {
+    let content: String = ids.iter().map(|id| format!("{}\n", id)).collect();
+    std::fs::write(failed_file, content)
+        .with_context(|| format!("Could not write '{}'", failed_file.display()))
+}
+
+/// Id of the paired video of a live photo, if any.
+fn live_photo_video_id(asset: &AssetResponseDto) -> Option<Uuid> {
+    Uuid::parse_str(asset.live_photo_video_id.as_deref()?).ok()
+}
+
 /// Return the last path component of `p`.
 ///
 /// Handles both Unix (`/`) and Windows-style (`\`) separators since
@@ -296,6 +777,58 @@ fn extract_zip(
     Ok(written)
 }
 
+/// Append the file at `path` as uncompressed entry `name` to `zip`, streamed via
+/// [`std::io::copy`].
+fn add_zip_entry<W: std::io::Write + std::io::Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    name: &str,
+    path: &Path,
+) -> Result<()> {
+    let mut file = std::fs::File::open(path)
+        .with_context(|| format!("Could not open '{}'", path.display()))?;
+    let size = file.metadata()?.len();
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Stored)
+        .large_file(size >= u32::MAX as u64);
+    zip.start_file(name, options)?;
+    std::io::copy(&mut file, zip)?;
+    Ok(())
+}
+
+/// Compare the checksum of each written file with `checksums` (same index as
+/// `filenames`, `None` = not verified) and delete corrupt files. Returns the
+/// indexes of the corrupt files.
+fn verify_files(
+    dir: &Path,
+    filenames: &[String],
+    checksums: &[Option<String>],
+) -> Result<Vec<usize>> {
+    let mut corrupt = Vec::new();
+    for (i, (filename, checksum)) in filenames.iter().zip(checksums).enumerate() {
+        let Some(checksum) = checksum else {
+            continue;
+        };
+        let path = dir.join(filename);
+        if &sha1_base64(&path)? != checksum {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Could not delete '{}'", path.display()))?;
+            corrupt.push(i);
+        }
+    }
+    Ok(corrupt)
+}
+
+/// Base64 encoded SHA-1 of a file as used by Immich for asset checksums. The file
+/// is streamed through the hasher so large videos aren't loaded into memory.
+fn sha1_base64(path: &Path) -> Result<String> {
+    let mut file = std::fs::File::open(path)
+        .with_context(|| format!("Could not open '{}'", path.display()))?;
+    let mut hasher = Sha1::new();
+    std::io::copy(&mut file, &mut hasher)
+        .with_context(|| format!("Could not read '{}'", path.display()))?;
+    Ok(base64::engine::general_purpose::STANDARD.encode(hasher.finalize()))
+}
+
 /// Return a filename that has not yet been used. If `name` was used N times
 /// before, return e.g. `stem (N).ext` and increment the counter.
 fn unique_name(used: &mut HashMap<String, u32>, name: &str) -> String {
@@ -320,6 +853,7 @@ fn unique_name(used: &mut HashMap<String, u32>, name: &str) -> String {
 mod tests {
     use super::*;
     use crate::immichctl::asset_cmd::tests::create_asset_for_download;
+    use crate::immichctl::assets::Assets;
     use crate::immichctl::tests::create_immichctl_with_server;
 
     use std::io::{Cursor, Write};
@@ -413,7 +947,9 @@ mod tests {
         let (ctl, _server) = create_immichctl_with_server().await;
         let outdir = tempfile::tempdir().unwrap();
 
-        let result = ctl.assets_download(outdir.path()).await;
+        let result = ctl
+            .assets_download(outdir.path(), false, NonZeroUsize::MIN, false, false, false)
+            .await;
         assert!(result.is_ok());
         // No files should be written
         let count = std::fs::read_dir(outdir.path()).unwrap().count();
@@ -464,7 +1000,9 @@ mod tests {
         .await;
 
         let outdir = tempfile::tempdir().unwrap();
-        let result = ctl.assets_download(outdir.path()).await;
+        let result = ctl
+            .assets_download(outdir.path(), false, NonZeroUsize::MIN, false, false, false)
+            .await;
         assert!(result.is_ok(), "{:?}", result.err());
 
         info_mock.assert_async().await;
@@ -506,7 +1044,9 @@ mod tests {
         .await;
 
         let outdir = tempfile::tempdir().unwrap();
-        let result = ctl.assets_download(outdir.path()).await;
+        let result = ctl
+            .assets_download(outdir.path(), false, NonZeroUsize::MIN, false, false, false)
+            .await;
         assert!(result.is_ok(), "{:?}", result.err());
 
         let original = outdir.path().join("IMG.jpg");
@@ -533,7 +1073,9 @@ mod tests {
         let nested = parent.path().join("a").join("b");
         assert!(!nested.exists());
 
-        let result = ctl.assets_download(&nested).await;
+        let result = ctl
+            .assets_download(&nested, false, NonZeroUsize::MIN, false, false, false)
+            .await;
         assert!(result.is_ok(), "{:?}", result.err());
         assert!(nested.join("X.bin").exists());
     }
@@ -562,7 +1104,9 @@ mod tests {
         let _mocks = mock_download(&mut server, &[id1], &[("BIG.bin", &payload)]).await;
 
         let outdir = tempfile::tempdir().unwrap();
-        let result = ctl.assets_download(outdir.path()).await;
+        let result = ctl
+            .assets_download(outdir.path(), false, NonZeroUsize::MIN, false, false, false)
+            .await;
         assert!(result.is_ok(), "{:?}", result.err());
 
         let written = std::fs::read(outdir.path().join("BIG.bin")).unwrap();
@@ -570,6 +1114,265 @@ mod tests {
         assert_eq!(written, payload);
     }
 
+    #[tokio::test]
+    async fn test_download_partial_failure_and_retry() -> Result<()> {
+        let (ctl, mut server) = create_immichctl_with_server().await;
+
+        let id1 = Uuid::new_v4();
+        let id2 = Uuid::new_v4();
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(create_asset_for_download(id1, "A.jpg", "/upload/A.jpg"));
+        sel.add_asset(create_asset_for_download(id2, "B.jpg", "/upload/B.jpg"));
+        sel.save()?;
+
+        let info_mock = server
+            .mock("POST", "/api/download/info")
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "archives": [
+                        {"assetIds": [id1.to_string()], "size": 1},
+                        {"assetIds": [id2.to_string()], "size": 1},
+                    ],
+                    "totalSize": 2,
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let _archive1_mock = server
+            .mock("POST", "/api/download/archive")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"assetIds": [id1.to_string()]}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/octet-stream")
+            .with_body(build_zip(&[("A.jpg", b"a")]))
+            .create_async()
+            .await;
+        let archive2_fail_mock = server
+            .mock("POST", "/api/download/archive")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"assetIds": [id2.to_string()]}),
+            ))
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let outdir = tempfile::tempdir()?;
+        let concurrency = NonZeroUsize::new(2).unwrap();
+        let err = ctl
+            .assets_download(outdir.path(), false, concurrency, false, false, false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().starts_with("Could not download 1 asset(s)"));
+        assert_eq!(std::fs::read(outdir.path().join("A.jpg"))?, b"a");
+        let failed_file = outdir.path().join(FAILED_FILE);
+        assert_eq!(read_failed_ids(&failed_file)?, HashSet::from([id2]));
+
+        // retry downloads only the failed asset and removes the sidecar file
+        archive2_fail_mock.remove_async().await;
+        info_mock.remove_async().await;
+        let (retry_info_mock, _archive_mock) =
+            mock_download(&mut server, &[id2], &[("B.jpg", b"b")]).await;
+        ctl.assets_download(outdir.path(), true, concurrency, false, false, false)
+            .await?;
+        retry_info_mock.assert_async().await;
+        assert_eq!(std::fs::read(outdir.path().join("B.jpg"))?, b"b");
+        assert!(!failed_file.exists());
+
+        // nothing recorded anymore
+        let err = ctl
+            .assets_download(outdir.path(), true, concurrency, false, false, false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().starts_with("No failed downloads recorded"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_manifest_resume() -> Result<()> {
+        let (ctl, mut server) = create_immichctl_with_server().await;
+
+        let id1 = Uuid::new_v4();
+        let id2 = Uuid::new_v4();
+        let mut asset1 = create_asset_for_download(id1, "A.jpg", "/upload/IMG.jpg");
+        asset1.checksum = "qvTGHdzF6KLavt4PO0gs2a6pQ00=".to_string(); // sha1("hello")
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(asset1);
+        // edited assets can't be verified
+        let mut asset2 = create_asset_for_download(id2, "B.jpg", "/upload/IMG.jpg");
+        asset2.is_edited = true;
+        sel.add_asset(asset2);
+        sel.save()?;
+
+        let info_mock = server
+            .mock("POST", "/api/download/info")
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "archives": [
+                        {"assetIds": [id1.to_string()], "size": 1},
+                        {"assetIds": [id2.to_string()], "size": 1},
+                    ],
+                    "totalSize": 2,
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let _archive1_mock = server
+            .mock("POST", "/api/download/archive")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"assetIds": [id1.to_string()]}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/octet-stream")
+            .with_body(build_zip(&[("IMG.jpg", b"hello")]))
+            .create_async()
+            .await;
+        let archive2_fail_mock = server
+            .mock("POST", "/api/download/archive")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"assetIds": [id2.to_string()]}),
+            ))
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let outdir = tempfile::tempdir()?;
+        assert!(
+            ctl.assets_download(outdir.path(), false, NonZeroUsize::MIN, false, false, false)
+                .await
+                .is_err()
+        );
+        let manifest = Manifest::load(outdir.path())?;
+        assert_eq!(manifest.assets[&id1].path, "IMG.jpg");
+        assert_eq!(manifest.assets[&id1].status, DownloadStatus::Done);
+        assert_eq!(manifest.assets[&id2].path, "IMG (1).jpg");
+        assert_eq!(manifest.assets[&id2].status, DownloadStatus::Failed);
+
+        // a plain re-run only downloads the failed asset, keeping its file name
+        archive2_fail_mock.remove_async().await;
+        info_mock.remove_async().await;
+        let (resume_info_mock, _archive_mock) =
+            mock_download(&mut server, &[id2], &[("IMG.jpg", b"b")]).await;
+        ctl.assets_download(outdir.path(), false, NonZeroUsize::MIN, false, false, false)
+            .await?;
+        resume_info_mock.assert_async().await;
+        assert_eq!(std::fs::read(outdir.path().join("IMG (1).jpg"))?, b"b");
+        assert_eq!(std::fs::read(outdir.path().join("IMG.jpg"))?, b"hello");
+        let manifest = Manifest::load(outdir.path())?;
+        assert_eq!(manifest.assets[&id2].status, DownloadStatus::Done);
+        assert!(!outdir.path().join(FAILED_FILE).exists());
+
+        // everything is done, --verify checks the existing files without downloading
+        resume_info_mock.remove_async().await;
+        ctl.assets_download(outdir.path(), false, NonZeroUsize::MIN, true, false, false)
+            .await?;
+        let manifest = Manifest::load(outdir.path())?;
+        assert_eq!(manifest.assets[&id1].status, DownloadStatus::Verified);
+        assert_eq!(manifest.assets[&id2].status, DownloadStatus::Done);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_live_videos() -> Result<()> {
+        let (ctl, mut server) = create_immichctl_with_server().await;
+
+        let photo_id = Uuid::new_v4();
+        let video_id = Uuid::new_v4();
+        let mut photo = create_asset_for_download(photo_id, "A.heic", "/upload/IMG.heic");
+        photo.live_photo_video_id = Some(video_id.to_string());
+        let video = create_asset_for_download(video_id, "A.mov", "/upload/IMG.mov");
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(photo);
+        sel.save()?;
+
+        let video_mock = server
+            .mock("GET", format!("/api/assets/{}", video_id).as_str())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&video)?)
+            .expect(1)
+            .create_async()
+            .await;
+        let (info_mock, _archive_mock) = mock_download(
+            &mut server,
+            &[photo_id, video_id],
+            &[("IMG.heic", b"photo"), ("IMG.mov", b"video")],
+        )
+        .await;
+
+        let outdir = tempfile::tempdir()?;
+        ctl.assets_download(outdir.path(), false, NonZeroUsize::MIN, false, false, true)
+            .await?;
+        video_mock.assert_async().await;
+        info_mock.assert_async().await;
+        assert_eq!(std::fs::read(outdir.path().join("IMG.heic"))?, b"photo");
+        assert_eq!(std::fs::read(outdir.path().join("IMG.mov"))?, b"video");
+        let manifest = Manifest::load(outdir.path())?;
+        assert_eq!(manifest.assets[&video_id].status, DownloadStatus::Done);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sha1_base64() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("a.txt");
+        std::fs::write(&path, b"hello")?;
+        assert_eq!(sha1_base64(&path)?, "qvTGHdzF6KLavt4PO0gs2a6pQ00=");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_verify_deletes_corrupt_files() -> Result<()> {
+        let (ctl, mut server) = create_immichctl_with_server().await;
+
+        let id1 = Uuid::new_v4();
+        let id2 = Uuid::new_v4();
+        let id3 = Uuid::new_v4();
+        let mut ok = create_asset_for_download(id1, "A.jpg", "/upload/A.jpg");
+        ok.checksum = "qvTGHdzF6KLavt4PO0gs2a6pQ00=".to_string();
+        let mut corrupt = create_asset_for_download(id2, "B.jpg", "/upload/B.jpg");
+        corrupt.checksum = "qvTGHdzF6KLavt4PO0gs2a6pQ00=".to_string();
+        let mut edited = create_asset_for_download(id3, "C.jpg", "/upload/C.jpg");
+        edited.is_edited = true;
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(ok);
+        sel.add_asset(corrupt);
+        sel.add_asset(edited);
+        sel.save()?;
+
+        let _mocks = mock_download(
+            &mut server,
+            &[id1, id2, id3],
+            &[
+                ("A.jpg", b"hello"),
+                ("B.jpg", b"hellO"),
+                ("C.jpg", b"edited"),
+            ],
+        )
+        .await;
+
+        let outdir = tempfile::tempdir()?;
+        let err = ctl
+            .assets_download(outdir.path(), false, NonZeroUsize::MIN, true, false, false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().starts_with("Could not download 1 asset(s)"));
+        assert!(outdir.path().join("A.jpg").exists());
+        assert!(!outdir.path().join("B.jpg").exists());
+        assert!(outdir.path().join("C.jpg").exists());
+        assert_eq!(
+            read_failed_ids(&outdir.path().join(FAILED_FILE))?,
+            HashSet::from([id2])
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_download_info_failure_includes_context() {
         let (ctl, mut server) = create_immichctl_with_server().await;
@@ -589,7 +1392,9 @@ mod tests {
             .await;
 
         let outdir = tempfile::tempdir().unwrap();
-        let result = ctl.assets_download(outdir.path()).await;
+        let result = ctl
+            .assets_download(outdir.path(), false, NonZeroUsize::MIN, false, false, false)
+            .await;
         assert!(result.is_err());
         let msg = result.err().unwrap().to_string();
         assert!(
@@ -598,4 +1403,74 @@ mod tests {
             msg
         );
     }
+
+    #[tokio::test]
+    async fn test_export_zip() -> Result<()> {
+        let (ctl, mut server) = create_immichctl_with_server().await;
+
+        let ids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(create_asset_for_download(ids[0], "IMG.jpg", "/a/1.jpg"));
+        sel.add_asset(create_asset_for_download(ids[1], "IMG.jpg", "/a/2.jpg"));
+        sel.add_asset(create_asset_for_download(ids[2], "VID.mp4", "/a/3.mp4"));
+        sel.save()?;
+        let mut mocks = vec![];
+        for (i, id) in ids.iter().enumerate() {
+            let path = format!("/api/assets/{}/original", id);
+            let mock = if i == 2 {
+                server.mock("GET", path.as_str()).with_status(500)
+            } else {
+                server
+                    .mock("GET", path.as_str())
+                    .with_status(200)
+                    .with_header("content-type", "application/octet-stream")
+                    .with_body(format!("content {}", id))
+            };
+            mocks.push(mock.create_async().await);
+        }
+
+        let outdir = tempfile::tempdir()?;
+        let zip_file = outdir.path().join("export.zip");
+        let err = ctl
+            .assets_export_zip(&zip_file, NonZeroUsize::new(2).unwrap())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Could not export 1 asset(s), see warnings above."
+        );
+        for m in mocks {
+            m.assert_async().await;
+        }
+
+        // the archive contains the other assets, named by original file name
+        let mut zip = zip::ZipArchive::new(std::fs::File::open(&zip_file)?)?;
+        let mut contents = HashMap::new();
+        for i in 0..zip.len() {
+            let mut entry = zip.by_index(i)?;
+            let mut content = String::new();
+            std::io::Read::read_to_string(&mut entry, &mut content)?;
+            contents.insert(entry.name().to_string(), content);
+        }
+        assert_eq!(contents.len(), 2);
+        let mut names: Vec<_> = contents.keys().cloned().collect();
+        names.sort();
+        assert_eq!(names, ["IMG (1).jpg", "IMG.jpg"]);
+        let mut values: Vec<_> = contents.values().cloned().collect();
+        values.sort();
+        let mut expected = vec![format!("content {}", ids[0]), format!("content {}", ids[1])];
+        expected.sort();
+        assert_eq!(values, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_export_zip_empty_selection() -> Result<()> {
+        let (ctl, _server) = create_immichctl_with_server().await;
+        let outdir = tempfile::tempdir()?;
+        let zip_file = outdir.path().join("export.zip");
+        ctl.assets_export_zip(&zip_file, NonZeroUsize::MIN).await?;
+        assert!(!zip_file.exists());
+        Ok(())
+    }
 }
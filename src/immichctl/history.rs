@@ -0,0 +1,95 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// One mutating command recorded in the history.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Utc>,
+    pub command: String,
+    pub count: usize,
+}
+
+/// Append-only log of mutating commands, one json object per line.
+pub struct History {
+    file: PathBuf,
+}
+
+impl History {
+    pub fn new(file: &Path) -> History {
+        History {
+            file: file.to_path_buf(),
+        }
+    }
+
+    pub fn append(&self, command: &str, count: usize) -> Result<()> {
+        let entry = HistoryEntry {
+            timestamp: Utc::now(),
+            command: command.to_string(),
+            count,
+        };
+        fs::create_dir_all(self.file.parent().unwrap())?;
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.file)
+            .context("Could not write history.")?;
+        let line = serde_json::to_string(&entry)?;
+        writeln!(file, "{}", line).context("Could not write history.")?;
+        Ok(())
+    }
+
+    /// All recorded entries, oldest first. Unreadable lines are skipped.
+    pub fn entries(&self) -> Result<Vec<HistoryEntry>> {
+        if !self.file.exists() {
+            return Ok(Vec::new());
+        }
+        let file = fs::File::open(&self.file).context("Could not read history.")?;
+        let mut entries = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line.context("Could not read history.")?;
+            if let Ok(entry) = serde_json::from_str(&line) {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_append_and_entries() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let history = History::new(&dir.path().join("history.jsonl"));
+        assert!(history.entries()?.is_empty());
+
+        history.append("tag assign 'vacation'", 3)?;
+        history.append("album rename 'a' to 'b'", 1)?;
+        let entries = history.entries()?;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].command, "tag assign 'vacation'");
+        assert_eq!(entries[0].count, 3);
+        assert_eq!(entries[1].command, "album rename 'a' to 'b'");
+        Ok(())
+    }
+
+    #[test]
+    fn test_entries_skips_invalid_lines() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let file = dir.path().join("history.jsonl");
+        fs::write(
+            &file,
+            "garbage\n{\"timestamp\":\"2025-10-07T16:12:36Z\",\"command\":\"tag assign 'x'\",\"count\":2}\n",
+        )?;
+        let entries = History::new(&file).entries()?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].count, 2);
+        Ok(())
+    }
+}
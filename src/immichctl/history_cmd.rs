@@ -0,0 +1,52 @@
+use super::ImmichCtl;
+use super::history::History;
+use anyhow::Result;
+
+impl ImmichCtl {
+    /// Record a successful mutating command in the history. A failing write only
+    /// warns as the command itself already succeeded.
+    pub fn record_history(&self, command: &str, count: usize) {
+        if let Err(err) = History::new(&self.history_file).append(command, count) {
+            self.eprint_warning(&format!("{:#}", err));
+        }
+    }
+
+    pub fn history_list(&self) -> Result<()> {
+        let entries = History::new(&self.history_file).entries()?;
+        if entries.is_empty() {
+            eprintln!("No commands recorded yet.");
+            return Ok(());
+        }
+        for entry in entries {
+            println!(
+                "{}  {}  ({} affected)",
+                entry
+                    .timestamp
+                    .to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                entry.command,
+                entry.count
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::immichctl::history::History;
+    use crate::immichctl::tests::create_immichctl_with_server;
+    use anyhow::Result;
+
+    #[tokio::test]
+    async fn test_record_history() -> Result<()> {
+        let (ctl, _server) = create_immichctl_with_server().await;
+        ctl.record_history("tag assign 'x'", 2);
+
+        let entries = History::new(&ctl.history_file).entries()?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].command, "tag assign 'x'");
+        assert_eq!(entries[0].count, 2);
+        ctl.history_list()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use anyhow::{Result, bail};
+use uuid::Uuid;
+
+/// Parse one or more asset ids separated by commas and/or whitespace (incl. newlines),
+/// e.g. a blob of ids copied from the web UI. Duplicates are returned once.
+pub fn parse_ids(input: &str) -> Result<Vec<Uuid>> {
+    let mut ids = Vec::new();
+    for token in input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty())
+    {
+        let Ok(id) = Uuid::parse_str(token) else {
+            bail!("Invalid asset id '{}', expected uuid", token);
+        };
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    if ids.is_empty() {
+        bail!("No asset id given, expected uuid");
+    }
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID1: &str = "a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1";
+    const ID2: &str = "a1a7f1a9-7394-49f7-a5a3-e876a7e16ab2";
+    const ID3: &str = "a1a7f1a9-7394-49f7-a5a3-e876a7e16ab3";
+
+    #[test]
+    fn test_parse_ids() {
+        let expected: Vec<Uuid> = [ID1, ID2, ID3]
+            .iter()
+            .map(|id| Uuid::parse_str(id).unwrap())
+            .collect();
+        assert_eq!(parse_ids(ID1).unwrap(), expected[..1]);
+        let mixed = format!(" {},{}\n\t{} ,\r\n{}, ", ID1, ID2, ID3, ID1);
+        assert_eq!(parse_ids(&mixed).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_parse_ids_invalid() {
+        assert_eq!(
+            parse_ids(&format!("{}, abc-123 {}", ID1, ID2))
+                .unwrap_err()
+                .to_string(),
+            "Invalid asset id 'abc-123', expected uuid"
+        );
+        assert_eq!(
+            parse_ids(" ,\n").unwrap_err().to_string(),
+            "No asset id given, expected uuid"
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use super::ImmichCtl;
+use super::api_error::ApiContext;
+use anyhow::{Result, bail};
+use uuid::Uuid;
+
+impl ImmichCtl {
+    /// Resolve a library by id or name. An id is used as is without a server request.
+    pub async fn find_library(&self, name_or_id: &str) -> Result<Uuid> {
+        if let Ok(id) = Uuid::parse_str(name_or_id) {
+            return Ok(id);
+        }
+        let libraries_resp = self
+            .immich()?
+            .get_all_libraries()
+            .api_context("Could not retrieve libraries")
+            .await?;
+
+        let mut it = libraries_resp.iter().filter(|l| l.name == name_or_id);
+        match (it.next(), it.next()) {
+            (None, _) => bail!("Library not found: '{}'", name_or_id),
+            (Some(l), None) => Ok(l.id),
+            _ => bail!("Library name is not unique: '{}'", name_or_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::immichctl::tests::create_immichctl_with_server;
+    use anyhow::Result;
+    use uuid::Uuid;
+
+    fn create_library(id: &str, name: &str) -> serde_json::Value {
+        serde_json::json!({
+            "assetCount": 0,
+            "createdAt": "2024-01-01T00:00:00Z",
+            "exclusionPatterns": [],
+            "id": id,
+            "importPaths": ["/mnt/photos"],
+            "name": name,
+            "ownerId": "b1a7f1a9-7394-49f7-a5a3-e876a7e16ab1",
+            "refreshedAt": null,
+            "updatedAt": "2024-01-01T00:00:00Z",
+        })
+    }
+
+    #[tokio::test]
+    async fn test_find_library() -> Result<()> {
+        let (ctl, mut server) = create_immichctl_with_server().await;
+
+        let libraries = vec![
+            create_library("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1", "Photos"),
+            create_library("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab2", "Scans"),
+            create_library("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab3", "Scans"),
+        ];
+        let mock = server
+            .mock("GET", "/api/libraries")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&libraries)?)
+            .expect(3)
+            .create_async()
+            .await;
+
+        assert_eq!(
+            ctl.find_library("Photos").await?,
+            Uuid::parse_str("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1")?
+        );
+        assert_eq!(
+            ctl.find_library("Videos").await.unwrap_err().to_string(),
+            "Library not found: 'Videos'"
+        );
+        assert_eq!(
+            ctl.find_library("Scans").await.unwrap_err().to_string(),
+            "Library name is not unique: 'Scans'"
+        );
+        // ids are not looked up
+        assert_eq!(
+            ctl.find_library("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab9")
+                .await?,
+            Uuid::parse_str("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab9")?
+        );
+        mock.assert_async().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use super::ImmichCtl;
+use super::api_error::ApiContext;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Output format of `assets memberships`
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MembershipFormat {
+    /// Human readable text
+    Text,
+    /// Json format
+    Json,
+}
+
+/// Album of an asset as printed by `assets memberships --format json`.
+#[derive(Debug, PartialEq, Serialize)]
+struct AlbumRef {
+    id: Uuid,
+    name: String,
+}
+
+/// Albums and tags of one asset.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Memberships {
+    id: Uuid,
+    original_file_name: String,
+    albums: Vec<AlbumRef>,
+    tags: Vec<String>,
+}
+
+impl ImmichCtl {
+    /// Print the albums and tags (full values) the asset `id` belongs to. The asset is
+    /// fetched from the server, it doesn't need to be selected.
+    pub async fn assets_memberships(&self, id: &str, format: MembershipFormat) -> Result<()> {
+        let memberships = self.memberships(id).await?;
+        match format {
+            MembershipFormat::Json => self.print_json(&memberships)?,
+            MembershipFormat::Text => {
+                println!(
+                    "Asset: {} ({})",
+                    memberships.id, memberships.original_file_name
+                );
+                println!("Albums:");
+                if memberships.albums.is_empty() {
+                    println!("  (none)");
+                }
+                for album in &memberships.albums {
+                    println!("  {}", album.name);
+                }
+                println!("Tags:");
+                if memberships.tags.is_empty() {
+                    println!("  (none)");
+                }
+                for tag in &memberships.tags {
+                    println!("  {}", tag);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn memberships(&self, id: &str) -> Result<Memberships> {
+        let id = Uuid::parse_str(id.trim())
+            .with_context(|| format!("Invalid asset id '{}', expected uuid", id))?;
+        let asset = self
+            .immich()?
+            .get_asset_info(&id, None, None)
+            .with_api_context(|| format!("Could not retrieve asset '{}'", id))
+            .await?
+            .into_inner();
+        let mut albums: Vec<AlbumRef> = self
+            .albums_of_asset(&id)
+            .await?
+            .into_iter()
+            .map(|a| AlbumRef {
+                id: a.id,
+                name: a.album_name,
+            })
+            .collect();
+        albums.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut tags: Vec<String> = asset.tags.into_iter().map(|t| t.value).collect();
+        tags.sort();
+        Ok(Memberships {
+            id,
+            original_file_name: asset.original_file_name,
+            albums,
+            tags,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::immichctl::album_cmd::tests::create_album;
+    use crate::immichctl::asset_cmd::tests::create_asset_with_timestamps;
+    use crate::immichctl::tag_cmd::tests::create_tag;
+    use crate::immichctl::tests::create_immichctl_with_server;
+    use chrono::{TimeZone, Utc};
+
+    #[tokio::test]
+    async fn test_memberships() -> Result<()> {
+        let (ctl, mut server) = create_immichctl_with_server().await;
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let mut asset = create_asset_with_timestamps(ts, ts);
+        asset.tags = vec![
+            create_tag("d1a7f1a9-7394-49f7-a5a3-e876a7e16ab1", "Trips/Rome", None),
+            create_tag("d1a7f1a9-7394-49f7-a5a3-e876a7e16ab2", "Food", None),
+        ];
+        let albums = vec![
+            create_album("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1", "Rome"),
+            create_album("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab2", "Best of 2024"),
+        ];
+        let asset_mock = server
+            .mock("GET", format!("/api/assets/{}", asset.id).as_str())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&asset)?)
+            .create_async()
+            .await;
+        let albums_mock = server
+            .mock("GET", "/api/albums")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "assetId".into(),
+                asset.id.to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&albums)?)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let memberships = ctl.memberships(&asset.id.to_string()).await?;
+        asset_mock.assert_async().await;
+        albums_mock.assert_async().await;
+        let album_names: Vec<_> = memberships.albums.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(album_names, vec!["Best of 2024", "Rome"]);
+        assert_eq!(memberships.tags, vec!["Food", "Trips/Rome"]);
+        assert_eq!(memberships.original_file_name, asset.original_file_name);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_memberships_invalid_id() {
+        let (ctl, _server) = create_immichctl_with_server().await;
+        assert_eq!(
+            ctl.memberships("no-uuid").await.unwrap_err().to_string(),
+            "Invalid asset id 'no-uuid', expected uuid"
+        );
+    }
+}
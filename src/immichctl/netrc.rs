@@ -0,0 +1,146 @@
+use std::path::PathBuf;
+
+/// Login name of the netrc entry used if the config has no server
+pub const NETRC_LOGIN: &str = "immichctl";
+
+/// Entry of a netrc file, `machine` is empty for the `default` entry.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct NetrcEntry {
+    pub machine: String,
+    pub login: Option<String>,
+    pub password: Option<String>,
+    pub account: Option<String>,
+}
+
+/// The netrc file set by the `NETRC` env variable or `$HOME/.netrc`.
+pub fn default_netrc_file() -> Option<PathBuf> {
+    match std::env::var_os("NETRC") {
+        Some(file) if !file.is_empty() => Some(PathBuf::from(file)),
+        _ => dirs::home_dir().map(|home| home.join(".netrc")),
+    }
+}
+
+/// Parse the `machine`, `default`, `login`, `password` and `account` tokens of a netrc
+/// file. Comment lines starting with `#` and `macdef` macros are skipped, quoted tokens
+/// are not supported. A `#` within a line is part of a token, e.g. of a password.
+pub fn parse_netrc(contents: &str) -> Vec<NetrcEntry> {
+    let mut tokens = Vec::new();
+    let mut in_macdef = false;
+    for line in contents.lines() {
+        if in_macdef {
+            // a macro definition ends with an empty line
+            in_macdef = !line.trim().is_empty();
+            continue;
+        }
+        if line.trim_start().starts_with('#') {
+            continue;
+        }
+        for token in line.split_whitespace() {
+            if token == "macdef" {
+                in_macdef = true;
+                break;
+            }
+            tokens.push(token);
+        }
+    }
+
+    let mut entries: Vec<NetrcEntry> = Vec::new();
+    let mut tokens = tokens.into_iter();
+    while let Some(token) = tokens.next() {
+        match token {
+            "machine" => entries.push(NetrcEntry {
+                machine: tokens.next().unwrap_or_default().to_string(),
+                ..Default::default()
+            }),
+            "default" => entries.push(NetrcEntry::default()),
+            "login" | "password" | "account" => {
+                let value = tokens.next().map(str::to_string);
+                let Some(entry) = entries.last_mut() else {
+                    continue;
+                };
+                match token {
+                    "login" => entry.login = value,
+                    "password" => entry.password = value,
+                    _ => entry.account = value,
+                }
+            }
+            _ => {}
+        }
+    }
+    entries
+}
+
+/// The machine entry of `host`. The `default` entry is never used, its password is
+/// unlikely to be an Immich API key.
+pub fn find_by_host<'a>(entries: &'a [NetrcEntry], host: &str) -> Option<&'a NetrcEntry> {
+    entries
+        .iter()
+        .find(|e| !e.machine.is_empty() && e.machine.eq_ignore_ascii_case(host))
+}
+
+/// The first machine entry with login `immichctl`.
+pub fn find_by_login(entries: &[NetrcEntry]) -> Option<&NetrcEntry> {
+    entries
+        .iter()
+        .find(|e| !e.machine.is_empty() && e.login.as_deref() == Some(NETRC_LOGIN))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NETRC: &str = "\
+# credentials
+machine github.com login octocat password secret
+machine immich.example.com
+    login immichctl
+    # api key
+    password key1
+    account https://immich.example.com:2283
+
+macdef init
+machine fake password nope
+
+default login anonymous password guest
+";
+
+    #[test]
+    fn test_parse_netrc() {
+        let entries = parse_netrc(NETRC);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(
+            entries[1],
+            NetrcEntry {
+                machine: "immich.example.com".to_string(),
+                login: Some("immichctl".to_string()),
+                password: Some("key1".to_string()),
+                account: Some("https://immich.example.com:2283".to_string()),
+            }
+        );
+        assert_eq!(entries[2].machine, "");
+        assert_eq!(entries[2].password.as_deref(), Some("guest"));
+    }
+
+    #[test]
+    fn test_parse_netrc_hash_in_token() {
+        let entries = parse_netrc(
+            "machine immich.example.com login immichctl password se#cret#\n  account #main\n",
+        );
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].password.as_deref(), Some("se#cret#"));
+        assert_eq!(entries[0].account.as_deref(), Some("#main"));
+    }
+
+    #[test]
+    fn test_find_entry() {
+        let entries = parse_netrc(NETRC);
+        let entry = find_by_host(&entries, "Immich.Example.com").unwrap();
+        assert_eq!(entry.password.as_deref(), Some("key1"));
+        assert_eq!(find_by_host(&entries, "other.example.com"), None);
+        assert_eq!(
+            find_by_login(&entries).unwrap().machine,
+            "immich.example.com"
+        );
+        assert_eq!(find_by_login(&parse_netrc("machine a login b")), None);
+    }
+}
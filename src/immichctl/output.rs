@@ -0,0 +1,197 @@
+use super::api_error::ApiError;
+use anyhow::Result;
+use reqwest::StatusCode;
+use serde::Serialize;
+use std::io::Write;
+
+/// Format of diagnostics like errors.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputMode {
+    /// Human readable text
+    #[default]
+    Text,
+    /// Json objects, one per line
+    Json,
+}
+
+/// Classify an error for machine consumers, e.g. `not_logged_in`, `auth`, `not_found`,
+/// `api`, `network`, `io` or `other`.
+pub fn error_kind(err: &anyhow::Error) -> &'static str {
+    for cause in err.chain() {
+        if let Some(e) = cause.downcast_ref::<ApiError>() {
+            return status_kind(Some(e.status));
+        }
+        if let Some(e) = cause.downcast_ref::<progenitor_client::Error<()>>() {
+            return api_error_kind(e);
+        }
+        if let Some(e) = cause.downcast_ref::<progenitor_client::Error<anyhow::Error>>() {
+            return api_error_kind(e);
+        }
+        if cause.downcast_ref::<reqwest::Error>().is_some() {
+            return "network";
+        }
+        if cause.downcast_ref::<std::io::Error>().is_some() {
+            return "io";
+        }
+        if cause.to_string().starts_with("Not logged in") {
+            return "not_logged_in";
+        }
+    }
+    "other"
+}
+
+fn api_error_kind<E>(err: &progenitor_client::Error<E>) -> &'static str {
+    match err {
+        progenitor_client::Error::CommunicationError(_)
+        | progenitor_client::Error::ResponseBodyError(_) => "network",
+        _ => status_kind(err.status()),
+    }
+}
+
+fn status_kind(status: Option<StatusCode>) -> &'static str {
+    match status {
+        Some(StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) => "auth",
+        Some(StatusCode::NOT_FOUND) => "not_found",
+        _ => "api",
+    }
+}
+
+/// Write `value` as json, indented if `pretty`, without trailing newline.
+pub fn write_json<T: Serialize + ?Sized>(
+    writer: &mut dyn Write,
+    value: &T,
+    pretty: bool,
+) -> Result<()> {
+    if pretty {
+        serde_json::to_writer_pretty(writer, value)?;
+    } else {
+        serde_json::to_writer(writer, value)?;
+    }
+    Ok(())
+}
+
+/// Print `value` as json to stdout, indented if `pretty`.
+pub fn print_json<T: Serialize + ?Sized>(value: &T, pretty: bool) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    write_json(&mut stdout, value, pretty)?;
+    writeln!(stdout)?;
+    Ok(())
+}
+
+/// Error as json object: `{"error": {"message": "...", "kind": "..."}}`
+pub fn error_json(err: &anyhow::Error, verbose: bool, pretty: bool) -> String {
+    let message = if verbose {
+        format!("{:#}", err)
+    } else {
+        err.to_string()
+    };
+    let json = serde_json::json!({
+        "error": {
+            "message": message,
+            "kind": error_kind(err),
+        }
+    });
+    if pretty {
+        serde_json::to_string_pretty(&json).unwrap_or_else(|_| json.to_string())
+    } else {
+        json.to_string()
+    }
+}
+
+/// Warning as single line json object: `{"warning": "...", "asset": "..."}`, `asset` is
+/// omitted for warnings not related to a single asset.
+pub fn warning_json(msg: &str, asset: Option<&uuid::Uuid>) -> String {
+    let mut json = serde_json::json!({ "warning": msg });
+    if let Some(asset) = asset {
+        json["asset"] = serde_json::json!(asset);
+    }
+    json.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{Context, anyhow};
+
+    #[test]
+    fn test_error_kind() {
+        let err = anyhow!("Not logged in. Use 'immichctl login <URL> --apikey <KEY>' to login.");
+        assert_eq!(error_kind(&err), "not_logged_in");
+
+        let err = Err::<(), _>(std::io::Error::other("disk full"))
+            .context("Could not save asset selection")
+            .unwrap_err();
+        assert_eq!(error_kind(&err), "io");
+
+        let err = Err::<(), _>(progenitor_client::Error::<()>::InvalidRequest(
+            "bad".to_string(),
+        ))
+        .context("Search failed")
+        .unwrap_err();
+        assert_eq!(error_kind(&err), "api");
+
+        let err = anyhow::Error::new(ApiError {
+            status: StatusCode::NOT_FOUND,
+            body: String::new(),
+        })
+        .context("Could not retrieve asset");
+        assert_eq!(error_kind(&err), "not_found");
+
+        assert_eq!(error_kind(&anyhow!("Tag not found")), "other");
+    }
+
+    #[test]
+    fn test_status_kind() {
+        assert_eq!(status_kind(Some(StatusCode::UNAUTHORIZED)), "auth");
+        assert_eq!(status_kind(Some(StatusCode::NOT_FOUND)), "not_found");
+        assert_eq!(status_kind(Some(StatusCode::BAD_REQUEST)), "api");
+    }
+
+    #[test]
+    fn test_error_json() {
+        let err = Err::<(), _>(anyhow!("Tag not found: 'x'"))
+            .context("Could not tag assets")
+            .unwrap_err();
+        assert_eq!(
+            error_json(&err, false, false),
+            r#"{"error":{"kind":"other","message":"Could not tag assets"}}"#
+        );
+        assert_eq!(
+            error_json(&err, true, false),
+            r#"{"error":{"kind":"other","message":"Could not tag assets: Tag not found: 'x'"}}"#
+        );
+        assert_eq!(
+            error_json(&err, false, true),
+            "{\n  \"error\": {\n    \"kind\": \"other\",\n    \"message\": \"Could not tag assets\"\n  }\n}"
+        );
+    }
+
+    #[test]
+    fn test_warning_json() {
+        assert_eq!(
+            warning_json("Could not retrieve asset", None),
+            r#"{"warning":"Could not retrieve asset"}"#
+        );
+        let asset = uuid::Uuid::parse_str("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1").unwrap();
+        assert_eq!(
+            warning_json("Checksum \"mismatch\"", Some(&asset)),
+            r#"{"asset":"a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1","warning":"Checksum \"mismatch\""}"#
+        );
+    }
+
+    #[test]
+    fn test_write_json() -> Result<()> {
+        let value = serde_json::json!({"id": 1, "tags": ["a"]});
+        let mut out = Vec::new();
+        write_json(&mut out, &value, false)?;
+        assert_eq!(String::from_utf8(out)?, r#"{"id":1,"tags":["a"]}"#);
+
+        let mut out = Vec::new();
+        write_json(&mut out, &value, true)?;
+        assert_eq!(
+            String::from_utf8(out)?,
+            "{\n  \"id\": 1,\n  \"tags\": [\n    \"a\"\n  ]\n}"
+        );
+        Ok(())
+    }
+}
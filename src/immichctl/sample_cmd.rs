@@ -0,0 +1,263 @@
+use super::ImmichCtl;
+use super::types::AssetResponseDto;
+use anyhow::{Result, bail};
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use uuid::Uuid;
+
+/// How many assets `assets sample` keeps
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SampleMode {
+    /// Exactly N assets (all if the selection is smaller)
+    Count(NonZeroUsize),
+    /// About P percent of the selection, rounded to the nearest asset count but at least one
+    Percent(f64),
+    /// Up to N assets per capture day (local date of dateTimeOriginal)
+    PerDay(NonZeroUsize),
+}
+
+/// Small seeded PRNG (SplitMix64), samples are reproducible across immichctl versions.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Random index below `bound` (> 0).
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// Pick `k` of `items` at random (partial Fisher-Yates shuffle).
+    fn choose<T: Copy>(&mut self, items: &mut [T], k: usize) -> Vec<T> {
+        let k = k.min(items.len());
+        for i in 0..k {
+            let j = i + self.below(items.len() - i);
+            items.swap(i, j);
+        }
+        items[..k].to_vec()
+    }
+}
+
+impl ImmichCtl {
+    /// Reduce the selection to a random sample. The same `seed` on the same selection
+    /// always yields the same sample, without seed a random one is used and printed.
+    pub fn assets_sample(&mut self, mode: SampleMode, seed: Option<u64>) -> Result<()> {
+        if let SampleMode::Percent(percent) = mode
+            && !(percent > 0.0 && percent <= 100.0)
+        {
+            bail!(
+                "Invalid --percent {}, expected a value in (0, 100]",
+                percent
+            );
+        }
+        let mut sel = self.load_assets();
+        if sel.is_empty() {
+            eprintln!("Selection is empty, nothing to sample.");
+            return Ok(());
+        }
+        let seed = seed.unwrap_or_else(|| Uuid::new_v4().as_u64_pair().0);
+        let keep = self.sample_ids(sel.iter_assets(), mode, seed);
+        let total = sel.len();
+        if self.dry_run {
+            eprintln!(
+                "Dry run: would keep {} of {} asset(s) in selection (seed {}).",
+                keep.len(),
+                total,
+                seed
+            );
+            return Ok(());
+        }
+        sel.retain(|asset| keep.binary_search(&asset.id).is_ok());
+        sel.save()?;
+        eprintln!(
+            "Kept {} of {} asset(s) in selection (seed {}).",
+            sel.len(),
+            total,
+            seed
+        );
+        Ok(())
+    }
+
+    /// Sorted ids of the sampled assets. Assets are ordered by capture time and id first,
+    /// so that the sample only depends on the seed and not on the order of the selection.
+    fn sample_ids<'a>(
+        &self,
+        assets: impl Iterator<Item = &'a AssetResponseDto>,
+        mode: SampleMode,
+        seed: u64,
+    ) -> Vec<Uuid> {
+        let mut assets: Vec<_> = assets
+            .map(|a| (Self::get_date_time_original(a, self.assume_tz), a.id))
+            .collect();
+        assets.sort();
+        let mut rng = SplitMix64(seed);
+        let mut keep = match mode {
+            SampleMode::Count(count) => {
+                let mut ids: Vec<_> = assets.iter().map(|(_, id)| *id).collect();
+                rng.choose(&mut ids, count.get())
+            }
+            SampleMode::Percent(percent) => {
+                // never round a non-empty selection down to an empty sample
+                let count = (assets.len() as f64 * percent / 100.0).round().max(1.0) as usize;
+                let mut ids: Vec<_> = assets.iter().map(|(_, id)| *id).collect();
+                rng.choose(&mut ids, count)
+            }
+            SampleMode::PerDay(per_day) => {
+                let mut days: BTreeMap<NaiveDate, Vec<Uuid>> = BTreeMap::new();
+                for (taken, id) in &assets {
+                    days.entry(taken.date_naive()).or_default().push(*id);
+                }
+                days.values_mut()
+                    .flat_map(|ids| rng.choose(ids, per_day.get()))
+                    .collect()
+            }
+        };
+        keep.sort();
+        keep
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::immichctl::asset_cmd::tests::create_asset_with_timestamps;
+    use crate::immichctl::assets::Assets;
+    use chrono::{TimeZone, Utc};
+
+    /// `per_day` assets on each of `days` days
+    fn create_assets(days: u32, per_day: u32) -> Vec<AssetResponseDto> {
+        let mut assets = vec![];
+        for day in 1..=days {
+            for hour in 0..per_day {
+                let ts = Utc.with_ymd_and_hms(2024, 1, day, hour, 0, 0).unwrap();
+                assets.push(create_asset_with_timestamps(ts, ts));
+            }
+        }
+        assets
+    }
+
+    #[test]
+    fn test_sample_count() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+        let assets = create_assets(2, 10);
+        assert_eq!(
+            ctl.sample_ids(
+                assets.iter(),
+                SampleMode::Count(NonZeroUsize::new(7).unwrap()),
+                1
+            )
+            .len(),
+            7
+        );
+        assert_eq!(
+            ctl.sample_ids(
+                assets.iter(),
+                SampleMode::Count(NonZeroUsize::new(50).unwrap()),
+                1
+            )
+            .len(),
+            20
+        );
+    }
+
+    #[test]
+    fn test_sample_percent() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+        let assets = create_assets(3, 11);
+        let sample = |percent| {
+            ctl.sample_ids(assets.iter(), SampleMode::Percent(percent), 42)
+                .len()
+        };
+        assert_eq!(sample(10.0), 3); // 3.3
+        assert_eq!(sample(50.0), 17); // 16.5
+        assert_eq!(sample(1.0), 1); // 0.33, at least one
+        assert_eq!(sample(100.0), 33);
+    }
+
+    #[test]
+    fn test_sample_percent_keeps_one() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+        let assets = create_assets(1, 2);
+        let keep = ctl.sample_ids(assets.iter(), SampleMode::Percent(10.0), 42);
+        assert_eq!(keep.len(), 1);
+        assert!(assets.iter().any(|a| a.id == keep[0]));
+        let none = ctl.sample_ids(std::iter::empty(), SampleMode::Percent(10.0), 42);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn test_sample_per_day() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+        let mut assets = create_assets(3, 5);
+        assets.extend(create_assets(4, 1).into_iter().skip(3)); // 1 asset on day 4
+        let keep = ctl.sample_ids(
+            assets.iter(),
+            SampleMode::PerDay(NonZeroUsize::new(2).unwrap()),
+            7,
+        );
+        assert_eq!(keep.len(), 2 + 2 + 2 + 1);
+        let mut per_day: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+        for asset in assets.iter().filter(|a| keep.contains(&a.id)) {
+            *per_day
+                .entry(ImmichCtl::get_date_time_original(asset, None).date_naive())
+                .or_default() += 1;
+        }
+        assert_eq!(
+            per_day.values().copied().collect::<Vec<_>>(),
+            vec![2, 2, 2, 1]
+        );
+    }
+
+    #[test]
+    fn test_sample_seed_is_reproducible() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+        let assets = create_assets(2, 20);
+        let sample = |seed, rev: bool| {
+            let mut assets: Vec<_> = assets.iter().collect();
+            if rev {
+                assets.reverse();
+            }
+            ctl.sample_ids(assets.into_iter(), SampleMode::Percent(25.0), seed)
+        };
+        assert_eq!(sample(5, false), sample(5, true));
+        assert_ne!(sample(5, false), sample(6, false));
+    }
+
+    #[test]
+    fn test_assets_sample() -> Result<()> {
+        let config_dir = tempfile::tempdir().unwrap();
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
+        let mut sel = Assets::load(&ctl.assets_file);
+        for asset in create_assets(2, 4) {
+            sel.add_asset(asset);
+        }
+        sel.save()?;
+
+        ctl.set_dry_run(true);
+        ctl.assets_sample(SampleMode::PerDay(NonZeroUsize::new(1).unwrap()), Some(3))?;
+        assert_eq!(Assets::load(&ctl.assets_file).len(), 8);
+        ctl.set_dry_run(false);
+        ctl.assets_sample(SampleMode::PerDay(NonZeroUsize::new(1).unwrap()), Some(3))?;
+        assert_eq!(Assets::load(&ctl.assets_file).len(), 2);
+
+        assert_eq!(
+            ctl.assets_sample(SampleMode::Percent(0.0), None)
+                .unwrap_err()
+                .to_string(),
+            "Invalid --percent 0, expected a value in (0, 100]"
+        );
+        Ok(())
+    }
+}
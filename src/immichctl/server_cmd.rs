@@ -1,6 +1,46 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
+use std::io::{BufRead, Write};
+use std::num::NonZeroU64;
 
 use super::ImmichCtl;
+use super::api_error::{ApiContext, auth_context};
+use super::types::MetadataSearchDto;
+
+/// Normalize a server URL entered by the user: `https://` is assumed if the scheme is
+/// missing, a trailing `/` or `/api` is removed (the client appends `/api` itself).
+/// URLs with `${VAR}` references are kept as entered, they are expanded on use.
+fn normalize_server_url(input: &str) -> Result<String> {
+    let input = input.trim();
+    if input.is_empty() {
+        bail!("No server URL given, expected e.g. https://immich.example.com");
+    }
+    if input.contains("${") {
+        return Ok(input.to_string());
+    }
+    let with_scheme = if input.contains("://") {
+        input.to_string()
+    } else {
+        format!("https://{}", input)
+    };
+    let url = reqwest::Url::parse(&with_scheme)
+        .ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https") && url.host().is_some())
+        .with_context(|| {
+            format!(
+                "Invalid server URL '{}', expected e.g. https://immich.example.com",
+                input
+            )
+        })?;
+    if url.query().is_some() || url.fragment().is_some() {
+        bail!(
+            "Invalid server URL '{}', expected e.g. https://immich.example.com",
+            input
+        );
+    }
+    let url = url.as_str().trim_end_matches('/');
+    let url = url.strip_suffix("/api").unwrap_or(url);
+    Ok(url.trim_end_matches('/').to_string())
+}
 
 impl ImmichCtl {
     pub async fn version(&self) -> Result<()> {
@@ -11,8 +51,8 @@ impl ImmichCtl {
             let response = self
                 .immich()?
                 .get_server_version()
-                .await
-                .context("Could not connect to the server to get the version")?;
+                .api_context("Could not connect to the server to get the version")
+                .await?;
             println!(
                 "Immich server version: {}.{}.{}",
                 response.major, response.minor, response.patch
@@ -25,29 +65,163 @@ impl ImmichCtl {
 
     pub async fn login(&mut self, server: &str, apikey: &str) -> Result<()> {
         let mut temp_config = self.config.clone();
-        temp_config.server = server.to_string();
-        temp_config.apikey = apikey.to_string();
-        let immich = Self::build_client(&temp_config)?;
+        temp_config.set_login(server, apikey);
+        let immich = Self::build_client(&temp_config, &self.request_id)?;
 
         immich
             .validate_access_token()
+            .api_context("Login failed. Could not connect to the server.")
             .await
-            .context("Login failed. Could not connect to the server.")?;
+            .map_err(|err| auth_context(err, "Login failed. Invalid API key"))?;
 
         self.config = temp_config;
         self.immich = Ok(immich);
 
         eprintln!("Login successful to server: {}", server);
         self.config.save()?;
+        match self.immich()?.get_my_user().await {
+            Ok(user) => eprintln!("Logged in as {} ({})", user.name, user.email),
+            Err(_) => eprintln!("Logged in, but could not determine the user of the API key."),
+        }
         Ok(())
     }
 
+    /// Interactive login: prompt for the server URL (defaults to the current server) and
+    /// the API key without echo, then login as with `login <server> --apikey <key>`.
+    pub async fn login_wizard(&mut self) -> Result<()> {
+        self.assert_interactive("immichctl login")?;
+        let current = self.config.logged_in().then(|| self.config.server.clone());
+        if let Some(server) = &current {
+            eprintln!("Currently logged in to: {}", server);
+        }
+        let server = Self::prompt_server(
+            &mut std::io::stdin().lock(),
+            &mut std::io::stderr(),
+            current.as_deref(),
+        )?;
+        let apikey = rpassword::prompt_password("API key: ").context("Could not read API key")?;
+        let apikey = apikey.trim();
+        if apikey.is_empty() {
+            bail!("No API key given, login aborted.");
+        }
+        self.login(&server, apikey).await
+    }
+
+    /// Prompt for the server URL until a valid one is entered, an empty input keeps the
+    /// `current` server.
+    fn prompt_server(
+        input: &mut dyn BufRead,
+        output: &mut dyn Write,
+        current: Option<&str>,
+    ) -> Result<String> {
+        let prompt = match current {
+            Some(server) => format!("Server URL [{}]: ", server),
+            None => "Server URL: ".to_string(),
+        };
+        loop {
+            let line = Self::prompt_line(input, output, &prompt)?;
+            let line = match (line.as_str(), current) {
+                ("", Some(server)) => server,
+                (line, _) => line,
+            };
+            match normalize_server_url(line) {
+                Ok(server) => return Ok(server),
+                Err(err) => writeln!(output, "{}", err)?,
+            }
+        }
+    }
+
+    /// Write `prompt` to `output` and read one line from `input`. Fails at end of input.
+    fn prompt_line(
+        input: &mut dyn BufRead,
+        output: &mut dyn Write,
+        prompt: &str,
+    ) -> Result<String> {
+        write!(output, "{}", prompt)?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            bail!("No input, login aborted.");
+        }
+        Ok(line.trim().to_string())
+    }
+
     pub fn show_login(&self) -> Result<()> {
         self.assert_logged_in()?;
         println!("Currently logged in to: {}", self.config.server);
         Ok(())
     }
 
+    /// Check config, connectivity, api key and access to the endpoints used by immichctl
+    /// and print a pass/fail report.
+    pub async fn doctor(&self) -> Result<()> {
+        let config = self.assert_logged_in();
+        if !Self::print_check("Login config present", config) {
+            bail!("Doctor found problems, please login first.");
+        }
+        let immich = self.immich()?;
+        let mut checks = vec![];
+
+        let ping = immich.ping_server().await.map(|_| ());
+        checks.push(Self::print_check(
+            &format!("Server reachable ({})", self.config.server),
+            ping.context("Could not ping server"),
+        ));
+        let token = match immich.validate_access_token().await {
+            Ok(resp) if resp.auth_status => Ok(()),
+            Ok(_) => Err(anyhow::anyhow!("API key was not accepted")),
+            Err(err) => Err(err).context("Could not validate API key"),
+        };
+        checks.push(Self::print_check("API key valid", token));
+        let tags = immich.get_all_tags().await.map(|_| ());
+        checks.push(Self::print_check(
+            "Read tags",
+            tags.context("Could not retrieve tags"),
+        ));
+        let albums = immich
+            .get_all_albums(None, None, None, None, None)
+            .await
+            .map(|_| ());
+        checks.push(Self::print_check(
+            "Read albums",
+            albums.context("Could not retrieve albums"),
+        ));
+        let search_dto = MetadataSearchDto {
+            size: NonZeroU64::new(1),
+            ..Default::default()
+        };
+        let search = immich
+            .search_assets(None, None, &search_dto)
+            .await
+            .map(|_| ());
+        checks.push(Self::print_check(
+            "Search assets",
+            search.context("Search failed"),
+        ));
+
+        let failed = checks.iter().filter(|passed| !**passed).count();
+        if failed > 0 {
+            bail!("{} of {} checks failed.", failed, checks.len());
+        }
+        eprintln!("All checks passed.");
+        Ok(())
+    }
+
+    /// Print the result of a doctor check, returns true if passed.
+    fn print_check(name: &str, result: Result<()>) -> bool {
+        match result {
+            Ok(()) => {
+                println!("[PASS] {}", name);
+                true
+            }
+            Err(err) => {
+                println!("[FAIL] {}: {:#}", name, err);
+                false
+            }
+        }
+    }
+
     pub fn logout(&mut self) -> Result<()> {
         self.config.logout();
         self.config.save()?;
@@ -62,6 +236,59 @@ mod tests {
     use crate::immichctl::{ImmichCtl, tests::create_immichctl_with_server};
     use mockito::Server;
 
+    #[test]
+    fn test_normalize_server_url() {
+        let cases = [
+            ("immich.example.com", "https://immich.example.com"),
+            (" http://immich:2283/ ", "http://immich:2283"),
+            (
+                "https://example.com/immich/api/",
+                "https://example.com/immich",
+            ),
+            ("HTTPS://Immich.Example.com", "https://immich.example.com"),
+            ("${IMMICH_HOST}", "${IMMICH_HOST}"),
+            (
+                " https://${IMMICH_HOST}:${Port} ",
+                "https://${IMMICH_HOST}:${Port}",
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(normalize_server_url(input).unwrap(), expected);
+        }
+        for input in ["", "ftp://immich", "http://", "https://immich?x=1"] {
+            assert!(normalize_server_url(input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_prompt_line() {
+        let mut output = Vec::new();
+        let line = ImmichCtl::prompt_line(&mut &b"  immich \n"[..], &mut output, "Server URL: ");
+        assert_eq!(line.unwrap(), "immich");
+        assert_eq!(String::from_utf8(output).unwrap(), "Server URL: ");
+        let eof = ImmichCtl::prompt_line(&mut &b""[..], &mut Vec::new(), "Server URL: ");
+        assert_eq!(eof.unwrap_err().to_string(), "No input, login aborted.");
+    }
+
+    #[test]
+    fn test_prompt_server() {
+        let prompt = |input: &str, current| {
+            let mut output = Vec::new();
+            let server = ImmichCtl::prompt_server(&mut input.as_bytes(), &mut output, current);
+            (server.unwrap(), String::from_utf8(output).unwrap())
+        };
+        // Enter keeps the current server, also a template with env variables
+        let (server, output) = prompt("\n", Some("${IMMICH_HOST}"));
+        assert_eq!(server, "${IMMICH_HOST}");
+        assert_eq!(output, "Server URL [${IMMICH_HOST}]: ");
+        let (server, _) = prompt("https://${IMMICH_HOST}/\n", None);
+        assert_eq!(server, "https://${IMMICH_HOST}/");
+        // invalid input is reported and asked again
+        let (server, output) = prompt("ftp://immich\nImmich.example.com/api\n", None);
+        assert_eq!(server, "https://immich.example.com");
+        assert!(output.contains("Invalid server URL 'ftp://immich'"));
+    }
+
     #[tokio::test]
     async fn test_login_logout() -> Result<()> {
         let config_dir = tempfile::tempdir().unwrap();
@@ -76,10 +303,35 @@ mod tests {
             .create_async()
             .await;
 
+        let me_mock = server
+            .mock("GET", "/api/users/me")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "avatarColor": "primary",
+                    "createdAt": "2024-01-01T00:00:00Z",
+                    "email": "jane@example.com",
+                    "id": "5460dc82-2353-47d1-878c-2f15a1084001",
+                    "isAdmin": false,
+                    "name": "Jane",
+                    "oauthId": "",
+                    "profileChangedAt": "2024-01-01T00:00:00Z",
+                    "profileImagePath": "",
+                    "shouldChangePassword": false,
+                    "status": "active",
+                    "updatedAt": "2024-01-01T00:00:00Z"
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
         ctl.login(&server.url(), "apikey").await?;
         ctl.immich()?;
 
         mock.assert_async().await;
+        me_mock.assert_async().await;
 
         assert!(ctl.config.logged_in());
 
@@ -89,6 +341,32 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_login_without_user() -> Result<()> {
+        let config_dir = tempfile::tempdir().unwrap();
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
+        let mut server = Server::new_async().await;
+
+        let _mock = server
+            .mock("POST", "/api/auth/validateToken")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"authStatus":true}"#)
+            .create_async()
+            .await;
+        let me_mock = server
+            .mock("GET", "/api/users/me")
+            .with_status(403)
+            .create_async()
+            .await;
+
+        ctl.login(&server.url(), "apikey").await?;
+
+        me_mock.assert_async().await;
+        assert!(ctl.config.logged_in());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_login_failed() {
         let config_dir = tempfile::tempdir().unwrap();
@@ -108,12 +386,75 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(
             result.err().unwrap().to_string(),
-            "Login failed. Could not connect to the server."
+            "Login failed. Invalid API key (401): Unauthorized"
         );
         mock.assert_async().await;
         assert!(!ctl.config.logged_in());
     }
 
+    #[tokio::test]
+    async fn test_doctor() -> Result<()> {
+        let (ctl, mut server) = create_immichctl_with_server().await;
+
+        let _ping_mock = server
+            .mock("GET", "/api/server/ping")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"res":"pong"}"#)
+            .create_async()
+            .await;
+        let _token_mock = server
+            .mock("POST", "/api/auth/validateToken")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"authStatus":true}"#)
+            .create_async()
+            .await;
+        let _tags_mock = server
+            .mock("GET", "/api/tags")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body("[]")
+            .create_async()
+            .await;
+        let _albums_mock = server
+            .mock("GET", "/api/albums")
+            .with_status(403)
+            .create_async()
+            .await;
+        let _search_mock = server
+            .mock("POST", "/api/search/metadata")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                    "assets": {"count": 0, "facets": [], "items": [], "nextPage": null, "total": 0}
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let result = ctl.doctor().await;
+
+        assert_eq!(result.unwrap_err().to_string(), "1 of 5 checks failed.");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_doctor_not_logged_in() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+
+        let result = ctl.doctor().await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Doctor found problems, please login first."
+        );
+    }
+
     #[tokio::test]
     async fn test_version_not_logged_in() -> Result<()> {
         let config_dir = tempfile::tempdir().unwrap();
@@ -0,0 +1,399 @@
+use super::api_error::ApiContext;
+use super::types::{AssetResponseDto, SharedLinkLoginDto};
+use super::{Client, ImmichCtl};
+use anyhow::{Context, Result, bail};
+use reqwest::header::{COOKIE, HeaderMap, HeaderValue, SET_COOKIE};
+use std::io::BufRead;
+
+/// Env variable with the shared link password, keeps it out of the process list
+const LINK_PASSWORD_ENV: &str = "IMMICHCTL_LINK_PASSWORD";
+
+/// A shared link is identified by its key (`/share/<key>`) or a custom slug (`/s/<slug>`).
+#[derive(Debug, PartialEq, Eq)]
+enum SharedLinkRef {
+    Key(String),
+    Slug(String),
+}
+
+impl SharedLinkRef {
+    /// Parse a shared link url or a plain key.
+    fn parse(link: &str) -> Result<SharedLinkRef> {
+        let link = link.trim();
+        let path = match link.split_once("://") {
+            Some((_, rest)) => rest.split_once('/').map_or("", |(_, path)| path),
+            None if link.contains('/') => link,
+            None if !link.is_empty() => return Ok(SharedLinkRef::Key(link.to_string())),
+            None => bail!("Empty shared link"),
+        };
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let mut segments = path.split('/').filter(|s| !s.is_empty());
+        match (segments.next(), segments.next()) {
+            (Some("share"), Some(key)) => Ok(SharedLinkRef::Key(key.to_string())),
+            (Some("s"), Some(slug)) => Ok(SharedLinkRef::Slug(slug.to_string())),
+            _ => bail!(
+                "Invalid shared link '{}', expected a key or an url like https://<server>/share/<key>",
+                link
+            ),
+        }
+    }
+
+    fn key(&self) -> Option<&str> {
+        match self {
+            SharedLinkRef::Key(key) => Some(key),
+            SharedLinkRef::Slug(_) => None,
+        }
+    }
+
+    fn slug(&self) -> Option<&str> {
+        match self {
+            SharedLinkRef::Key(_) => None,
+            SharedLinkRef::Slug(slug) => Some(slug),
+        }
+    }
+}
+
+impl ImmichCtl {
+    /// The shared link password given by `--link-password` (`-` reads it from stdin),
+    /// otherwise by the `IMMICHCTL_LINK_PASSWORD` env variable.
+    pub fn link_password(password: Option<&str>) -> Result<Option<String>> {
+        Self::resolve_link_password(
+            password,
+            std::env::var(LINK_PASSWORD_ENV).ok(),
+            &mut std::io::stdin().lock(),
+        )
+    }
+
+    fn resolve_link_password(
+        password: Option<&str>,
+        env_password: Option<String>,
+        input: &mut dyn BufRead,
+    ) -> Result<Option<String>> {
+        match password {
+            Some("-") => {
+                let mut line = String::new();
+                input
+                    .read_line(&mut line)
+                    .context("Could not read shared link password from stdin")?;
+                let line = line.trim_end_matches(['\r', '\n']);
+                if line.is_empty() {
+                    bail!("No shared link password on stdin");
+                }
+                Ok(Some(line.to_string()))
+            }
+            Some(password) => Ok(Some(password.to_string())),
+            None => Ok(env_password.filter(|p| !p.is_empty())),
+        }
+    }
+
+    /// All assets behind a shared link. Album links are enumerated via the album timeline.
+    pub async fn shared_link_assets(
+        &self,
+        link: &str,
+        password: Option<&str>,
+    ) -> Result<Vec<AssetResponseDto>> {
+        let link = SharedLinkRef::parse(link)?;
+        let client = match password {
+            Some(password) => self.shared_link_login(&link, password).await?,
+            None => self.immich()?.clone(),
+        };
+        let (key, slug) = (link.key(), link.slug());
+        let shared_link = client
+            .get_my_shared_link(key, slug)
+            .api_context(
+                "Could not retrieve shared link, use --link-password for password protected links",
+            )
+            .await?
+            .into_inner();
+        let Some(album) = shared_link.album else {
+            return Ok(shared_link.assets);
+        };
+
+        let buckets = client
+            .get_time_buckets(
+                Some(&album.id),
+                None,
+                None,
+                None,
+                key,
+                None,
+                None,
+                None,
+                slug,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .api_context("Could not retrieve shared album")
+            .await?;
+        let mut asset_ids = Vec::new();
+        for bucket in buckets.iter() {
+            let bucket_assets = client
+                .get_time_bucket(
+                    Some(&album.id),
+                    None,
+                    None,
+                    None,
+                    key,
+                    None,
+                    None,
+                    None,
+                    slug,
+                    None,
+                    &bucket.time_bucket,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .api_context("Could not retrieve shared album")
+                .await?;
+            asset_ids.extend(bucket_assets.into_inner().id);
+        }
+
+        let mut assets = Vec::with_capacity(asset_ids.len());
+        for (i, id) in asset_ids.iter().enumerate() {
+            let uuid = uuid::Uuid::parse_str(id)
+                .with_context(|| format!("Invalid asset id '{}' in shared album", id))?;
+            let asset = client
+                .get_asset_info(&uuid, key, slug)
+                .with_api_context(|| format!("Could not retrieve asset '{}'", id))
+                .await?;
+            assets.push(asset.into_inner());
+            self.eprint_progress_indicator(i, asset_ids.len(), 50);
+        }
+        Ok(assets)
+    }
+
+    /// Login to a password protected shared link, returns a client that sends the
+    /// session cookie of the link.
+    async fn shared_link_login(&self, link: &SharedLinkRef, password: &str) -> Result<Client> {
+        let dto = SharedLinkLoginDto {
+            password: password.to_string(),
+        };
+        let resp = self
+            .immich()?
+            .shared_link_login(link.key(), link.slug(), &dto)
+            .api_context("Shared link login failed, check --link-password")
+            .await?;
+        let cookie = Self::session_cookie(resp.headers())?;
+        let mut headers = HeaderMap::new();
+        headers.insert(COOKIE, cookie);
+        Self::build_client_with_headers(&self.config, &self.request_id, headers)
+    }
+
+    /// `Cookie` header value with all cookies set by a response.
+    fn session_cookie(headers: &HeaderMap) -> Result<HeaderValue> {
+        let cookies: Vec<_> = headers
+            .get_all(SET_COOKIE)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .filter_map(|v| v.split(';').next())
+            .collect();
+        if cookies.is_empty() {
+            bail!("Shared link login didn't return a session cookie");
+        }
+        Ok(HeaderValue::from_str(&cookies.join("; "))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::immichctl::album_cmd::tests::create_album;
+    use crate::immichctl::asset_cmd::tests::create_asset_for_download;
+    use crate::immichctl::tests::create_immichctl_with_server;
+
+    #[test]
+    fn test_parse_shared_link() {
+        assert_eq!(
+            SharedLinkRef::parse("abc123").unwrap(),
+            SharedLinkRef::Key("abc123".to_string())
+        );
+        assert_eq!(
+            SharedLinkRef::parse("https://immich.example.com/share/abc123?x=1").unwrap(),
+            SharedLinkRef::Key("abc123".to_string())
+        );
+        assert_eq!(
+            SharedLinkRef::parse("http://immich:2283/s/vacation/").unwrap(),
+            SharedLinkRef::Slug("vacation".to_string())
+        );
+        assert!(SharedLinkRef::parse("https://immich.example.com/photos/1").is_err());
+        assert!(SharedLinkRef::parse("").is_err());
+    }
+
+    #[test]
+    fn test_resolve_link_password() {
+        let resolve = |password, env: Option<&str>, input: &str| {
+            ImmichCtl::resolve_link_password(
+                password,
+                env.map(str::to_string),
+                &mut input.as_bytes(),
+            )
+        };
+        assert_eq!(
+            resolve(Some("secret"), Some("env"), "").unwrap(),
+            Some("secret".to_string())
+        );
+        assert_eq!(
+            resolve(Some("-"), Some("env"), "from stdin\nnext line\n").unwrap(),
+            Some("from stdin".to_string())
+        );
+        assert!(resolve(Some("-"), None, "").is_err());
+        assert_eq!(
+            resolve(None, Some("env"), "").unwrap(),
+            Some("env".to_string())
+        );
+        assert_eq!(resolve(None, Some(""), "").unwrap(), None);
+        assert_eq!(resolve(None, None, "").unwrap(), None);
+    }
+
+    #[test]
+    fn test_session_cookie() {
+        let mut headers = HeaderMap::new();
+        assert!(ImmichCtl::session_cookie(&headers).is_err());
+        headers.append(
+            SET_COOKIE,
+            HeaderValue::from_static("immich_shared_link_token=tok; Path=/; HttpOnly"),
+        );
+        headers.append(SET_COOKIE, HeaderValue::from_static("other=1"));
+        assert_eq!(
+            ImmichCtl::session_cookie(&headers).unwrap(),
+            "immich_shared_link_token=tok; other=1"
+        );
+    }
+
+    fn shared_link_json(
+        album: Option<serde_json::Value>,
+        assets: &[AssetResponseDto],
+    ) -> serde_json::Value {
+        serde_json::json!({
+            "album": album,
+            "allowDownload": true,
+            "allowUpload": false,
+            "assets": assets,
+            "createdAt": "2024-01-01T00:00:00Z",
+            "description": null,
+            "expiresAt": null,
+            "id": "c1a7f1a9-7394-49f7-a5a3-e876a7e16ab1",
+            "key": "abc123",
+            "password": null,
+            "showMetadata": true,
+            "slug": null,
+            "type": if album.is_some() { "ALBUM" } else { "INDIVIDUAL" },
+            "userId": "b1a7f1a9-7394-49f7-a5a3-e876a7e16ab1",
+        })
+    }
+
+    #[tokio::test]
+    async fn test_shared_link_assets_individual_with_password() -> Result<()> {
+        let (ctl, mut server) = create_immichctl_with_server().await;
+        let asset = create_asset_for_download(uuid::Uuid::new_v4(), "A.jpg", "/upload/A.jpg");
+        let link = shared_link_json(None, std::slice::from_ref(&asset));
+
+        let login_mock = server
+            .mock("POST", "/api/shared-links/login?key=abc123")
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({"password": "secret"}),
+            ))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_header("set-cookie", "immich_shared_link_token=tok; Path=/")
+            .with_body(link.to_string())
+            .create_async()
+            .await;
+        let me_mock = server
+            .mock("GET", "/api/shared-links/me?key=abc123")
+            .match_header("cookie", "immich_shared_link_token=tok")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(link.to_string())
+            .create_async()
+            .await;
+
+        let assets = ctl
+            .shared_link_assets(&format!("{}/share/abc123", server.url()), Some("secret"))
+            .await?;
+        login_mock.assert_async().await;
+        me_mock.assert_async().await;
+        assert_eq!(assets.len(), 1);
+        assert_eq!(assets[0].id, asset.id);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_shared_link_assets_album() -> Result<()> {
+        let (ctl, mut server) = create_immichctl_with_server().await;
+        let album_id = "a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1";
+        let album = create_album(album_id, "Shared");
+        let asset = create_asset_for_download(uuid::Uuid::new_v4(), "A.jpg", "/upload/A.jpg");
+        let link = shared_link_json(Some(serde_json::to_value(&album)?), &[]);
+
+        let _me_mock = server
+            .mock("GET", "/api/shared-links/me?key=abc123")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(link.to_string())
+            .create_async()
+            .await;
+        let _buckets_mock = server
+            .mock("GET", "/api/timeline/buckets")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("albumId".into(), album_id.into()),
+                mockito::Matcher::UrlEncoded("key".into(), "abc123".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"[{"count": 1, "timeBucket": "2024-01-01"}]"#)
+            .create_async()
+            .await;
+        let _bucket_mock = server
+            .mock("GET", "/api/timeline/bucket")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "timeBucket".into(),
+                "2024-01-01".into(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "createdAt": ["2024-01-01T00:00:00Z"],
+                    "duration": [null],
+                    "fileCreatedAt": ["2024-01-01T00:00:00Z"],
+                    "id": [asset.id.to_string()],
+                    "isFavorite": [false],
+                    "isImage": [true],
+                    "isTrashed": [false],
+                    "livePhotoVideoId": [null],
+                    "localOffsetHours": [0],
+                    "ownerId": ["b1a7f1a9-7394-49f7-a5a3-e876a7e16ab1"],
+                    "projectionType": [null],
+                    "ratio": [1.5],
+                    "thumbhash": [null],
+                    "visibility": ["timeline"],
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let asset_mock = server
+            .mock(
+                "GET",
+                format!("/api/assets/{}?key=abc123", asset.id).as_str(),
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&asset)?)
+            .create_async()
+            .await;
+
+        let assets = ctl.shared_link_assets("abc123", None).await?;
+        asset_mock.assert_async().await;
+        assert_eq!(assets.len(), 1);
+        assert_eq!(assets[0].id, asset.id);
+        Ok(())
+    }
+}
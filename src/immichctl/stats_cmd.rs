@@ -0,0 +1,164 @@
+use super::ImmichCtl;
+use super::types::{AssetResponseDto, AssetTypeEnum};
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset};
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// Output format of `assets stats`
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatsFormat {
+    /// Aligned key/value lines
+    Text,
+    /// Json format
+    Json,
+}
+
+/// Summary of the selected assets.
+#[derive(Debug, Default, PartialEq, Serialize)]
+struct SelectionStats {
+    total: usize,
+    images: usize,
+    videos: usize,
+    audio: usize,
+    other: usize,
+    favorites: usize,
+    earliest: Option<DateTime<FixedOffset>>,
+    latest: Option<DateTime<FixedOffset>>,
+    /// Distinct timezones of dateTimeOriginal, west to east
+    timezones: Vec<String>,
+}
+
+impl ImmichCtl {
+    /// Print counts by type, favorites, the date span and the timezones of the selection.
+    pub fn assets_stats(&self, format: StatsFormat) -> Result<()> {
+        let sel = self.load_assets();
+        let stats = self.selection_stats(sel.iter_assets());
+        match format {
+            StatsFormat::Json => self.print_json(&stats)?,
+            StatsFormat::Text => {
+                for (key, value) in Self::stats_lines(&stats) {
+                    println!("{:<11}{}", format!("{}:", key), value);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn selection_stats<'a>(
+        &self,
+        assets: impl Iterator<Item = &'a AssetResponseDto>,
+    ) -> SelectionStats {
+        let mut stats = SelectionStats::default();
+        let mut offsets = BTreeSet::new();
+        for asset in assets {
+            stats.total += 1;
+            match asset.type_ {
+                AssetTypeEnum::Image => stats.images += 1,
+                AssetTypeEnum::Video => stats.videos += 1,
+                AssetTypeEnum::Audio => stats.audio += 1,
+                AssetTypeEnum::Other => stats.other += 1,
+            }
+            if asset.is_favorite {
+                stats.favorites += 1;
+            }
+            let taken = Self::get_date_time_original(asset, self.assume_tz);
+            offsets.insert(taken.offset().local_minus_utc());
+            stats.earliest = Some(stats.earliest.map_or(taken, |t| t.min(taken)));
+            stats.latest = Some(stats.latest.map_or(taken, |t| t.max(taken)));
+        }
+        stats.timezones = offsets
+            .into_iter()
+            .filter_map(FixedOffset::east_opt)
+            .map(|tz| tz.to_string())
+            .collect();
+        stats
+    }
+
+    fn stats_lines(stats: &SelectionStats) -> Vec<(&'static str, String)> {
+        let date =
+            |dt: Option<DateTime<FixedOffset>>| dt.map_or("-".to_string(), |dt| dt.to_rfc3339());
+        vec![
+            ("Total", stats.total.to_string()),
+            ("Images", stats.images.to_string()),
+            ("Videos", stats.videos.to_string()),
+            ("Audio", stats.audio.to_string()),
+            ("Other", stats.other.to_string()),
+            ("Favorites", stats.favorites.to_string()),
+            ("Earliest", date(stats.earliest)),
+            ("Latest", date(stats.latest)),
+            ("Timezones", stats.timezones.join(", ")),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::immichctl::asset_cmd::tests::create_asset_with_timestamps;
+    use chrono::{TimeDelta, TimeZone, Utc};
+
+    #[test]
+    fn test_selection_stats() {
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let photo = create_asset_with_timestamps(ts, ts);
+        // taken earlier in +02:00
+        let earlier = ts - TimeDelta::days(1);
+        let mut favorite = create_asset_with_timestamps(earlier, earlier + TimeDelta::hours(2));
+        favorite.is_favorite = true;
+        // taken later in -05:00
+        let later = ts + TimeDelta::days(1);
+        let mut video = create_asset_with_timestamps(later, later - TimeDelta::hours(5));
+        video.type_ = AssetTypeEnum::Video;
+
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+        let stats = ctl.selection_stats([&photo, &favorite, &video].into_iter());
+        assert_eq!(
+            stats,
+            SelectionStats {
+                total: 3,
+                images: 2,
+                videos: 1,
+                audio: 0,
+                other: 0,
+                favorites: 1,
+                earliest: Some(DateTime::parse_from_rfc3339("2023-12-31T12:00:00+02:00").unwrap()),
+                latest: Some(DateTime::parse_from_rfc3339("2024-01-02T05:00:00-05:00").unwrap()),
+                timezones: vec![
+                    "-05:00".to_string(),
+                    "+00:00".to_string(),
+                    "+02:00".to_string()
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_selection_stats_assume_tz() {
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let photo = create_asset_with_timestamps(ts, ts + TimeDelta::hours(2));
+        let config_dir = tempfile::tempdir().unwrap();
+        let mut ctl = ImmichCtl::with_config_dir(config_dir.path());
+        ctl.set_assume_tz(FixedOffset::west_opt(5 * 3600));
+
+        let stats = ctl.selection_stats([&photo].into_iter());
+        assert_eq!(stats.timezones, vec!["-05:00".to_string()]);
+        assert_eq!(
+            stats.earliest,
+            Some(DateTime::parse_from_rfc3339("2024-01-01T05:00:00-05:00").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_selection_stats_empty() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let ctl = ImmichCtl::with_config_dir(config_dir.path());
+        let stats = ctl.selection_stats(std::iter::empty());
+        assert_eq!(stats, SelectionStats::default());
+        let lines = ImmichCtl::stats_lines(&stats);
+        assert_eq!(lines[0], ("Total", "0".to_string()));
+        assert_eq!(lines[6], ("Earliest", "-".to_string()));
+        assert_eq!(lines[8], ("Timezones", String::new()));
+    }
+}
@@ -1,49 +1,108 @@
 use super::ImmichCtl;
-use super::assets::Assets;
-use super::types::{BulkIdsDto, TagResponseDto};
-use anyhow::{Context, Result, bail};
+use super::api_error::ApiContext;
+use super::types::{BulkIdErrorReason, TagResponseDto, TagUpdateDto, TagUpdateDtoColor};
+use anyhow::{Context, Result, anyhow, bail};
+use regex::Regex;
 use uuid::Uuid;
 
+/// How a tag name given on the command line is matched against tags.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TagMatch {
+    /// Full or simple name, must be unique
+    #[default]
+    Exact,
+    /// Glob pattern on the full name, `*` matches any characters (including `/`), `?` one character
+    Glob,
+    /// Regular expression on the full name
+    Regex,
+}
+
 impl ImmichCtl {
-    pub async fn tag_assign(&mut self, name: &str) -> Result<()> {
-        let sel = Assets::load(&self.assets_file);
+    pub async fn tag_assign(&mut self, name: &str, tag_match: TagMatch) -> Result<()> {
+        let sel = self.load_assets();
         if sel.is_empty() {
-            eprintln!("Selection is empty, nothing to tag.");
+            self.eprint_or_count("Selection is empty, nothing to tag.", 0);
             return Ok(());
         }
 
-        let tag_id = self.find_tag_by_name(name).await?;
-        let dto = BulkIdsDto {
-            ids: sel.asset_uuids(),
-        };
-        let tag_resp = self
-            .immich()?
-            .tag_assets(&tag_id, &dto)
-            .await
-            .context("Could not tag assets")?;
-        let cnt = tag_resp.iter().filter(|r| r.success).count();
-        eprintln!("Tagged {} assets with '{}'.", cnt, name);
+        let tags = self.find_tags(name, tag_match).await?;
+        let mut total = 0;
+        for tag in &tags {
+            if self.dry_run {
+                eprintln!(
+                    "Dry run: would tag {} assets with '{}'.",
+                    sel.len(),
+                    tag.value
+                );
+                continue;
+            }
+            let client = self.immich()?;
+            let tag_resp = self
+                .bulk_write(&sel.asset_uuids(), |dto| async move {
+                    let resp = client
+                        .tag_assets(&tag.id, &dto)
+                        .api_context("Could not tag assets")
+                        .await?;
+                    Ok(resp.into_inner())
+                })
+                .await?;
+            let cnt = tag_resp.iter().filter(|r| r.success).count();
+            let already_tagged = tag_resp
+                .iter()
+                .filter(|r| r.error == Some(BulkIdErrorReason::Duplicate))
+                .count();
+            if !self.count_only {
+                eprintln!("Tagged {} assets with '{}'.", cnt, tag.value);
+            }
+            if self.verbose {
+                eprintln!("{}", Self::tag_details(sel.len(), cnt, already_tagged));
+            }
+            self.record_history(&format!("tag assign '{}'", tag.value), cnt);
+            total += cnt;
+        }
+        if self.count_only {
+            println!("{}", total);
+        }
         Ok(())
     }
 
+    /// Details of a tag assign: the server reports assets that already had the tag as
+    /// `duplicate` errors, they aren't counted as tagged.
+    fn tag_details(requested: usize, tagged: usize, already_tagged: usize) -> String {
+        format!(
+            "Requested {} assets: {} newly tagged, {} already tagged, {} failed.",
+            requested,
+            tagged,
+            already_tagged,
+            requested.saturating_sub(tagged + already_tagged)
+        )
+    }
+
     pub async fn tag_unassign(&mut self, name: &str) -> Result<()> {
-        let sel = Assets::load(&self.assets_file);
+        let sel = self.load_assets();
         if sel.is_empty() {
             eprintln!("Selection is empty, nothing to untag.");
             return Ok(());
         }
 
         let tag_id = self.find_tag_by_name(name).await?;
-        let dto = BulkIdsDto {
-            ids: sel.asset_uuids(),
-        };
+        if self.dry_run {
+            eprintln!("Dry run: would untag {} assets from '{}'.", sel.len(), name);
+            return Ok(());
+        }
+        let client = self.immich()?;
         let untag_resp = self
-            .immich()?
-            .untag_assets(&tag_id, &dto)
-            .await
-            .context("Could not untag assets")?;
+            .bulk_write(&sel.asset_uuids(), |dto| async move {
+                let resp = client
+                    .untag_assets(&tag_id, &dto)
+                    .api_context("Could not untag assets")
+                    .await?;
+                Ok(resp.into_inner())
+            })
+            .await?;
         let cnt = untag_resp.iter().filter(|r| r.success).count();
         eprintln!("Untagged {} assets from '{}'.", cnt, name);
+        self.record_history(&format!("tag unassign '{}'", name), cnt);
         Ok(())
     }
 
@@ -51,8 +110,8 @@ impl ImmichCtl {
         let tags_resp = self
             .immich()?
             .get_all_tags()
-            .await
-            .context("Could not retrieve tags")?;
+            .api_context("Could not retrieve tags")
+            .await?;
         let tag_id = Self::_find_tag_by_name(name, &tags_resp);
         match tag_id {
             Some(uuid) => Ok(uuid),
@@ -61,6 +120,66 @@ impl ImmichCtl {
             }
         }
     }
+    /// Find the tags matching `name`. With `TagMatch::Exact` exactly one tag is returned,
+    /// glob and regex patterns intentionally resolve to all tags whose full name matches.
+    pub async fn find_tags(&self, name: &str, tag_match: TagMatch) -> Result<Vec<TagResponseDto>> {
+        let tags_resp = self
+            .immich()?
+            .get_all_tags()
+            .api_context("Could not retrieve tags")
+            .await?;
+        if tag_match == TagMatch::Exact {
+            let Some(tag_id) = Self::_find_tag_by_name(name, &tags_resp) else {
+                bail!("Tag not found or not unique: '{}'", name);
+            };
+            return Ok(tags_resp
+                .into_inner()
+                .into_iter()
+                .filter(|t| t.id == tag_id)
+                .collect());
+        }
+
+        let re = Self::tag_pattern_regex(name, tag_match)?;
+        let tags: Vec<_> = tags_resp
+            .into_inner()
+            .into_iter()
+            .filter(|t| re.is_match(&t.value))
+            .collect();
+        if tags.is_empty() {
+            bail!("No tag matches pattern: '{}'", name);
+        }
+        if tags.len() > 1 {
+            let values: Vec<_> = tags.iter().map(|t| t.value.as_str()).collect();
+            eprintln!(
+                "Pattern '{}' matches {} tags: {}",
+                name,
+                tags.len(),
+                values.join(", ")
+            );
+        }
+        Ok(tags)
+    }
+
+    /// Anchored regex for a glob or regex tag pattern.
+    fn tag_pattern_regex(pattern: &str, tag_match: TagMatch) -> Result<Regex> {
+        let re = match tag_match {
+            TagMatch::Glob => {
+                let mut re = String::from("^");
+                for c in pattern.chars() {
+                    match c {
+                        '*' => re.push_str(".*"),
+                        '?' => re.push('.'),
+                        c => re.push_str(&regex::escape(&c.to_string())),
+                    }
+                }
+                re.push('$');
+                re
+            }
+            _ => format!("^(?:{})$", pattern),
+        };
+        Regex::new(&re).with_context(|| format!("Invalid tag pattern '{}'", pattern))
+    }
+
     /// Find a tag by its full or simple name (full name = including parent tags separated by '/').
     /// Returns the UUID of the tag if found and unambiguous.
     fn _find_tag_by_name(name: &str, tags: &[TagResponseDto]) -> Option<Uuid> {
@@ -76,12 +195,59 @@ impl ImmichCtl {
         None
     }
 
+    pub async fn tag_set_color(&self, name: &str, color: &str) -> Result<()> {
+        let color = Self::parse_tag_color(color)?;
+        let tags_resp = self
+            .immich()?
+            .get_all_tags()
+            .api_context("Could not retrieve tags")
+            .await?;
+        let Some(tag_id) = Self::_find_tag_by_name(name, &tags_resp) else {
+            bail!("Tag not found or not unique: '{}'", name);
+        };
+        let old_color = tags_resp
+            .iter()
+            .find(|t| t.id == tag_id)
+            .and_then(|t| t.color.clone())
+            .unwrap_or_else(|| "none".to_string());
+        if self.dry_run {
+            eprintln!(
+                "Dry run: would change color of tag '{}' from {} to {}.",
+                name, old_color, color
+            );
+            return Ok(());
+        }
+
+        let dto = TagUpdateDto {
+            color: Some(TagUpdateDtoColor::try_from(color.clone()).map_err(|e| anyhow!("{}", e))?),
+        };
+        self.immich()?
+            .update_tag(&tag_id, &dto)
+            .api_context("Could not update tag")
+            .await?;
+        eprintln!(
+            "Changed color of tag '{}' from {} to {}.",
+            name, old_color, color
+        );
+        self.record_history(&format!("tag set-color '{}' {}", name, color), 1);
+        Ok(())
+    }
+
+    /// Validate a `#RRGGBB` color. The leading `#` is optional.
+    fn parse_tag_color(color: &str) -> Result<String> {
+        let hex = color.strip_prefix('#').unwrap_or(color);
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            bail!("Invalid color '{}', expected #RRGGBB", color);
+        }
+        Ok(format!("#{}", hex))
+    }
+
     pub async fn tag_list(&self) -> Result<()> {
         let tags_resp = self
             .immich()?
             .get_all_tags()
-            .await
-            .context("Could not retrieve tags")?;
+            .api_context("Could not retrieve tags")
+            .await?;
         let mut tags: Vec<&TagResponseDto> = tags_resp.iter().collect();
         tags.sort_by(|a, b| a.value.cmp(&b.value));
         for tag in tags {
@@ -94,6 +260,10 @@ impl ImmichCtl {
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::immichctl::asset_cmd::tests::create_asset_for_download;
+    use crate::immichctl::assets::Assets;
+    use crate::immichctl::history::History;
+    use crate::immichctl::tests::create_immichctl_with_server;
     use chrono::DateTime;
 
     pub fn create_tag(id: &str, value: &str, parent_id: Option<&str>) -> TagResponseDto {
@@ -112,6 +282,222 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_tag_details() {
+        assert_eq!(
+            ImmichCtl::tag_details(10, 7, 2),
+            "Requested 10 assets: 7 newly tagged, 2 already tagged, 1 failed."
+        );
+        assert_eq!(
+            ImmichCtl::tag_details(1, 1, 1),
+            "Requested 1 assets: 1 newly tagged, 1 already tagged, 0 failed."
+        );
+    }
+
+    #[test]
+    fn test_tag_pattern_regex() {
+        let re = ImmichCtl::tag_pattern_regex("events/*", TagMatch::Glob).unwrap();
+        assert!(re.is_match("events/2024"));
+        assert!(re.is_match("events/2024/summer"));
+        assert!(!re.is_match("events"));
+        assert!(!re.is_match("old/events/2024"));
+
+        let re = ImmichCtl::tag_pattern_regex("a.?", TagMatch::Glob).unwrap();
+        assert!(re.is_match("a.b"));
+        assert!(!re.is_match("axb"));
+
+        let re = ImmichCtl::tag_pattern_regex("events/20(23|24)", TagMatch::Regex).unwrap();
+        assert!(re.is_match("events/2024"));
+        assert!(!re.is_match("events/2024/summer"));
+        assert!(ImmichCtl::tag_pattern_regex("(", TagMatch::Regex).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tag_assign_glob() -> anyhow::Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(create_asset_for_download(Uuid::new_v4(), "a.jpg", "a.jpg"));
+        sel.save()?;
+        let tags = vec![
+            create_tag("5460dc82-2353-47d1-878c-2f15a1084001", "events", None),
+            create_tag(
+                "5460dc82-2353-47d1-878c-2f15a1084002",
+                "events/a",
+                Some("5460dc82-2353-47d1-878c-2f15a1084001"),
+            ),
+            create_tag(
+                "5460dc82-2353-47d1-878c-2f15a1084003",
+                "events/b",
+                Some("5460dc82-2353-47d1-878c-2f15a1084001"),
+            ),
+        ];
+        let _tags_mock = server
+            .mock("GET", "/api/tags")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&tags)?)
+            .create_async()
+            .await;
+        let mut tag_mocks = Vec::new();
+        for id in [
+            "5460dc82-2353-47d1-878c-2f15a1084002",
+            "5460dc82-2353-47d1-878c-2f15a1084003",
+        ] {
+            tag_mocks.push(
+                server
+                    .mock("PUT", format!("/api/tags/{}/assets", id).as_str())
+                    .with_status(200)
+                    .with_header("content-type", "application/json")
+                    .with_body(
+                        serde_json::json!([{"id": Uuid::new_v4(), "success": true}]).to_string(),
+                    )
+                    .expect(1)
+                    .create_async()
+                    .await,
+            );
+        }
+
+        ctl.tag_assign("events/*", TagMatch::Glob).await?;
+        for mock in tag_mocks {
+            mock.assert_async().await;
+        }
+
+        let err = ctl.tag_assign("none/*", TagMatch::Glob).await.unwrap_err();
+        assert_eq!(err.to_string(), "No tag matches pattern: 'none/*'");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_tag_color() {
+        assert_eq!(ImmichCtl::parse_tag_color("#FF8800").unwrap(), "#FF8800");
+        assert_eq!(ImmichCtl::parse_tag_color("a0b1c2").unwrap(), "#a0b1c2");
+        assert!(ImmichCtl::parse_tag_color("#FFF").is_err());
+        assert!(ImmichCtl::parse_tag_color("#GG0000").is_err());
+        assert!(ImmichCtl::parse_tag_color("").is_err());
+        assert_eq!(
+            ImmichCtl::parse_tag_color("red").err().unwrap().to_string(),
+            "Invalid color 'red', expected #RRGGBB"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tag_set_color() -> anyhow::Result<()> {
+        let (ctl, mut server) = create_immichctl_with_server().await;
+
+        let tags = vec![create_tag(
+            "5460dc82-2353-47d1-878c-2f15a1084001",
+            "root1",
+            None,
+        )];
+        let tags_mock = server
+            .mock("GET", "/api/tags")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&tags)?)
+            .create_async()
+            .await;
+        let mut updated = tags[0].clone();
+        updated.color = Some("#00FF00".to_string());
+        let update_mock = server
+            .mock("PUT", "/api/tags/5460dc82-2353-47d1-878c-2f15a1084001")
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({"color": "#00FF00"}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&updated)?)
+            .create_async()
+            .await;
+
+        ctl.tag_set_color("root1", "#00FF00").await?;
+
+        tags_mock.assert_async().await;
+        update_mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tag_assign_in_batches() -> anyhow::Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+        ctl.set_batch_size(std::num::NonZeroUsize::new(2).unwrap());
+
+        let mut sel = Assets::load(&ctl.assets_file);
+        for _ in 0..3 {
+            sel.add_asset(create_asset_for_download(Uuid::new_v4(), "a.jpg", "a.jpg"));
+        }
+        sel.save()?;
+        let tags = vec![create_tag(
+            "5460dc82-2353-47d1-878c-2f15a1084001",
+            "root1",
+            None,
+        )];
+        let _tags_mock = server
+            .mock("GET", "/api/tags")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&tags)?)
+            .create_async()
+            .await;
+        // 3 assets with batch size 2 -> 2 requests
+        let tag_assets_mock = server
+            .mock(
+                "PUT",
+                "/api/tags/5460dc82-2353-47d1-878c-2f15a1084001/assets",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::json!([{"id": Uuid::new_v4(), "success": true}]).to_string())
+            .expect(2)
+            .create_async()
+            .await;
+
+        ctl.tag_assign("root1", TagMatch::Exact).await?;
+
+        tag_assets_mock.assert_async().await;
+        let history = History::new(&ctl.history_file).entries()?;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].command, "tag assign 'root1'");
+        assert_eq!(history[0].count, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tag_assign_dry_run() -> anyhow::Result<()> {
+        let (mut ctl, mut server) = create_immichctl_with_server().await;
+        ctl.set_dry_run(true);
+
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(create_asset_for_download(Uuid::new_v4(), "a.jpg", "a.jpg"));
+        sel.save()?;
+        let tags = vec![create_tag(
+            "5460dc82-2353-47d1-878c-2f15a1084001",
+            "root1",
+            None,
+        )];
+        let _tags_mock = server
+            .mock("GET", "/api/tags")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&tags)?)
+            .create_async()
+            .await;
+        let tag_assets_mock = server
+            .mock(
+                "PUT",
+                "/api/tags/5460dc82-2353-47d1-878c-2f15a1084001/assets",
+            )
+            .expect(0)
+            .create_async()
+            .await;
+
+        ctl.tag_assign("root1", TagMatch::Exact).await?;
+
+        tag_assets_mock.assert_async().await;
+        assert!(History::new(&ctl.history_file).entries()?.is_empty());
+        Ok(())
+    }
+
     #[test]
     fn test_find_tag_by_name() {
         let tags = vec![
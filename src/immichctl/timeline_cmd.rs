@@ -0,0 +1,312 @@
+use super::ImmichCtl;
+use super::api_error::ApiContext;
+use super::assets::Assets;
+use super::types::{AlbumResponseDto, AssetResponseDto, MetadataSearchDto};
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::ControlFlow;
+use uuid::Uuid;
+
+/// Group name of assets without album or tag
+const NO_GROUP: &str = "(none)";
+
+/// Grouping of `assets timeline`
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimelineGroup {
+    /// Group by album membership
+    Album,
+    /// Group by tag (full tag value)
+    Tag,
+}
+
+/// Output format of `assets timeline`
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimelineFormat {
+    /// Aligned table
+    Text,
+    /// CSV format: name,count,first,last
+    Csv,
+    /// Json format
+    Json,
+}
+
+/// Date span of the selected assets of one album or tag.
+#[derive(Debug, PartialEq, Serialize)]
+struct TimelineRow {
+    name: String,
+    count: usize,
+    first: DateTime<FixedOffset>,
+    last: DateTime<FixedOffset>,
+}
+
+impl TimelineRow {
+    fn new(name: &str, taken: DateTime<FixedOffset>) -> Self {
+        TimelineRow {
+            name: name.to_string(),
+            count: 0,
+            first: taken,
+            last: taken,
+        }
+    }
+
+    fn add(&mut self, taken: DateTime<FixedOffset>) {
+        self.count += 1;
+        self.first = self.first.min(taken);
+        self.last = self.last.max(taken);
+    }
+}
+
+impl ImmichCtl {
+    /// Print the earliest and latest dateTimeOriginal of the selected assets per album or
+    /// tag. An asset counts for every group it belongs to, assets without any are grouped
+    /// as `(none)`. Tags are taken from the selection, album memberships are matched
+    /// locally against the asset ids of all albums.
+    pub async fn assets_timeline(&self, by: TimelineGroup, format: TimelineFormat) -> Result<()> {
+        let sel = self.load_assets();
+        if sel.is_empty() {
+            eprintln!("Selection is empty, no timeline.");
+            return Ok(());
+        }
+        let rows = self.timeline(&sel, by).await?;
+
+        match format {
+            TimelineFormat::Json => self.print_json(&rows)?,
+            TimelineFormat::Csv => {
+                for row in &rows {
+                    println!(
+                        "{},{},{},{}",
+                        self.csv_quoting.field(&row.name),
+                        row.count,
+                        row.first.to_rfc3339(),
+                        row.last.to_rfc3339()
+                    );
+                }
+            }
+            TimelineFormat::Text => {
+                let width = rows.iter().map(|r| r.name.chars().count()).max();
+                let width = width.unwrap_or_default().max(5);
+                println!(
+                    "{:<width$}  {:>5}  {:<25}  {:<25}",
+                    "NAME", "COUNT", "FIRST", "LAST"
+                );
+                for row in &rows {
+                    println!(
+                        "{:<width$}  {:>5}  {:<25}  {:<25}",
+                        row.name,
+                        row.count,
+                        row.first.to_rfc3339(),
+                        row.last.to_rfc3339()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Timeline rows of the selection `sel` grouped `by` album or tag.
+    async fn timeline(&self, sel: &Assets, by: TimelineGroup) -> Result<Vec<TimelineRow>> {
+        let groups: Vec<(&AssetResponseDto, Vec<String>)> = match by {
+            TimelineGroup::Tag => sel
+                .iter_assets()
+                .map(|asset| (asset, asset.tags.iter().map(|t| t.value.clone()).collect()))
+                .collect(),
+            TimelineGroup::Album => {
+                let selected: HashSet<Uuid> = sel.iter_assets().map(|a| a.id).collect();
+                let mut album_names = self.album_names_of_assets(&selected).await?;
+                sel.iter_assets()
+                    .map(|asset| (asset, album_names.remove(&asset.id).unwrap_or_default()))
+                    .collect()
+            }
+        };
+        Ok(self.timeline_rows(&groups))
+    }
+
+    /// One row per group name, sorted by name with `(none)` last.
+    fn timeline_rows(&self, groups: &[(&AssetResponseDto, Vec<String>)]) -> Vec<TimelineRow> {
+        let mut by_name: BTreeMap<&str, TimelineRow> = BTreeMap::new();
+        let mut none: Option<TimelineRow> = None;
+        for (asset, names) in groups {
+            let taken = Self::get_date_time_original(asset, self.assume_tz);
+            if names.is_empty() {
+                none.get_or_insert_with(|| TimelineRow::new(NO_GROUP, taken))
+                    .add(taken);
+            }
+            for name in names {
+                by_name
+                    .entry(name)
+                    .or_insert_with(|| TimelineRow::new(name, taken))
+                    .add(taken);
+            }
+        }
+        by_name.into_values().chain(none).collect()
+    }
+
+    /// Names of the albums containing each of the `selected` assets. The albums are
+    /// retrieved once, their assets are searched album by album and matched locally.
+    async fn album_names_of_assets(
+        &self,
+        selected: &HashSet<Uuid>,
+    ) -> Result<HashMap<Uuid, Vec<String>>> {
+        let albums = self
+            .immich()?
+            .get_all_albums(None, None, None, None, None)
+            .api_context("Could not retrieve albums")
+            .await?
+            .into_inner();
+        let mut names: HashMap<Uuid, Vec<String>> = HashMap::new();
+        for (i, album) in albums.iter().enumerate() {
+            self.eprint_progress_indicator(i, albums.len(), 10);
+            if album.asset_count == 0 {
+                continue;
+            }
+            let search_dto = MetadataSearchDto {
+                album_ids: vec![album.id],
+                ..Default::default()
+            };
+            // collects all pages, never stops early
+            let _ = self
+                .search_pages_with(search_dto, &mut |asset| {
+                    if selected.contains(&asset.id) {
+                        names
+                            .entry(asset.id)
+                            .or_default()
+                            .push(album.album_name.clone());
+                    }
+                    ControlFlow::Continue(())
+                })
+                .await?;
+        }
+        Ok(names)
+    }
+
+    /// Albums containing the asset `id`.
+    pub async fn albums_of_asset(&self, id: &Uuid) -> Result<Vec<AlbumResponseDto>> {
+        let albums = self
+            .immich()?
+            .get_all_albums(Some(id), None, None, None, None)
+            .with_api_context(|| format!("Could not retrieve albums of asset '{}'", id))
+            .await?;
+        Ok(albums.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::immichctl::album_cmd::tests::create_album;
+    use crate::immichctl::asset_cmd::tests::create_asset_with_timestamps;
+    use crate::immichctl::assets::Assets;
+    use crate::immichctl::tag_cmd::tests::create_tag;
+    use crate::immichctl::tests::create_immichctl_with_server;
+    use chrono::{Datelike, TimeZone, Utc};
+
+    #[tokio::test]
+    async fn test_timeline_rows_by_tag() -> Result<()> {
+        let (ctl, _server) = create_immichctl_with_server().await;
+        let at = |d| Utc.with_ymd_and_hms(2024, 1, d, 10, 0, 0).unwrap();
+        let mut a1 = create_asset_with_timestamps(at(1), at(1));
+        let mut a2 = create_asset_with_timestamps(at(5), at(5));
+        let a3 = create_asset_with_timestamps(at(3), at(3));
+        let rome = create_tag("d1a7f1a9-7394-49f7-a5a3-e876a7e16ab1", "Trips/Rome", None);
+        let food = create_tag("d1a7f1a9-7394-49f7-a5a3-e876a7e16ab2", "Food", None);
+        a1.tags = vec![rome.clone()];
+        a2.tags = vec![rome, food];
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(a1);
+        sel.add_asset(a2);
+        sel.add_asset(a3);
+        sel.save()?;
+
+        let groups: Vec<_> = sel
+            .iter_assets()
+            .map(|a| {
+                let names = a.tags.iter().map(|t| t.value.clone()).collect();
+                (a, names)
+            })
+            .collect();
+        let rows = ctl.timeline_rows(&groups);
+        let summary: Vec<_> = rows
+            .iter()
+            .map(|r| (r.name.as_str(), r.count, r.first.day(), r.last.day()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Food", 1, 5, 5),
+                ("Trips/Rome", 2, 1, 5),
+                (NO_GROUP, 1, 3, 3)
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_assets_timeline_by_album() -> Result<()> {
+        let (ctl, mut server) = create_immichctl_with_server().await;
+        let ts = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let asset = create_asset_with_timestamps(ts, ts);
+        let later = create_asset_with_timestamps(ts + chrono::TimeDelta::days(3), ts);
+        let unassigned = create_asset_with_timestamps(ts, ts);
+        let mut sel = Assets::load(&ctl.assets_file);
+        sel.add_asset(asset.clone());
+        sel.add_asset(later.clone());
+        sel.add_asset(unassigned);
+
+        let albums = vec![
+            create_album("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab1", "Rome"),
+            create_album("a1a7f1a9-7394-49f7-a5a3-e876a7e16ab2", "Paris"),
+        ];
+        let albums_mock = server
+            .mock("GET", "/api/albums")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&albums)?)
+            .expect(1)
+            .create_async()
+            .await;
+        let search_body = |items: Vec<&AssetResponseDto>| {
+            serde_json::json!({
+                "albums": {"count": 0, "facets": [], "items": [], "total": 0},
+                "assets": {"count": items.len(), "facets": [], "items": items, "nextPage": null, "total": items.len()}
+            })
+            .to_string()
+        };
+        // Rome contains the selected asset and one that is not selected
+        let other = create_asset_with_timestamps(ts, ts);
+        let rome_mock = server
+            .mock("POST", "/api/search/metadata")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"albumIds": [albums[0].id]}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(search_body(vec![&asset, &other, &later]))
+            .expect(1)
+            .create_async()
+            .await;
+        let paris_mock = server
+            .mock("POST", "/api/search/metadata")
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({"albumIds": [albums[1].id]}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(search_body(vec![&other]))
+            .expect(1)
+            .create_async()
+            .await;
+
+        let rows = ctl.timeline(&sel, TimelineGroup::Album).await?;
+        let summary: Vec<_> = rows
+            .iter()
+            .map(|r| (r.name.as_str(), r.count, r.first.day(), r.last.day()))
+            .collect();
+        assert_eq!(summary, vec![("Rome", 2, 1, 4), (NO_GROUP, 1, 1, 1)]);
+        albums_mock.assert_async().await;
+        rome_mock.assert_async().await;
+        paris_mock.assert_async().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Timezone as fixed offset (`±HH:MM`) or IANA name (e.g. `Europe/Berlin`). The offset
+/// of an IANA timezone depends on the instant because of daylight saving time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeZoneValue {
+    Fixed(FixedOffset),
+    /// Canonical name from the bundled timezone database
+    Iana(&'static str),
+}
+
+impl TimeZoneValue {
+    /// Offset of the timezone at `instant`.
+    pub fn offset_at(&self, instant: &DateTime<Utc>) -> FixedOffset {
+        match self {
+            TimeZoneValue::Fixed(offset) => *offset,
+            TimeZoneValue::Iana(name) => tzdb::tz_by_name(name)
+                .and_then(|tz| tz.find_local_time_type(instant.timestamp()).ok().copied())
+                .and_then(|t| FixedOffset::east_opt(t.ut_offset()))
+                .unwrap_or(FixedOffset::east_opt(0).unwrap()),
+        }
+    }
+}
+
+impl From<FixedOffset> for TimeZoneValue {
+    fn from(offset: FixedOffset) -> Self {
+        TimeZoneValue::Fixed(offset)
+    }
+}
+
+impl FromStr for TimeZoneValue {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Ok(offset) = FixedOffset::from_str(s) {
+            return Ok(TimeZoneValue::Fixed(offset));
+        }
+        match tzdb::TZ_NAMES.iter().find(|n| n.eq_ignore_ascii_case(s)) {
+            Some(name) => Ok(TimeZoneValue::Iana(name)),
+            None => anyhow::bail!(
+                "Invalid timezone '{}', expected ±HH:MM or an IANA name like Europe/Berlin",
+                s
+            ),
+        }
+    }
+}
+
+impl fmt::Display for TimeZoneValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeZoneValue::Fixed(offset) => write!(f, "{}", offset),
+            TimeZoneValue::Iana(name) => f.write_str(name),
+        }
+    }
+}
+
+impl Serialize for TimeZoneValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeZoneValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_timezone() {
+        assert_eq!(
+            "+02:00".parse::<TimeZoneValue>().unwrap(),
+            TimeZoneValue::Fixed(FixedOffset::east_opt(2 * 3600).unwrap())
+        );
+        assert_eq!(
+            "europe/berlin".parse::<TimeZoneValue>().unwrap(),
+            TimeZoneValue::Iana("Europe/Berlin")
+        );
+        assert_eq!(
+            "Mars/Olympus"
+                .parse::<TimeZoneValue>()
+                .unwrap_err()
+                .to_string(),
+            "Invalid timezone 'Mars/Olympus', expected ±HH:MM or an IANA name like Europe/Berlin"
+        );
+    }
+
+    #[test]
+    fn test_offset_at() {
+        let berlin = TimeZoneValue::Iana("Europe/Berlin");
+        let winter = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+        let summer = Utc.with_ymd_and_hms(2024, 7, 15, 12, 0, 0).unwrap();
+        assert_eq!(berlin.offset_at(&winter).local_minus_utc(), 3600);
+        assert_eq!(berlin.offset_at(&summer).local_minus_utc(), 2 * 3600);
+
+        let fixed: TimeZoneValue = FixedOffset::west_opt(5 * 3600).unwrap().into();
+        assert_eq!(fixed.offset_at(&summer).local_minus_utc(), -5 * 3600);
+    }
+
+    #[test]
+    fn test_serde_timezone() {
+        let tz = TimeZoneValue::Iana("America/New_York");
+        let json = serde_json::to_string(&tz).unwrap();
+        assert_eq!(json, "\"America/New_York\"");
+        assert_eq!(serde_json::from_str::<TimeZoneValue>(&json).unwrap(), tz);
+        let tz: TimeZoneValue = serde_json::from_str("\"-03:30\"").unwrap();
+        assert_eq!(tz.to_string(), "-03:30");
+    }
+}
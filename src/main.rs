@@ -1,20 +1,78 @@
+mod datetime_value;
 mod immichctl;
 mod timedelta;
 
 use anyhow::{Result, bail};
 use chrono::{FixedOffset, TimeDelta};
-use clap::{Parser, Subcommand};
-use immichctl::{AssetColumns, AssetSearchArgs, CurlMethod, ImmichCtl};
+use clap::{Parser, Subcommand, ValueEnum};
+use datetime_value::DateTimeValue;
+use immichctl::{
+    AlbumExportFormat, AlbumShowFormat, AssetColumns, AssetFilter, AssetSearchArgs,
+    ClearFilterArgs, ColorMode, CsvQuoting, CurlMethod, ImmichCtl, ListExtreme, ListFormat,
+    MembershipFormat, OutputMode, SampleMode, StatsFormat, TagMatch, TimeZoneValue, TimelineFormat,
+    TimelineGroup,
+};
+use std::io::IsTerminal;
+use std::num::{NonZeroU64, NonZeroUsize};
 use std::path::PathBuf;
 use timedelta::TimeDeltaValue;
+use uuid::Uuid;
 
 /// A command line interface for Immich.
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
-    /// Enable verbose output for detailed error messages
+    /// Enable verbose output for detailed error messages and additional warnings
     #[arg(short, long, global = true)]
     verbose: bool,
+    /// Suppress progress output
+    #[arg(short, long, global = true)]
+    quiet: bool,
+    /// Print the intended effect of mutating commands without performing server writes or selection changes
+    #[arg(long, global = true)]
+    dry_run: bool,
+    /// Format of error messages, warnings, `config show` and `apikey list`: text or json (`{"error": {"message": "...", "kind": "..."}}`) [default: IMMICHCTL_FORMAT or text]
+    #[arg(long, global = true, value_enum, value_name = "format")]
+    output: Option<OutputMode>,
+    /// Colored output: auto (only on a terminal, honors NO_COLOR), always or never
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value = "auto",
+        value_name = "when"
+    )]
+    color: ColorMode,
+    /// Indent json output of list, curl, album show/export and json errors [default: only on a terminal]
+    #[arg(long, global = true, conflicts_with = "compact")]
+    pretty: bool,
+    /// Write json output on a single line
+    #[arg(long, global = true)]
+    compact: bool,
+    /// Maximal number of asset ids per bulk request (tag/album assign/unassign)
+    #[arg(long, global = true, value_name = "count", default_value_t = immichctl::DEFAULT_BATCH_SIZE)]
+    batch_size: NonZeroUsize,
+    /// Maximal number of parallel read requests (assets refresh, assets remove-from-albums, assets download --live-videos)
+    #[arg(long, global = true, value_name = "count", default_value_t = immichctl::DEFAULT_READ_CONCURRENCY)]
+    read_concurrency: NonZeroUsize,
+    /// Maximal number of parallel write requests (assets datetime, tag/album assign/unassign)
+    #[arg(long, global = true, value_name = "count", default_value_t = immichctl::DEFAULT_WRITE_CONCURRENCY)]
+    write_concurrency: NonZeroUsize,
+    /// Config file (default: $IMMICHCTL_CONFIG or config.json in $IMMICHCTL_CONFIG_DIR or $HOME/.immichctl)
+    #[arg(long, global = true, value_name = "path")]
+    config: Option<PathBuf>,
+    /// Asset selection file (default: assets.json next to the config file)
+    #[arg(long, global = true, value_name = "path")]
+    selection_file: Option<PathBuf>,
+    /// Take a missing server and/or API key from the netrc file ($NETRC or $HOME/.netrc)
+    #[arg(long, global = true)]
+    netrc: bool,
+    /// Netrc file to use, implies --netrc
+    #[arg(long, global = true, value_name = "path")]
+    netrc_file: Option<PathBuf>,
+    /// Value of the x-request-id header of all requests, e.g. to find them in server logs [default: random UUID]
+    #[arg(long, global = true, value_name = "id")]
+    request_id: Option<String>,
     #[command(subcommand)]
     command: Commands,
 }
@@ -23,7 +81,7 @@ struct Cli {
 enum Commands {
     /// Prints version information
     Version,
-    /// Login to an Immich instance
+    /// Login to an Immich instance, interactively if called without arguments
     Login {
         /// The server URL as positional argument
         server: Option<String>,
@@ -33,6 +91,20 @@ enum Commands {
     },
     /// Logout from the current Immich instance
     Logout,
+    /// Show the history of mutating commands
+    History,
+    /// Check connectivity, API key and permissions
+    Doctor,
+    /// Show the configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+    /// List and revoke API keys
+    Apikey {
+        #[command(subcommand)]
+        command: ApikeyCommands,
+    },
     /// Manage the asset selection
     #[command(visible_aliases = ["asset", "a"])]
     Assets {
@@ -61,32 +133,97 @@ enum Commands {
         /// HTTP data to include in the request body
         #[arg(short = 'd', long)]
         data: Option<String>,
+        /// Override the api-version header (default: API version of immichctl)
+        #[arg(long, value_name = "version")]
+        api_version: Option<String>,
+        /// Print only the value at this dotted path of the json response, e.g. assets.items[0].id (repeatable)
+        #[arg(long = "field", value_name = "path")]
+        fields: Vec<String>,
     },
 }
 
 #[derive(Subcommand, Debug)]
 enum AssetCommands {
-    /// Clear the local selection store
-    Clear,
+    /// Clear the local selection store, or with filters remove only the matching assets
+    Clear {
+        #[command(flatten)]
+        filter: ClearFilterArgs,
+        /// Clear a non-empty selection without filters
+        #[arg(short, long)]
+        yes: bool,
+    },
     /// Search for assets and add/remove them to/from the local asset selection.
-    Search(AssetSearchArgs),
+    Search(Box<AssetSearchArgs>),
+    /// Re-run a tag/album search and align the selection with it: adds new matches and removes selected assets that don't match anymore
+    Sync {
+        /// Tag name to search
+        #[arg(long, value_name = "tag name", required_unless_present = "album")]
+        tag: Option<String>,
+        /// Album name to search
+        #[arg(long, value_name = "album name")]
+        album: Option<String>,
+    },
     /// Refresh asset metadata including exif data (slow)
-    Refresh,
-    /// Count items in the local selection store
+    ///
+    /// Progress is saved periodically. An interrupted refresh is resumed on the next run.
+    /// With --server the server re-extracts the metadata from the asset files first.
+    Refresh {
+        /// Queue a metadata (EXIF) re-extraction job on the server for the selected assets
+        #[arg(long)]
+        server: bool,
+        /// Re-fetch the selected assets into the local selection (default without --server)
+        #[arg(long)]
+        local: bool,
+        /// Continue with the remaining assets if an asset can't be retrieved
+        #[arg(long)]
+        continue_on_error: bool,
+        /// Only assets taken at or after this date/time (e.g. 2025-10-07T18:00:00+02:00 or 7d for 7 days ago)
+        #[arg(long, value_name = "date/time")]
+        since: Option<DateTimeValue>,
+        /// Only assets taken before this date/time (e.g. 2025-10-07T18:00:00+02:00 or 1d for 1 day ago)
+        #[arg(long, value_name = "date/time")]
+        until: Option<DateTimeValue>,
+    },
+    /// Count items in the local selection store, `{"count": N}` with --output json
     Count,
     /// List asset ids in the local selection store
     List {
-        /// Output format
-        #[arg(long, default_value = "csv", value_enum)]
-        format: ListFormat,
-        /// Columns to display
-        #[arg(
-            short,
-            long = "column",
-            default_value = "original-file-name",
-            value_enum
-        )]
+        /// Output format [default: IMMICHCTL_FORMAT or csv]
+        #[arg(long, value_enum)]
+        format: Option<ListFormat>,
+        /// Columns to display, repeatable or comma separated [default: original-file-name for csv/tsv, all fields for json]
+        #[arg(short, long = "column", value_enum, value_delimiter = ',')]
         columns: Vec<AssetColumns>,
+        /// Quoting of csv fields
+        #[arg(long, value_enum, default_value = "minimal", value_name = "mode")]
+        quoting: CsvQuoting,
+        /// Only list assets matching `<column>=<value>`, `<column>!=<value>` or `<column>~<part of value>` (case-insensitive, repeatable)
+        #[arg(long = "where", value_name = "predicate")]
+        filters: Vec<AssetFilter>,
+        /// Write the list to a file instead of stdout
+        #[arg(long, value_name = "path")]
+        output_file: Option<PathBuf>,
+        /// Timezone in format ±HH:MM for assets without EXIF timezone
+        #[arg(long, value_name = "timezone")]
+        assume_tz: Option<FixedOffset>,
+        /// Only assets taken at or after this date/time (e.g. 2025-10-07T18:00:00+02:00 or 7d for 7 days ago)
+        #[arg(long, value_name = "date/time")]
+        since: Option<DateTimeValue>,
+        /// Only assets taken before this date/time (e.g. 2025-10-07T18:00:00+02:00 or 1d for 1 day ago)
+        #[arg(long, value_name = "date/time")]
+        until: Option<DateTimeValue>,
+        /// Only the N most recently taken assets, newest first
+        #[arg(long, value_name = "N", conflicts_with = "oldest")]
+        newest: Option<usize>,
+        /// Only the N earliest taken assets, oldest first
+        #[arg(long, value_name = "N")]
+        oldest: Option<usize>,
+        /// Print a `# N assets` summary line to stderr after the list (not with --quiet)
+        #[arg(long)]
+        summary: bool,
+        /// Start csv or tsv output with a line of column names
+        #[arg(long)]
+        header: bool,
     },
     /// Adjust dateTimeOriginal and timezone of selected assets
     Datetime {
@@ -96,26 +233,148 @@ enum AssetCommands {
         /// New timezone in format ±HH:MM
         #[arg(long, value_name = "timezone")]
         timezone: Option<FixedOffset>,
+        /// Timezone in format ±HH:MM for assets without EXIF timezone
+        #[arg(long, value_name = "timezone")]
+        assume_tz: Option<FixedOffset>,
+    },
+    /// Set the timezone of selected assets, keeping the wall clock reading of dateTimeOriginal
+    SetTimezone {
+        /// New timezone in format ±HH:MM or IANA name, e.g. Europe/Berlin
+        timezone: TimeZoneValue,
+        /// Keep the instant and convert the clock reading to the new timezone instead
+        #[arg(long)]
+        convert_instant: bool,
+        /// Timezone in format ±HH:MM for assets without EXIF timezone
+        #[arg(long, value_name = "timezone")]
+        assume_tz: Option<FixedOffset>,
+    },
+    /// Print the JSON Schema of the asset selection file
+    Schema,
+    /// Interactively review the selection and drop assets from it
+    Review,
+    /// Reduce the selection to a random sample
+    #[command(group(clap::ArgGroup::new("mode").required(true).args(["count", "percent", "per_day"])))]
+    Sample {
+        /// Keep N assets
+        #[arg(long, value_name = "N")]
+        count: Option<NonZeroUsize>,
+        /// Keep about P percent of the assets, at least one
+        #[arg(long, value_name = "P")]
+        percent: Option<f64>,
+        /// Keep up to N assets per capture day (local date of dateTimeOriginal)
+        #[arg(long, value_name = "N")]
+        per_day: Option<NonZeroUsize>,
+        /// Timezone in format ±HH:MM for assets without EXIF timezone
+        #[arg(long, value_name = "timezone")]
+        assume_tz: Option<FixedOffset>,
+        /// Seed of the random selection, the same seed yields the same sample [default: random]
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Print recently added assets without changing the selection
+    Recent {
+        /// Assets added within the last days
+        #[arg(long, default_value_t = 7)]
+        days: u32,
+        /// Maximal number of assets
         #[arg(long)]
-        dry_run: bool,
+        limit: Option<NonZeroU64>,
+        /// Output format [default: IMMICHCTL_FORMAT or csv]
+        #[arg(long, value_enum)]
+        format: Option<ListFormat>,
+        /// Columns to display, repeatable or comma separated [default: original-file-name for csv/tsv, all fields for json]
+        #[arg(short, long = "column", value_enum, value_delimiter = ',')]
+        columns: Vec<AssetColumns>,
+        /// Quoting of csv fields
+        #[arg(long, value_enum, default_value = "minimal", value_name = "mode")]
+        quoting: CsvQuoting,
     },
     /// Download selected assets into a local directory
     Download {
         /// Output directory (created if missing)
         #[arg(long, default_value = ".")]
         dir: PathBuf,
+        /// Only download the selected assets that failed in the previous download into the same directory
+        #[arg(long)]
+        retry_failed: bool,
+        /// Number of archives downloaded in parallel
+        #[arg(long, value_name = "count", default_value = "1")]
+        concurrency: NonZeroUsize,
+        /// Verify the checksum of each downloaded file and delete corrupt files
+        #[arg(long)]
+        verify: bool,
+        /// Download all selected assets again, even if the download manifest lists them as done
+        #[arg(long)]
+        force: bool,
+        /// Also download the paired videos of live photos
+        #[arg(long)]
+        live_videos: bool,
+    },
+    /// Report the date span (first/last dateTimeOriginal) of the selected assets per album or tag
+    Timeline {
+        /// Group the selected assets by album or tag
+        #[arg(long, value_enum)]
+        by: TimelineGroup,
+        /// Output format [default: IMMICHCTL_FORMAT or text]
+        #[arg(long, value_enum)]
+        format: Option<TimelineFormat>,
+    },
+    /// Summarize the selection: counts by type, favorites, date span and timezones
+    Stats {
+        /// Output format [default: IMMICHCTL_FORMAT or text]
+        #[arg(long, value_enum)]
+        format: Option<StatsFormat>,
+        /// Same as `--format json`
+        #[arg(long, conflicts_with = "format")]
+        json: bool,
+    },
+    /// Show the albums and tags an asset belongs to
+    Memberships {
+        /// Asset id (UUID), the asset doesn't need to be selected
+        id: String,
+        /// Output format [default: IMMICHCTL_FORMAT or text]
+        #[arg(long, value_enum)]
+        format: Option<MembershipFormat>,
+    },
+    /// Remove the selected assets from all albums they belong to
+    RemoveFromAlbums,
+    /// Export the original files of the selected assets into a zip archive
+    ExportZip {
+        /// Zip archive to create
+        path: PathBuf,
+        /// Number of assets downloaded in parallel
+        #[arg(long, value_name = "count", default_value = "1")]
+        concurrency: NonZeroUsize,
     },
 }
 
-/// Columns for CSV listing of selected assets
-#[derive(clap::ValueEnum, Clone, Debug)]
-enum ListFormat {
-    /// CSV format
-    Csv,
-    /// Json format
-    Json,
-    /// Json format, pretty printed
-    JsonPretty,
+#[derive(Subcommand, Debug)]
+enum ApikeyCommands {
+    /// List the API keys of the user, the key in use is marked as current
+    List,
+    /// Revoke an API key
+    Revoke {
+        /// Id of the API key
+        id: Uuid,
+        /// Revoke the key even if immichctl is logged in with it
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigCommands {
+    /// Print the effective configuration (files, server, whether an API key is set)
+    Show,
+    /// Set the default timezone for assets without EXIF timezone (used if --assume-tz isn't given)
+    SetTimezone {
+        /// Timezone in format ±HH:MM or IANA name, e.g. Europe/Berlin
+        #[arg(required_unless_present = "unset")]
+        timezone: Option<TimeZoneValue>,
+        /// Remove the default timezone, timezones are derived from asset metadata again
+        #[arg(long, conflicts_with = "timezone")]
+        unset: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -124,27 +383,109 @@ enum TagCommands {
     Assign {
         /// Tag name to add
         name: String,
+        /// Treat name as glob pattern on the full tag name and assign all matching tags
+        #[arg(long, conflicts_with = "regex")]
+        glob: bool,
+        /// Treat name as regular expression on the full tag name and assign all matching tags
+        #[arg(long)]
+        regex: bool,
+        /// Print only the number of assigned assets to stdout, e.g. for scripts
+        #[arg(long)]
+        count_only: bool,
     },
     /// Unassign a tag from selected assets
     Unassign {
         /// Tag name to remove
         name: String,
     },
+    /// Set the color of a tag
+    SetColor {
+        /// Tag name
+        name: String,
+        /// Color in format #RRGGBB
+        color: String,
+    },
     /// List all tags
     List,
 }
 
 #[derive(Subcommand, Debug)]
 enum AlbumCommands {
-    /// Assign selected assets to an album
+    /// Assign selected assets to one or more albums
     Assign {
-        /// Album name to assign
-        name: String,
+        /// Album names to assign
+        #[arg(required_unless_present = "id", conflicts_with = "id")]
+        names: Vec<String>,
+        /// Album id to assign, e.g. for albums with duplicate names
+        #[arg(long)]
+        id: Option<Uuid>,
+        /// Print only the number of assigned assets to stdout, e.g. for scripts
+        #[arg(long)]
+        count_only: bool,
     },
     /// Unassign selected assets from an album
     Unassign {
         /// Album name to remove
+        #[arg(required_unless_present = "id", conflicts_with = "id")]
+        name: Option<String>,
+        /// Album id to remove, e.g. for albums with duplicate names
+        #[arg(long)]
+        id: Option<Uuid>,
+    },
+    /// Rename an album
+    Rename {
+        /// Current album name
         name: String,
+        /// New album name
+        new_name: String,
+        /// New album description
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// Move assets from one album to another
+    Move {
+        /// Album to remove the assets from
+        #[arg(long)]
+        from: String,
+        /// Album to add the assets to
+        #[arg(long)]
+        to: String,
+        /// Move all assets of the source album instead of the selected ones
+        #[arg(long)]
+        all: bool,
+    },
+    /// Show album details
+    Show {
+        /// Album name
+        name: String,
+        /// Also list the assets of the album
+        #[arg(long)]
+        assets: bool,
+        /// Output format
+        #[arg(long, default_value = "text", value_enum)]
+        format: AlbumShowFormat,
+    },
+    /// Print the assets of an album without changing the asset selection
+    Export {
+        /// Album name
+        name: String,
+        /// Output format
+        #[arg(long, default_value = "csv", value_enum)]
+        format: AlbumExportFormat,
+        /// Quoting of csv fields
+        #[arg(long, value_enum, default_value = "minimal", value_name = "mode")]
+        quoting: CsvQuoting,
+    },
+    /// Create a public shared link for an album
+    Share {
+        /// Album name to share
+        name: String,
+        /// Link expiry as duration from now, e.g. 7d or 12h
+        #[arg(long, value_name = "duration")]
+        expires: Option<TimeDeltaValue>,
+        /// Password protecting the link
+        #[arg(long)]
+        password: Option<String>,
     },
     /// List all albums
     List,
@@ -154,19 +495,65 @@ enum AlbumCommands {
 async fn main() {
     let cli = Cli::parse();
     if let Err(err) = _main(&cli).await {
+        if format_or_env(cli.output, OutputMode::Text) == OutputMode::Json {
+            eprintln!(
+                "{}",
+                immichctl::output::error_json(&err, cli.verbose, cli.pretty)
+            );
+            std::process::exit(1);
+        }
+        let prefix = immichctl::color::red("Error:", cli.color.stderr_enabled());
         if cli.verbose {
-            eprintln!("Error: {:?}", err);
+            eprintln!("{} {:?}", prefix, err);
         } else {
-            eprintln!("Error: {}", err);
+            eprintln!("{} {}", prefix, err);
         }
         std::process::exit(1);
     }
 }
 
+/// The format given on the command line, otherwise the `IMMICHCTL_FORMAT` env variable
+/// if it's a valid value for `T`, otherwise `default`.
+fn format_or_env<T: ValueEnum>(format: Option<T>, default: T) -> T {
+    resolve_format(
+        format,
+        std::env::var("IMMICHCTL_FORMAT").ok().as_deref(),
+        default,
+    )
+}
+
+/// `format` if given, otherwise `env_format` if it's a valid value for `T`, otherwise `default`.
+fn resolve_format<T: ValueEnum>(format: Option<T>, env_format: Option<&str>, default: T) -> T {
+    format
+        .or_else(|| T::from_str(env_format?, true).ok())
+        .unwrap_or(default)
+}
+
+/// Indent json if requested by `--pretty`, never with `--compact`, otherwise only on a terminal.
+fn pretty_json(pretty: bool, compact: bool, is_terminal: bool) -> bool {
+    pretty || (!compact && is_terminal)
+}
+
 async fn _main(cli: &Cli) -> Result<()> {
     tracing_subscriber::fmt::init();
 
-    let mut immichctl = ImmichCtl::new();
+    let mut immichctl = ImmichCtl::new(cli.config.as_deref(), cli.selection_file.as_deref());
+    immichctl.set_quiet(cli.quiet);
+    immichctl.set_verbose(cli.verbose);
+    immichctl.set_dry_run(cli.dry_run);
+    immichctl.set_color(cli.color);
+    let pretty_json = pretty_json(cli.pretty, cli.compact, std::io::stdout().is_terminal());
+    immichctl.set_pretty_json(pretty_json);
+    immichctl.set_output(format_or_env(cli.output, OutputMode::Text));
+    immichctl.set_batch_size(cli.batch_size);
+    immichctl.set_read_concurrency(cli.read_concurrency);
+    immichctl.set_write_concurrency(cli.write_concurrency);
+    if let Some(request_id) = &cli.request_id {
+        immichctl.set_request_id(request_id)?;
+    }
+    if cli.netrc || cli.netrc_file.is_some() {
+        immichctl.use_netrc(cli.netrc_file.as_deref())?;
+    }
 
     match &cli.command {
         Commands::Version => {
@@ -174,18 +561,56 @@ async fn _main(cli: &Cli) -> Result<()> {
         }
         Commands::Login { server, apikey } => match (server, apikey) {
             (Some(server), Some(apikey)) => immichctl.login(server, apikey).await?,
+            (None, None) if ImmichCtl::is_interactive() => immichctl.login_wizard().await?,
             (None, None) => immichctl.show_login()?,
             _ => bail!(
-                "Please provide both server URL and --apikey to login, or no arguments to see the current server."
+                "Please provide both server URL and --apikey to login, or no arguments for the interactive login."
             ),
         },
         Commands::Logout => {
             immichctl.logout()?;
         }
-        Commands::Curl { path, method, data } => {
-            immichctl.curl(path, *method, data).await?;
+        Commands::History => {
+            immichctl.history_list()?;
+        }
+        Commands::Doctor => {
+            immichctl.doctor().await?;
+        }
+        Commands::Config { command } => match command {
+            ConfigCommands::Show => {
+                immichctl.config_show(format_or_env(cli.output, OutputMode::Text))?;
+            }
+            ConfigCommands::SetTimezone { timezone, .. } => {
+                immichctl.config_set_timezone(*timezone)?;
+            }
+        },
+        Commands::Apikey { command } => match command {
+            ApikeyCommands::List => {
+                immichctl
+                    .apikey_list(format_or_env(cli.output, OutputMode::Text))
+                    .await?;
+            }
+            ApikeyCommands::Revoke { id, yes } => {
+                immichctl.apikey_revoke(id, *yes).await?;
+            }
+        },
+        Commands::Curl {
+            path,
+            method,
+            data,
+            api_version,
+            fields,
+        } => {
+            immichctl
+                .curl(path, *method, data, api_version, fields)
+                .await?;
         }
         Commands::Assets { command } => match command {
+            AssetCommands::Sync { tag, album } => {
+                immichctl
+                    .assets_sync(tag.as_deref(), album.as_deref())
+                    .await?;
+            }
             AssetCommands::Search(args) => match args.remove {
                 true => {
                     immichctl.assets_search_remove(args).await?;
@@ -199,54 +624,245 @@ async fn _main(cli: &Cli) -> Result<()> {
                     immichctl.assets_search_add(args).await?;
                 }
             },
-            AssetCommands::Clear => {
-                immichctl.assets_clear()?;
+            AssetCommands::Clear { filter, yes } => {
+                immichctl.assets_clear(filter, *yes).await?;
             }
             AssetCommands::Count => {
-                immichctl.assets_count();
+                immichctl.assets_count(format_or_env(cli.output, OutputMode::Text))?;
             }
-            AssetCommands::Refresh => {
-                immichctl.assets_refresh().await?;
+            AssetCommands::Refresh {
+                server,
+                local,
+                continue_on_error,
+                since,
+                until,
+            } => {
+                immichctl.set_date_window(since.map(|v| *v), until.map(|v| *v));
+                if *server {
+                    immichctl.assets_refresh_server().await?;
+                }
+                if *local || !*server {
+                    immichctl.assets_refresh(*continue_on_error).await?;
+                }
+            }
+            AssetCommands::List {
+                format,
+                columns,
+                quoting,
+                filters,
+                output_file,
+                assume_tz,
+                since,
+                until,
+                newest,
+                oldest,
+                summary,
+                header,
+            } => {
+                immichctl.set_assume_tz(*assume_tz);
+                immichctl.set_csv_quoting(*quoting);
+                immichctl.set_list_header(*header);
+                immichctl.set_list_summary(*summary);
+                immichctl.set_date_window(since.map(|v| *v), until.map(|v| *v));
+                let output_file = output_file.as_deref();
+                let extreme = newest
+                    .map(ListExtreme::Newest)
+                    .or(oldest.map(ListExtreme::Oldest));
+                match format_or_env(*format, ListFormat::Csv) {
+                    ListFormat::Csv => {
+                        immichctl.assets_list_csv(columns, filters, extreme, output_file)?
+                    }
+                    ListFormat::Tsv => {
+                        immichctl.assets_list_tsv(columns, filters, extreme, output_file)?
+                    }
+                    ListFormat::Json => immichctl.assets_list_json(
+                        pretty_json,
+                        columns,
+                        filters,
+                        extreme,
+                        output_file,
+                    )?,
+                    ListFormat::JsonPretty => {
+                        immichctl.assets_list_json(true, columns, filters, extreme, output_file)?
+                    }
+                }
             }
-            AssetCommands::List { format, columns } => match format {
-                ListFormat::Csv => immichctl.assets_list_csv(columns),
-                ListFormat::Json => immichctl.assets_list_json(false)?,
-                ListFormat::JsonPretty => immichctl.assets_list_json(true)?,
-            },
             AssetCommands::Datetime {
                 offset,
                 timezone,
-                dry_run,
+                assume_tz,
             } => {
+                immichctl.set_assume_tz(*assume_tz);
                 let o = match offset {
                     Some(v) => **v,
                     None => TimeDelta::zero(),
                 };
+                immichctl.assets_datetime_adjust(&o, timezone).await?;
+            }
+            AssetCommands::SetTimezone {
+                timezone,
+                convert_instant,
+                assume_tz,
+            } => {
+                immichctl.set_assume_tz(*assume_tz);
                 immichctl
-                    .assets_datetime_adjust(&o, timezone, *dry_run)
+                    .assets_set_timezone(*timezone, *convert_instant)
                     .await?;
             }
-            AssetCommands::Download { dir } => {
-                immichctl.assets_download(dir).await?;
+            AssetCommands::Schema => {
+                immichctl.assets_schema()?;
+            }
+            AssetCommands::Review => {
+                immichctl.assets_review()?;
+            }
+            AssetCommands::Sample {
+                count,
+                percent,
+                per_day,
+                assume_tz,
+                seed,
+            } => {
+                let mode = match (count, percent, per_day) {
+                    (Some(count), _, _) => SampleMode::Count(*count),
+                    (_, Some(percent), _) => SampleMode::Percent(*percent),
+                    (_, _, Some(per_day)) => SampleMode::PerDay(*per_day),
+                    _ => unreachable!("clap requires one sample mode"),
+                };
+                immichctl.set_assume_tz(*assume_tz);
+                immichctl.assets_sample(mode, *seed)?;
+            }
+            AssetCommands::Recent {
+                days,
+                limit,
+                format,
+                columns,
+                quoting,
+            } => {
+                immichctl.set_csv_quoting(*quoting);
+                immichctl
+                    .assets_recent(
+                        *days,
+                        *limit,
+                        format_or_env(*format, ListFormat::Csv),
+                        columns,
+                    )
+                    .await?;
+            }
+            AssetCommands::Download {
+                dir,
+                retry_failed,
+                concurrency,
+                verify,
+                force,
+                live_videos,
+            } => {
+                immichctl
+                    .assets_download(
+                        dir,
+                        *retry_failed,
+                        *concurrency,
+                        *verify,
+                        *force,
+                        *live_videos,
+                    )
+                    .await?;
+            }
+            AssetCommands::Timeline { by, format } => {
+                immichctl
+                    .assets_timeline(*by, format_or_env(*format, TimelineFormat::Text))
+                    .await?;
+            }
+            AssetCommands::Stats { format, json } => {
+                let format = if *json {
+                    StatsFormat::Json
+                } else {
+                    format_or_env(*format, StatsFormat::Text)
+                };
+                immichctl.assets_stats(format)?;
+            }
+            AssetCommands::Memberships { id, format } => {
+                immichctl
+                    .assets_memberships(id, format_or_env(*format, MembershipFormat::Text))
+                    .await?;
+            }
+            AssetCommands::RemoveFromAlbums => {
+                immichctl.assets_remove_from_albums().await?;
+            }
+            AssetCommands::ExportZip { path, concurrency } => {
+                immichctl.assets_export_zip(path, *concurrency).await?;
             }
         },
         Commands::Tags { command } => match command {
-            TagCommands::Assign { name } => {
-                immichctl.tag_assign(name).await?;
+            TagCommands::Assign {
+                name,
+                glob,
+                regex,
+                count_only,
+            } => {
+                immichctl.set_count_only(*count_only);
+                let tag_match = if *glob {
+                    TagMatch::Glob
+                } else if *regex {
+                    TagMatch::Regex
+                } else {
+                    TagMatch::Exact
+                };
+                immichctl.tag_assign(name, tag_match).await?;
             }
             TagCommands::Unassign { name } => {
                 immichctl.tag_unassign(name).await?;
             }
+            TagCommands::SetColor { name, color } => {
+                immichctl.tag_set_color(name, color).await?;
+            }
             TagCommands::List => {
                 immichctl.tag_list().await?;
             }
         },
         Commands::Albums { command } => match command {
-            AlbumCommands::Assign { name } => {
-                immichctl.album_assign(name).await?;
+            AlbumCommands::Assign {
+                names,
+                id,
+                count_only,
+            } => {
+                immichctl.set_count_only(*count_only);
+                immichctl.album_assign(names, *id).await?;
+            }
+            AlbumCommands::Unassign { name, id } => {
+                immichctl.album_unassign(name.as_deref(), *id).await?;
             }
-            AlbumCommands::Unassign { name } => {
-                immichctl.album_unassign(name).await?;
+            AlbumCommands::Rename {
+                name,
+                new_name,
+                description,
+            } => {
+                immichctl.album_rename(name, new_name, description).await?;
+            }
+            AlbumCommands::Move { from, to, all } => {
+                immichctl.album_move(from, to, *all).await?;
+            }
+            AlbumCommands::Show {
+                name,
+                assets,
+                format,
+            } => {
+                immichctl.album_show(name, *assets, *format).await?;
+            }
+            AlbumCommands::Export {
+                name,
+                format,
+                quoting,
+            } => {
+                immichctl.set_csv_quoting(*quoting);
+                immichctl.album_export(name, *format).await?;
+            }
+            AlbumCommands::Share {
+                name,
+                expires,
+                password,
+            } => {
+                let e = expires.map(|v| *v);
+                immichctl.album_share(name, &e, password).await?;
             }
             AlbumCommands::List => {
                 immichctl.album_list().await?;
@@ -264,4 +880,93 @@ mod tests {
         use clap::CommandFactory;
         Cli::command().debug_assert();
     }
+
+    #[test]
+    fn test_resolve_format() {
+        assert_eq!(resolve_format(None, None, ListFormat::Csv), ListFormat::Csv);
+        assert_eq!(
+            resolve_format(Some(ListFormat::Json), None, ListFormat::Csv),
+            ListFormat::Json
+        );
+        assert_eq!(
+            resolve_format(None, Some("json-pretty"), ListFormat::Csv),
+            ListFormat::JsonPretty
+        );
+        assert_eq!(
+            resolve_format(Some(ListFormat::Csv), Some("json-pretty"), ListFormat::Csv),
+            ListFormat::Csv
+        );
+        // not a valid output mode
+        assert_eq!(
+            resolve_format(None, Some("json-pretty"), OutputMode::Text),
+            OutputMode::Text
+        );
+    }
+
+    #[test]
+    fn test_comma_separated_columns() {
+        let columns = |args: &[&str]| {
+            let cli = Cli::try_parse_from(args).unwrap();
+            match cli.command {
+                Commands::Assets {
+                    command: AssetCommands::List { columns, .. },
+                } => columns,
+                _ => unreachable!(),
+            }
+        };
+        let repeated = columns(&[
+            "immichctl",
+            "assets",
+            "list",
+            "-c",
+            "id",
+            "-c",
+            "file",
+            "-c",
+            "datetime",
+        ]);
+        assert_eq!(
+            repeated,
+            vec![
+                AssetColumns::Id,
+                AssetColumns::OriginalFileName,
+                AssetColumns::DateTimeOriginal
+            ]
+        );
+        let comma = columns(&[
+            "immichctl",
+            "assets",
+            "list",
+            "--column",
+            "id,file,datetime",
+        ]);
+        assert_eq!(comma, repeated);
+        let mixed = columns(&[
+            "immichctl",
+            "assets",
+            "list",
+            "-c",
+            "id,file",
+            "-c",
+            "datetime",
+        ]);
+        assert_eq!(mixed, repeated);
+        assert!(Cli::try_parse_from(["immichctl", "assets", "list", "-c", "id,nope"]).is_err());
+    }
+
+    #[test]
+    fn test_sample_rejects_zero() {
+        for mode in ["--count", "--per-day"] {
+            assert!(Cli::try_parse_from(["immichctl", "assets", "sample", mode, "0"]).is_err());
+            assert!(Cli::try_parse_from(["immichctl", "assets", "sample", mode, "1"]).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_pretty_json() {
+        assert!(pretty_json(false, false, true));
+        assert!(!pretty_json(false, false, false));
+        assert!(pretty_json(true, false, false));
+        assert!(!pretty_json(false, true, true));
+    }
 }
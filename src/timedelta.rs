@@ -10,6 +10,9 @@ lazy_static! {
         Regex::new(r"^(?P<sign>[-+])?(?P<days>\d+d)?(?P<hours>\d+h)?(?P<minutes>\d+m)?$").unwrap();
 }
 
+/// Maximal supported time delta in days (about 27000 years).
+const MAX_DAYS: i64 = 10_000_000;
+
 /// Wrapper for chrono::TimeDelta to support parsing from string and formatting.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct TimeDeltaValue(TimeDelta);
@@ -77,24 +80,31 @@ impl FromStr for TimeDeltaValue {
             return Err(anyhow::anyhow!("Invalid time delta format"));
         }
 
-        let sign = if caps.name("sign").map_or("+", |m| m.as_str()) == "-" {
-            -1
-        } else {
-            1
+        let negative = caps.name("sign").is_some_and(|m| m.as_str() == "-");
+        let days = Self::parse_component(&caps, "days")?;
+        let hours = Self::parse_component(&caps, "hours")?;
+        let minutes = Self::parse_component(&caps, "minutes")?;
+
+        let delta = TimeDelta::try_days(days)
+            .zip(TimeDelta::try_hours(hours))
+            .zip(TimeDelta::try_minutes(minutes))
+            .and_then(|((d, h), m)| d.checked_add(&h)?.checked_add(&m))
+            .filter(|delta| delta.num_days() <= MAX_DAYS)
+            .ok_or_else(|| {
+                anyhow::anyhow!("Time delta '{}' out of range, maximum is {}d", s, MAX_DAYS)
+            })?;
+        Ok(TimeDeltaValue(if negative { -delta } else { delta }))
+    }
+}
+
+impl TimeDeltaValue {
+    fn parse_component(caps: &regex::Captures, name: &str) -> anyhow::Result<i64> {
+        let Some(m) = caps.name(name) else {
+            return Ok(0);
         };
-        let days = caps
-            .name("days")
-            .map_or(0, |m| m.as_str().trim_end_matches('d').parse().unwrap_or(0));
-        let hours = caps
-            .name("hours")
-            .map_or(0, |m| m.as_str().trim_end_matches('h').parse().unwrap_or(0));
-        let minutes = caps
-            .name("minutes")
-            .map_or(0, |m| m.as_str().trim_end_matches('m').parse().unwrap_or(0));
-
-        Ok(TimeDeltaValue(
-            (TimeDelta::days(days) + TimeDelta::hours(hours) + TimeDelta::minutes(minutes)) * sign,
-        ))
+        m.as_str()[..m.as_str().len() - 1]
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Time delta '{}' out of range", m.as_str()))
     }
 }
 
@@ -137,6 +147,20 @@ mod tests {
         assert!(TimeDeltaValue::from_str("foo").is_err());
     }
 
+    #[test]
+    fn test_from_str_out_of_range() {
+        let err = TimeDeltaValue::from_str("99999999d").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Time delta '99999999d' out of range, maximum is 10000000d"
+        );
+        assert!(TimeDeltaValue::from_str("-99999999d").is_err());
+        assert!(TimeDeltaValue::from_str("99999999999999999999d").is_err());
+        assert!(TimeDeltaValue::from_str("9999999999999999h").is_err());
+        assert!(TimeDeltaValue::from_str("10000000d").is_ok());
+        assert!(TimeDeltaValue::from_str("-10000000d23h59m").is_ok());
+    }
+
     #[test]
     fn test_display() {
         let td = TimeDeltaValue(TimeDelta::days(1) + TimeDelta::hours(2) + TimeDelta::minutes(3));
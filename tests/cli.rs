@@ -27,6 +27,7 @@ static ASSET_UUID: &str = "a09c9ba5-45e0-40b8-82cb-55c93ff49125";
 fn new_cmd(homedir: &Path) -> Command {
     let mut cmd = Command::new(env!("CARGO_BIN_EXE_immichctl"));
     cmd.env("HOME", homedir);
+    cmd.env_remove("IMMICHCTL_CONFIG_DIR");
     cmd
 }
 
@@ -151,6 +152,207 @@ fn test_assets_search_not_logged_in() {
         .stderr(predicate::str::contains("Error: Not logged in."));
 }
 
+#[test]
+#[serial]
+fn test_assets_search_not_logged_in_json_error() {
+    let homedir = tempfile::tempdir().unwrap();
+    let mut cmd = new_cmd(homedir.path());
+    cmd.arg("--output")
+        .arg("json")
+        .arg("assets")
+        .arg("search")
+        .arg("--id")
+        .arg(ASSET_UUID);
+    cmd.assert().failure().stderr(predicate::str::starts_with(
+        r#"{"error":{"kind":"not_logged_in","message":"Not logged in."#,
+    ));
+}
+
+#[test]
+#[serial]
+fn test_assets_search_not_logged_in_format_env() {
+    let homedir = tempfile::tempdir().unwrap();
+    let mut cmd = new_cmd(homedir.path());
+    cmd.env("IMMICHCTL_FORMAT", "json")
+        .arg("assets")
+        .arg("search")
+        .arg("--id")
+        .arg(ASSET_UUID);
+    cmd.assert().failure().stderr(predicate::str::starts_with(
+        r#"{"error":{"kind":"not_logged_in""#,
+    ));
+
+    // flag wins over env
+    let mut cmd = new_cmd(homedir.path());
+    cmd.env("IMMICHCTL_FORMAT", "json")
+        .arg("--output")
+        .arg("text")
+        .arg("assets")
+        .arg("search")
+        .arg("--id")
+        .arg(ASSET_UUID);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Error: Not logged in."));
+}
+
+/// `--count-only` prints just the number to stdout.
+#[test]
+#[serial]
+fn test_count_only_offline() {
+    let homedir = tempfile::tempdir().unwrap();
+    for args in [
+        ["tags", "assign", "vacation", "--count-only"],
+        ["albums", "assign", "Vacation", "--count-only"],
+    ] {
+        let mut cmd = new_cmd(homedir.path());
+        cmd.args(args).assert().success().stdout("0\n").stderr("");
+    }
+}
+
+#[test]
+#[serial]
+fn test_assets_list_summary_offline() {
+    let homedir = tempfile::tempdir().unwrap();
+    let mut cmd = new_cmd(homedir.path());
+    cmd.args(["assets", "list", "--summary"])
+        .assert()
+        .success()
+        .stdout("")
+        .stderr("# 0 assets\n");
+    let mut cmd = new_cmd(homedir.path());
+    cmd.args(["assets", "list", "--summary", "--quiet"])
+        .assert()
+        .success()
+        .stderr("");
+}
+
+#[test]
+#[serial]
+fn test_assets_count_json_offline() {
+    let homedir = tempfile::tempdir().unwrap();
+    let mut cmd = new_cmd(homedir.path());
+    cmd.args(["assets", "count"])
+        .assert()
+        .success()
+        .stdout("0\n");
+    let mut cmd = new_cmd(homedir.path());
+    cmd.args(["assets", "count", "--output", "json"])
+        .assert()
+        .success()
+        .stdout("{\"count\":0}\n");
+}
+
+#[test]
+#[serial]
+fn test_config_dir_env_offline() {
+    let homedir = tempfile::tempdir().unwrap();
+    let config_dir = tempfile::tempdir().unwrap();
+    let mut cmd = new_cmd(homedir.path());
+    cmd.env("IMMICHCTL_CONFIG_DIR", config_dir.path())
+        .args(["config", "show"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            config_dir.path().join("config.json").to_str().unwrap(),
+        ));
+}
+
+/// Commands working on the local selection only must not need a (valid) login.
+#[test]
+#[serial]
+fn test_local_commands_offline() {
+    let homedir = tempfile::tempdir().unwrap();
+    let config_dir = homedir.path().join(".immichctl");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(
+        config_dir.join("config.json"),
+        r#"{"server": "http://127.0.0.1:9", "apikey": "invalid\nkey"}"#,
+    )
+    .unwrap();
+    // one selected asset, so that the commands work on a non-empty selection
+    std::fs::write(
+        config_dir.join("assets.json"),
+        format!(
+            r#"{{"assets": {{"{id}": {{"checksum": "checksum", "createdAt": "2024-01-01T00:00:00Z", "duration": null, "fileCreatedAt": "2024-01-01T00:00:00Z", "fileModifiedAt": "2024-01-01T00:00:00Z", "hasMetadata": true, "height": null, "id": "{id}", "isArchived": false, "isEdited": false, "isFavorite": false, "isOffline": false, "isTrashed": false, "localDateTime": "2024-01-01T00:00:00Z", "originalFileName": "a.jpg", "originalPath": "a.jpg", "ownerId": "375d5a18-f4bf-41db-bbe4-c2a3e7da107f", "thumbhash": null, "type": "IMAGE", "updatedAt": "2024-01-01T00:00:00Z", "visibility": "timeline", "width": null}}}}}}"#,
+            id = ASSET_UUID
+        ),
+    )
+    .unwrap();
+
+    let mut cmd = new_cmd(homedir.path());
+    cmd.args(["assets", "count"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1\n"));
+    let mut cmd = new_cmd(homedir.path());
+    cmd.args(["assets", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("a.jpg\n"));
+    // server updates of the selection are only previewed with --dry-run
+    let mut cmd = new_cmd(homedir.path());
+    cmd.args(["assets", "refresh", "--dry-run"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Dry run: would refresh metadata for 1 assets.",
+        ));
+    let mut cmd = new_cmd(homedir.path());
+    cmd.args(["assets", "datetime", "--offset", "1h", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "a.jpg: 2024-01-01 00:00:00 +00:00 -> 2024-01-01 01:00:00 +00:00",
+        ));
+    // without --dry-run the server is needed
+    let mut cmd = new_cmd(homedir.path());
+    cmd.args(["assets", "datetime", "--offset", "1h"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid API key in configuration"));
+
+    let commands: &[&[&str]] = &[
+        &["assets", "list", "--format", "json"],
+        &["assets", "search", "--remove", "--favorite"],
+        &["assets", "clear", "--yes"],
+        &["assets", "schema"],
+        &["assets", "refresh"],
+        &["assets", "datetime", "--offset", "1h"],
+        &["history"],
+        &["config", "show"],
+        &["config", "show", "--output", "json"],
+    ];
+    for args in commands {
+        let mut cmd = new_cmd(homedir.path());
+        cmd.args(*args).assert().success();
+    }
+
+    // a server command fails with a clear message
+    let mut cmd = new_cmd(homedir.path());
+    cmd.args(["tags", "list"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid API key in configuration"));
+
+    let mut cmd = new_cmd(homedir.path());
+    cmd.arg("logout").assert().success();
+}
+
+#[test]
+#[serial]
+fn test_assets_review_non_interactive() {
+    let homedir = tempfile::tempdir().unwrap();
+    let mut cmd = new_cmd(homedir.path());
+    cmd.arg("assets")
+        .arg("review")
+        .write_stdin("")
+        .timeout(std::time::Duration::from_secs(10));
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "'immichctl assets review' prompts for input and needs an interactive terminal",
+    ));
+}
+
 #[test]
 #[serial]
 fn test_assets_search_id() {
@@ -158,7 +360,7 @@ fn test_assets_search_id() {
     login(homedir.path());
 
     let mut cmd = new_cmd(homedir.path());
-    cmd.arg("assets").arg("clear");
+    cmd.arg("assets").arg("clear").arg("--yes");
     cmd.assert().success();
 
     let mut cmd = new_cmd(homedir.path());
@@ -303,7 +505,7 @@ fn test_search_by_date() {
     ));
 
     let mut cmd = new_cmd(homedir.path());
-    cmd.arg("assets").arg("clear");
+    cmd.arg("assets").arg("clear").arg("--yes");
     cmd.assert().success();
 
     let mut cmd = new_cmd(homedir.path());
@@ -690,7 +892,7 @@ fn test_assets_download() {
     login(homedir.path());
 
     let mut cmd = new_cmd(homedir.path());
-    cmd.arg("assets").arg("clear");
+    cmd.arg("assets").arg("clear").arg("--yes");
     cmd.assert().success();
 
     let mut cmd = new_cmd(homedir.path());
@@ -719,7 +921,7 @@ fn test_assets_download() {
     assert_eq!(entries[0].file_name(), "20251007-121205.jpg");
 
     let mut cmd = new_cmd(homedir.path());
-    cmd.arg("assets").arg("clear");
+    cmd.arg("assets").arg("clear").arg("--yes");
     cmd.assert().success();
 }
 
@@ -802,7 +1004,7 @@ fn test_cleanup() {
 
     // check that immchctl_test_album is not used
     let mut cmd = new_cmd(homedir.path());
-    cmd.arg("assets").arg("clear");
+    cmd.arg("assets").arg("clear").arg("--yes");
     cmd.assert().success();
     let mut cmd = new_cmd(homedir.path());
     cmd.arg("assets")